@@ -0,0 +1,108 @@
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+
+use clap::Subcommand;
+use color_eyre::eyre::{Context, Result};
+use owo_colors::OwoColorize;
+use single_instance::SingleInstance;
+use subspace_sdk::farmer::{BenchmarkKind, BenchmarkReport, SLOT_DURATION};
+use subspace_sdk::Farm;
+
+use crate::commands::farm::SINGLE_INSTANCE;
+use crate::config::{config_path, parse_config};
+use crate::utils::IntoEyre;
+
+/// subcommands of the `benchmark` command
+#[derive(Debug, Subcommand)]
+pub(crate) enum BenchmarkCommands {
+    /// measures the audit of all the plotted sectors of the farm, which has to
+    /// be done every slot
+    Audit(BenchmarkArgs),
+    /// measures the proving of solutions from the plotted sectors of the
+    /// farm, which has to be done before the slot deadline to get rewarded
+    Prove(BenchmarkArgs),
+}
+
+/// arguments of the `benchmark audit` and `benchmark prove` commands
+#[derive(Debug, clap::Args)]
+pub(crate) struct BenchmarkArgs {
+    /// directory of the farm, it doesn't have to be in the config
+    pub(crate) directory: PathBuf,
+    /// number of audits of the whole farm, or of sectors to prove
+    #[arg(long, default_value = "10")]
+    pub(crate) sample_size: NonZeroUsize,
+}
+
+/// implementation of the `benchmark` command
+///
+/// runs the audit or the proving of the farm with synthetic challenges, with
+/// the farming thread pool size of the config
+pub(crate) async fn benchmark(command: BenchmarkCommands) -> Result<()> {
+    let (kind, BenchmarkArgs { directory, sample_size }) = match command {
+        BenchmarkCommands::Audit(args) => (BenchmarkKind::Audit, args),
+        BenchmarkCommands::Prove(args) => (BenchmarkKind::Prove, args),
+    };
+
+    let instance =
+        SingleInstance::new(SINGLE_INSTANCE).context("failed to initialize single instance")?;
+    if !instance.is_single() {
+        println!(
+            "{} the farmer is running, so the results are skewed by the farming",
+            "Warning:".yellow()
+        );
+    }
+
+    // farms can be benchmarked before `pulsar init`, with the default size
+    let config_path = config_path();
+    let farming_thread_pool_size = if config_path.exists() {
+        parse_config()
+            .with_context(|| format!("couldn't parse the config at `{}`", config_path.display()))?
+            .farmer
+            .farming_thread_pool_size()?
+    } else {
+        None
+    };
+
+    println!("Benchmarking the {kind} of the farm at `{}`...", directory.display());
+    let report = Farm::benchmark(&directory, kind, sample_size, farming_thread_pool_size)
+        .await
+        .into_eyre()
+        .with_context(|| format!("couldn't benchmark the farm at `{}`", directory.display()))?;
+    println!("{}", describe_benchmark(&report));
+    Ok(())
+}
+
+/// describes the results of the benchmark for the user
+pub(crate) fn describe_benchmark(report: &BenchmarkReport) -> String {
+    let BenchmarkReport {
+        kind,
+        plotted_sectors,
+        samples,
+        farming_thread_pool_size,
+        sectors_per_second,
+        sector_latency,
+        slot_time,
+        ..
+    } = report;
+
+    let mut lines = vec![format!(
+        "Benchmarked the {kind} of {plotted_sectors} plotted sectors, {samples} sample(s) with \
+         {farming_thread_pool_size} farming thread(s):"
+    )];
+    lines.push(match kind {
+        BenchmarkKind::Audit =>
+            format!("Throughput: {sectors_per_second:.2} sectors/s ({}/s)", report.throughput()),
+        BenchmarkKind::Prove => format!("Throughput: {sectors_per_second:.2} proofs/s"),
+    });
+    lines.push(format!("Latency per sector: {sector_latency:.2?}"));
+    lines.push(format!("Time per slot: {slot_time:.2?}"));
+    lines.push(match report.deadline_headroom() {
+        Some(headroom) => format!("{} {headroom:.2?} left until the slot deadline", "OK:".green()),
+        None => format!(
+            "{} the slot deadline is missed by {:.2?}, solutions are lost",
+            "Too slow:".red(),
+            *slot_time - SLOT_DURATION
+        ),
+    });
+    lines.join("\n  ")
+}
@@ -0,0 +1,248 @@
+use std::fs::{self, OpenOptions};
+use std::path::Path;
+
+use clap::Subcommand;
+use color_eyre::eyre::{eyre, Context, Result};
+use owo_colors::OwoColorize;
+use subspace_sdk::ByteSize;
+
+use crate::config::{config_path, parse_config, write_config, Config};
+
+/// subcommands of the `config` command
+#[derive(Debug, Subcommand)]
+pub(crate) enum ConfigCommands {
+    /// prints the config
+    Show,
+    /// prints the value of the given key (i.e. `farmer.farms.0.size`)
+    Get { key: String },
+    /// sets the given key to the given value (i.e. `node.name my-node`), and
+    /// writes the config back
+    Set { key: String, value: String },
+    /// checks the config, along with the directories and the extra options
+    Validate,
+    /// prints the path of the config file
+    Path,
+}
+
+/// implementation of the `config` command
+pub(crate) fn config(command: ConfigCommands) -> Result<()> {
+    match command {
+        ConfigCommands::Show => {
+            let config = parse_config().context("couldn't parse the config")?;
+            print!("{}", toml::to_string_pretty(&config).context("couldn't serialize the config")?);
+        }
+        ConfigCommands::Get { key } => {
+            let config = parse_config().context("couldn't parse the config")?;
+            let config = toml::Value::try_from(&config).context("couldn't serialize the config")?;
+            match get_value(&config, &key)? {
+                toml::Value::String(value) => println!("{value}"),
+                toml::Value::Table(table) => print!(
+                    "{}",
+                    toml::to_string_pretty(table).context("couldn't serialize the value")?
+                ),
+                value => println!("{value}"),
+            }
+        }
+        ConfigCommands::Set { key, value } => {
+            let config = parse_config().context("couldn't parse the config")?;
+            let mut config =
+                toml::Value::try_from(&config).context("couldn't serialize the config")?;
+            let value = parse_value(&value, get_value(&config, &key).ok());
+            set_value(&mut config, &key, value.clone())?;
+
+            let config: Config =
+                config.try_into().with_context(|| format!("invalid value for `{key}`"))?;
+            config.validate()?;
+            config.node.validate_extra(&config.chain).context("invalid `node.advanced`")?;
+            config.farmer.validate_extra().context("invalid `farmer.advanced`")?;
+            write_config(&config)?;
+
+            println!("`{key}` is set to {value}");
+        }
+        ConfigCommands::Validate => validate()?,
+        ConfigCommands::Path => println!("{}", config_path().display()),
+    }
+
+    Ok(())
+}
+
+/// validates the config thoroughly, reporting every problem found, instead of
+/// stopping at the first one
+fn validate() -> Result<()> {
+    let config = parse_config()
+        .with_context(|| format!("couldn't parse the config at `{}`", config_path().display()))?;
+    let mut errors = vec![];
+    let mut warnings = vec![];
+
+    if let Err(error) = config.validate() {
+        errors.push(format!("{error:#}"));
+    }
+
+    for (farm_index, farm) in config.farmer.farms.iter().enumerate() {
+        let key = format!("farmer.farms.{farm_index}");
+        if let Err(error) = check_directory(&farm.directory) {
+            errors.push(format!("`{key}.directory`: {error:#}"));
+            continue;
+        }
+        match missing_space(&farm.directory, farm.size) {
+            Ok(None) => {}
+            Ok(Some(missing)) => errors.push(format!(
+                "`{key}.size`: there is not enough free space for the farm, {missing} more is \
+                 needed"
+            )),
+            Err(error) => errors.push(format!("`{key}.size`: {error:#}")),
+        }
+    }
+
+    if let Err(error) = check_directory(&config.node.directory) {
+        errors.push(format!("`node.directory`: {error:#}"));
+    }
+
+    // node options can only be checked against a valid chain
+    if let Err(error) = config.chain.chain_spec() {
+        errors.push(format!("`chain`: {error:#}"));
+    } else {
+        match config.node.validate_extra(&config.chain) {
+            Ok(ineffective_keys) => warnings
+                .extend(ineffective_keys.into_iter().map(|key| format!("`node.advanced.{key}`"))),
+            Err(error) => errors.push(format!("`node.advanced`: {error:#}")),
+        }
+    }
+    match config.farmer.validate_extra() {
+        Ok(ineffective_keys) => warnings
+            .extend(ineffective_keys.into_iter().map(|key| format!("`farmer.advanced.{key}`"))),
+        Err(error) => errors.push(format!("`farmer.advanced`: {error:#}")),
+    }
+
+    for warning in &warnings {
+        println!(
+            "{} {warning} has no effect (either it is unknown, or set to its default value)",
+            "warning:".yellow()
+        );
+    }
+    for error in &errors {
+        println!("{} {error}", "error:".red());
+    }
+
+    if !errors.is_empty() {
+        return Err(eyre!("config is invalid, found {} error(s)", errors.len()));
+    }
+    println!("Config at `{}` is valid!", config_path().display());
+
+    Ok(())
+}
+
+/// the directory should exist, and should be writable
+fn check_directory(directory: &Path) -> Result<()> {
+    if !directory.is_dir() {
+        return Err(eyre!("directory `{}` does not exist", directory.display()));
+    }
+
+    let probe = directory.join(".pulsar-write-check");
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(&probe)
+        .with_context(|| format!("directory `{}` is not writable", directory.display()))?;
+    fs::remove_file(&probe).with_context(|| format!("couldn't remove `{}`", probe.display()))?;
+
+    Ok(())
+}
+
+/// returns the amount of space missing for the farm to grow to `farm_size`,
+/// taking the already allocated farm files into account
+fn missing_space(directory: &Path, farm_size: ByteSize) -> Result<Option<ByteSize>> {
+    let available = fs2::available_space(directory).with_context(|| {
+        format!("couldn't get the available space for `{}`", directory.display())
+    })?;
+    let used = directory_size(directory)
+        .with_context(|| format!("couldn't get the size of `{}`", directory.display()))?;
+
+    let needed = farm_size.as_u64().saturating_sub(used);
+    Ok((needed > available).then(|| ByteSize::b(needed - available)))
+}
+
+/// total size of the files in the given directory (recursively)
+fn directory_size(directory: &Path) -> std::io::Result<u64> {
+    let mut size = 0;
+    for entry in fs::read_dir(directory)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        size += if metadata.is_dir() { directory_size(&entry.path())? } else { metadata.len() };
+    }
+    Ok(size)
+}
+
+/// returns the value at the given dotted key (i.e. `farmer.farms.0.size`)
+pub(crate) fn get_value<'a>(mut value: &'a toml::Value, key: &str) -> Result<&'a toml::Value> {
+    for segment in key.split('.') {
+        value = match value {
+            toml::Value::Table(table) => table.get(segment),
+            toml::Value::Array(array) =>
+                segment.parse::<usize>().ok().and_then(|index| array.get(index)),
+            _ => None,
+        }
+        .ok_or_else(|| eyre!("key `{key}` does not exist in the config"))?;
+    }
+    Ok(value)
+}
+
+/// sets the value at the given dotted key, creating the missing tables
+pub(crate) fn set_value(
+    mut value: &mut toml::Value,
+    key: &str,
+    new_value: toml::Value,
+) -> Result<()> {
+    let segments = key.split('.').collect::<Vec<_>>();
+    let (last, parents) = segments.split_last().expect("split always yields a segment; qed");
+    if segments.iter().any(|segment| segment.is_empty()) {
+        return Err(eyre!("key `{key}` is not valid"));
+    }
+
+    for segment in parents {
+        value = match value {
+            toml::Value::Table(table) => table
+                .entry(segment.to_string())
+                .or_insert_with(|| toml::Value::Table(toml::Table::new())),
+            toml::Value::Array(array) => segment
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| array.get_mut(index))
+                .ok_or_else(|| eyre!("key `{key}` does not exist in the config"))?,
+            _ => return Err(eyre!("key `{key}` does not point into a table")),
+        };
+    }
+
+    match value {
+        toml::Value::Table(table) => {
+            table.insert(last.to_string(), new_value);
+        }
+        toml::Value::Array(array) => {
+            let entry = last
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| array.get_mut(index))
+                .ok_or_else(|| eyre!("key `{key}` does not exist in the config"))?;
+            *entry = new_value;
+        }
+        _ => return Err(eyre!("key `{key}` does not point into a table")),
+    }
+
+    Ok(())
+}
+
+/// parses the given value as a toml value, strings can be supplied without
+/// quotes
+pub(crate) fn parse_value(value: &str, existing: Option<&toml::Value>) -> toml::Value {
+    // keep string values as they are, i.e. node name `123` should not become an
+    // integer
+    if let Some(toml::Value::String(_)) = existing {
+        return toml::Value::String(value.to_owned());
+    }
+
+    toml::from_str::<toml::Table>(&format!("value = {value}"))
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| toml::Value::String(value.to_owned()))
+}
@@ -0,0 +1,163 @@
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use color_eyre::eyre::{eyre, Context, Result};
+use serde::Serialize;
+use subspace_sdk::node::{BlockNumber, Hash};
+
+use crate::summary::history::{FarmingEvent, FarmingEventKind, HistoryFile};
+use crate::summary::history_path;
+use crate::utils::date_parser;
+
+/// seconds in a day
+const DAY: u64 = 24 * 60 * 60;
+
+/// output formats of the `export` command
+#[derive(Debug, Default, Clone, Copy, clap::ValueEnum)]
+pub(crate) enum ExportFormat {
+    #[default]
+    Csv,
+    Json,
+}
+
+/// arguments of the `export` command
+#[derive(Debug, Default, clap::Args)]
+pub(crate) struct ExportArgs {
+    #[arg(long, value_enum, default_value_t)]
+    pub(crate) format: ExportFormat,
+    /// only exports the blocks and votes from the given date (i.e.
+    /// `2024-01-31`)
+    #[arg(long)]
+    pub(crate) from: Option<String>,
+    /// only exports the blocks and votes until the given date, inclusive
+    #[arg(long)]
+    pub(crate) to: Option<String>,
+    /// writes the export to the given file, instead of the stdout
+    #[arg(short, long)]
+    pub(crate) output: Option<PathBuf>,
+}
+
+/// single row of the export
+#[derive(Debug, Serialize)]
+pub(crate) struct ExportRecord {
+    pub(crate) kind: FarmingEventKind,
+    pub(crate) block_number: BlockNumber,
+    pub(crate) block_hash: Hash,
+    pub(crate) farm: Option<PathBuf>,
+    /// time of the block in RFC 3339 format
+    pub(crate) timestamp: String,
+    /// reward in SSC
+    pub(crate) reward: String,
+    /// reward in the smallest unit of SSC
+    pub(crate) reward_shannons: String,
+}
+
+impl From<FarmingEvent> for ExportRecord {
+    fn from(event: FarmingEvent) -> Self {
+        let timestamp = i64::try_from(event.timestamp)
+            .ok()
+            .and_then(|timestamp| chrono::DateTime::from_timestamp(timestamp, 0))
+            .map(|date_time| date_time.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+            .unwrap_or_default();
+        Self {
+            kind: event.kind,
+            block_number: event.block_number,
+            block_hash: event.block_hash,
+            farm: event.farm,
+            timestamp,
+            reward: event.reward.to_string(),
+            reward_shannons: event.reward.0.to_string(),
+        }
+    }
+}
+
+/// implementation of the `export` command
+///
+/// exports the blocks authored and the votes cast, which are recorded in the
+/// farming history
+pub(crate) async fn export(args: ExportArgs) -> Result<()> {
+    let ExportArgs { format, from, to, output } = args;
+    // the history is only read, so it can be exported while the farmer appends
+    // to it
+    let records = export_records(&history_path(), from.as_deref(), to.as_deref()).await?;
+
+    let contents = match format {
+        ExportFormat::Csv => to_csv(&records),
+        ExportFormat::Json => {
+            let mut json =
+                serde_json::to_string_pretty(&records).context("couldn't serialize the export")?;
+            json.push('\n');
+            json
+        }
+    };
+
+    match output {
+        Some(output) => {
+            std::fs::write(&output, contents)
+                .with_context(|| format!("couldn't write the export to `{}`", output.display()))?;
+            eprintln!("Exported {} record(s) to `{}`", records.len(), output.display());
+        }
+        None => print!("{contents}"),
+    }
+
+    Ok(())
+}
+
+/// reads the blocks and votes of the history between the given dates
+/// (inclusive), as export records
+pub(crate) async fn export_records(
+    history_path: &Path,
+    from: Option<&str>,
+    to: Option<&str>,
+) -> Result<Vec<ExportRecord>> {
+    let from = from.map(date_parser).transpose()?;
+    let to = to
+        .map(|to| {
+            // dates without time cover the whole day
+            let end_of_day = if to.contains('T') { 0 } else { DAY - 1 };
+            date_parser(to).map(|to| to + end_of_day)
+        })
+        .transpose()?;
+    if let (Some(from), Some(to)) = (from, to) {
+        if from > to {
+            return Err(eyre!("`--from` should not be later than `--to`"));
+        }
+    }
+
+    Ok(HistoryFile::read_events(history_path, from, to)
+        .await
+        .context("couldn't read the farming history, are you sure you have ran `farm` command?")?
+        .into_iter()
+        .map(ExportRecord::from)
+        .collect())
+}
+
+/// renders the records as CSV, with a header row
+pub(crate) fn to_csv(records: &[ExportRecord]) -> String {
+    let mut csv = "kind,block_number,block_hash,farm,timestamp,reward,reward_shannons\n".to_owned();
+    for record in records {
+        let farm = record.farm.as_ref().map(|farm| farm.display().to_string()).unwrap_or_default();
+        // writing to a `String` never fails
+        let _ = writeln!(
+            csv,
+            "{},{},{:?},{},{},{},{}",
+            record.kind,
+            record.block_number,
+            record.block_hash,
+            escape_csv_field(&farm),
+            record.timestamp,
+            record.reward,
+            record.reward_shannons,
+        );
+    }
+    csv
+}
+
+/// quotes the field if it contains any of the special characters of CSV
+fn escape_csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
@@ -1,30 +1,140 @@
+use std::collections::{HashMap, HashSet};
 use std::io::Write;
+use std::net::SocketAddr;
+use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 
 use color_eyre::eyre::{eyre, Context, Error, Result};
-use color_eyre::Report;
+use futures::future::OptionFuture;
 use futures::prelude::*;
-use indicatif::{ProgressBar, ProgressStyle};
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
 use owo_colors::OwoColorize;
 use single_instance::SingleInstance;
-use subspace_sdk::node::{Hash, SyncingProgress};
-use subspace_sdk::{Farmer, Node, PublicKey};
+use sp_core::crypto::{AccountId32, ByteArray};
+use subspace_sdk::farmer::{
+    BuildError, InitialPlottingProgress, SectorEvent, SectorEventKind, SectorUpdates,
+};
+use subspace_sdk::node::{BlockNumber, Event, Hash, RewardsEvent, SubspaceEvent, SyncingProgress};
+use subspace_sdk::{FarmDescription, Farmer, Node, PublicKey, RemoteNode};
 use tokio::signal;
+use tokio::sync::mpsc;
 use tokio::task::JoinHandle;
 use tracing::instrument;
 
-use crate::config::{validate_config, ChainConfig, Config};
-use crate::summary::{Summary, SummaryFile, SummaryUpdateFields};
-use crate::utils::{install_tracing, raise_fd_limit, spawn_task, IntoEyre, IntoEyreStream};
+use crate::commands::farm::info::{info, InfoArgs};
+use crate::commands::farm::output::{emit, FarmEvent, OutputFormat};
+use crate::commands::farm::plotting::{control_plotting, pause, resume};
+use crate::commands::farm::resize::{resize, ResizeArgs};
+use crate::commands::farm::scrub::{scrub, ScrubArgs};
+use crate::config::{validate_config, ChainConfig, Config, FarmConfig, FarmerConfig};
+use crate::status::run_status_server;
+use crate::summary::history::{FarmingEvent, FarmingEventKind};
+use crate::summary::{Rewards, Summary, SummaryFile, SummaryUpdateFields};
+use crate::utils::{
+    get_user_input, install_tracing, raise_fd_limit, spawn_task, IntoEyre, IntoEyreStream,
+    LogFilterReloader,
+};
+
+pub(crate) mod info;
+pub(crate) mod output;
+pub(crate) mod plotting;
+pub(crate) mod resize;
+pub(crate) mod scrub;
 
 /// allows us to detect multiple instances of the farmer and act on it
 pub(crate) const SINGLE_INSTANCE: &str = ".subspaceFarmer";
 const BATCH_BLOCKS: usize = 1000;
 const N_TASKS: usize = 10;
+/// how long to wait for the farmer and the node to close, unless configured
+const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(60);
 
 type MaybeHandles = Option<(JoinHandle<Result<()>>, JoinHandle<Result<()>>)>;
 
+/// node the farmer is connected to
+#[derive(Debug)]
+enum FarmingNode {
+    /// node running in the same process
+    Local(Arc<Node>),
+    /// node reached over RPC, configured with `farmer.node_rpc_url`
+    Remote(RemoteNode),
+}
+
+impl FarmingNode {
+    /// node running in the same process, blocks can only be scanned with it
+    fn local(&self) -> Option<Arc<Node>> {
+        match self {
+            Self::Local(node) => Some(node.clone()),
+            Self::Remote(_) => None,
+        }
+    }
+
+    /// closes the local node, or disconnects from the remote one
+    async fn close(self) -> Result<()> {
+        match self {
+            Self::Local(node) => Arc::try_unwrap(node)
+                .map_err(|_| eyre!("node is still in use, so it couldn't be closed"))?
+                .close()
+                .await
+                .into_eyre()
+                .context("couldn't close the node"),
+            Self::Remote(node) =>
+                node.close().await.into_eyre().context("couldn't disconnect from the node"),
+        }
+    }
+}
+
+/// arguments of the `farm` command
+#[derive(Debug, Default, clap::Args)]
+pub(crate) struct FarmArgs {
+    #[arg(short, long, action)]
+    pub(crate) verbose: bool,
+    #[arg(short, long, action)]
+    pub(crate) enable_domains: bool,
+    #[arg(long, action)]
+    pub(crate) no_rotation: bool,
+    /// serves the status on `/status` (JSON) and `/metrics` (Prometheus) at the
+    /// given address (i.e. `127.0.0.1:9955`)
+    #[arg(long, env = "PULSAR_STATUS_SERVER")]
+    pub(crate) status_server: Option<SocketAddr>,
+    /// serves the `farmer_*` RPC methods over WebSocket at the given address
+    /// (i.e. `127.0.0.1:9966`), so that other tools can watch the farmer
+    #[arg(long, env = "PULSAR_FARMER_RPC")]
+    pub(crate) farmer_rpc: Option<SocketAddr>,
+    /// `json` replaces the progress bars with newline-delimited JSON events on
+    /// the stdout
+    #[arg(long, value_enum, default_value_t, env = "PULSAR_OUTPUT")]
+    pub(crate) output: OutputFormat,
+    /// shorthand for `--output json`
+    #[arg(long, action)]
+    pub(crate) headless: bool,
+    /// how long to wait for the farmer and the node to close on shutdown (i.e.
+    /// `90s`), defaults to a minute
+    #[arg(long, env = "PULSAR_SHUTDOWN_TIMEOUT")]
+    pub(crate) shutdown_timeout: Option<String>,
+    #[command(subcommand)]
+    pub(crate) command: Option<FarmCommands>,
+}
+
+/// subcommands of the `farm` command, which manage the farms instead of
+/// farming
+#[derive(Debug, clap::Subcommand)]
+pub(crate) enum FarmCommands {
+    /// grows or shrinks a farm, and updates its size in the config
+    Resize(ResizeArgs),
+    /// checks the farms against their checksums, and repairs them with
+    /// `--repair`
+    Scrub(ScrubArgs),
+    /// prints what is stored in a farm, without starting anything
+    Info(InfoArgs),
+    /// pauses the plotting of the farms until `farm resume`, also of the next
+    /// start
+    Pause,
+    /// resumes the plotting paused with `farm pause`
+    Resume,
+}
+
 /// implementation of the `farm` command
 ///
 /// takes `is_verbose`, returns a [`Farmer`], [`Node`], and a [`SingleInstance`]
@@ -34,11 +144,74 @@ type MaybeHandles = Option<(JoinHandle<Result<()>>, JoinHandle<Result<()>>)>;
 /// lastly, depending on the verbosity, it subscribes to plotting progress and
 /// new solutions
 #[instrument]
-pub(crate) async fn farm(is_verbose: bool, enable_domains: bool, no_rotation: bool) -> Result<()> {
-    install_tracing(is_verbose, no_rotation);
+pub(crate) async fn farm(args: FarmArgs) -> Result<()> {
+    let FarmArgs {
+        verbose: is_verbose,
+        enable_domains,
+        no_rotation,
+        status_server,
+        farmer_rpc,
+        output,
+        headless,
+        shutdown_timeout,
+        command,
+    } = args;
+    if let Some(command) = command {
+        return match command {
+            FarmCommands::Resize(args) => resize(args),
+            FarmCommands::Scrub(args) => scrub(args).await,
+            FarmCommands::Info(args) => info(args),
+            FarmCommands::Pause => pause(),
+            FarmCommands::Resume => resume(),
+        };
+    }
+    let output = if headless { OutputFormat::Json } else { output };
+    let shutdown_timeout = match shutdown_timeout {
+        Some(shutdown_timeout) =>
+            humantime::parse_duration(&shutdown_timeout).map_err(|error| {
+                eyre!("could not parse the shutdown timeout `{shutdown_timeout}`: {error}")
+            })?,
+        None => DEFAULT_SHUTDOWN_TIMEOUT,
+    };
+    if is_verbose && output == OutputFormat::Json {
+        return Err(eyre!(
+            "verbose mode prints the logs to the stdout, it cannot be used with the JSON output"
+        ));
+    }
+    let reload_log_filter = install_tracing(is_verbose, no_rotation);
     color_eyre::install()
         .context("color eyre installment failed, it should have been the first one")?;
 
+    let result = run_farm(
+        is_verbose,
+        enable_domains,
+        status_server,
+        farmer_rpc,
+        output,
+        shutdown_timeout,
+        reload_log_filter,
+    )
+    .await;
+    if output == OutputFormat::Json {
+        match &result {
+            Ok(()) => emit(FarmEvent::Stopped),
+            Err(error) => emit(FarmEvent::Error { message: format!("{error:#}") }),
+        }
+    }
+    result
+}
+
+/// starts the node and the farmer, and farms until the app is closed
+async fn run_farm(
+    is_verbose: bool,
+    enable_domains: bool,
+    status_server: Option<SocketAddr>,
+    farmer_rpc: Option<SocketAddr>,
+    output: OutputFormat,
+    shutdown_timeout: Duration,
+    reload_log_filter: LogFilterReloader,
+) -> Result<()> {
+    let is_text_output = output == OutputFormat::Text;
     let instance = SingleInstance::new(SINGLE_INSTANCE)
         .context("Cannot take the instance lock from the OS! Aborting...")?;
     if !instance.is_single() {
@@ -49,41 +222,109 @@ pub(crate) async fn farm(is_verbose: bool, enable_domains: bool, no_rotation: bo
     // raise file limit
     raise_fd_limit();
 
-    let Config { chain, farmer: farmer_config, node: mut node_config } =
+    let Config { chain, farmer: mut farmer_config, node: mut node_config, log_level, .. } =
         validate_config().context("couldn't validate config")?;
     let reward_address = farmer_config.reward_address;
-
-    // apply advanced options (flags)
-    if enable_domains {
-        println!("Setting the {} flag for the node...", "enable_domains".underline());
-        node_config.advanced.enable_domains = true;
+    if log_level.is_some() {
+        reload_log_filter(log_level.as_deref())?;
     }
+    // farms of the reloaded config, which are applied by `wait_on_farmer`
+    let (farms_sender, farms_receiver) = mpsc::unbounded_channel();
+    #[cfg(unix)]
+    let reload_handle =
+        spawn_task("config_reloader", reload_on_hangup(reload_log_filter, farms_sender, output));
+    #[cfg(not(unix))]
+    drop((reload_log_filter, farms_sender));
 
-    println!("Starting node ...");
-    let node = Arc::new(
-        node_config
-            .clone()
-            .build(chain.clone(), is_verbose)
-            .await
-            .context("error building the node")?,
-    );
-    println!("Node started successfully!");
+    let node = match farmer_config.node_rpc_url.take() {
+        Some(url) => {
+            if enable_domains {
+                return Err(eyre!("domains cannot be enabled when farming with a remote node"));
+            }
+            if is_text_output {
+                println!("Connecting to the node at {url} ...");
+            }
+            let node = node_config
+                .clone()
+                .connect(chain.clone(), &url)
+                .await
+                .context("error connecting to the node")?;
+            match output {
+                OutputFormat::Text => println!("Connected to the node successfully!"),
+                OutputFormat::Json => emit(FarmEvent::NodeConnected { url: &url }),
+            }
+            FarmingNode::Remote(node)
+        }
+        None => {
+            // apply advanced options (flags)
+            if enable_domains {
+                if is_text_output {
+                    println!("Setting the {} flag for the node...", "enable_domains".underline());
+                }
+                node_config.advanced.enable_domains = true;
+            }
 
-    if !matches!(chain, ChainConfig::Dev) {
-        if !is_verbose {
-            subscribe_to_node_syncing(&node).await.context("couldn't subscribe to syncing")?;
-        } else {
-            node.sync().await.into_eyre().context("Node syncing failed")?;
+            if is_text_output {
+                println!("Starting node ...");
+            }
+            let node = Arc::new(
+                node_config
+                    .clone()
+                    .build(chain.clone(), is_verbose)
+                    .await
+                    .context("error building the node")?,
+            );
+            match output {
+                OutputFormat::Text => println!("Node started successfully!"),
+                OutputFormat::Json => emit(FarmEvent::NodeStarted),
+            }
+
+            if !matches!(chain, ChainConfig::Dev) {
+                if !is_verbose {
+                    subscribe_to_node_syncing(&node, output)
+                        .await
+                        .context("couldn't subscribe to syncing")?;
+                } else {
+                    node.sync().await.into_eyre().context("Node syncing failed")?;
+                }
+            }
+            FarmingNode::Local(node)
         }
+    };
+
+    if is_text_output {
+        println!("Starting farmer ...");
     }
+    let (farmer, farmer_config) =
+        build_farmer(&node, farmer_config, farmer_rpc, is_text_output).await?;
+    let farmer = Arc::new(farmer);
+    let farm_directories =
+        farmer_config.farms.iter().map(|farm| farm.directory.clone()).collect::<Vec<_>>();
 
-    let summary_file = SummaryFile::new(Some(farmer_config.farm_size))
+    let summary_file = SummaryFile::new(Some(farmer_config.total_farm_size()))
         .await
         .context("constructing new SummaryFile failed")?;
+    match output {
+        OutputFormat::Text => println!("Farmer started successfully!"),
+        OutputFormat::Json => emit(FarmEvent::FarmerStarted { farms: &farm_directories }),
+    }
+    if let (Some(address), true) = (farmer_rpc, is_text_output) {
+        println!("Farmer RPC is listening on ws://{address}");
+    }
+    let plotting_control_handle = spawn_task(
+        "plotting_controller",
+        control_plotting(farmer.plotting_controller(), farmer_config.plotting_schedule, output),
+    );
 
-    println!("Starting farmer ...");
-    let farmer = Arc::new(farmer_config.build(&node).await.context("farmer couldn't be build")?);
-    println!("Farmer started successfully!");
+    let status_server_handle = status_server.map(|address| {
+        if is_text_output {
+            println!("Status server is listening on http://{address}");
+        }
+        spawn_task(
+            "status_server",
+            run_status_server(address, node.local(), farmer.clone(), summary_file.clone()),
+        )
+    });
 
     let maybe_handles = if !is_verbose {
         // we need this to handle errors when block is not found
@@ -107,19 +348,32 @@ pub(crate) async fn farm(is_verbose: bool, enable_domains: bool, no_rotation: bo
                 farmer.clone(),
                 is_initial_progress_finished.clone(),
                 sector_size_bytes,
+                output,
             ),
         );
 
-        let solution_sub_handle = spawn_task(
-            "solution_subscriber",
-            subscribe_to_solutions(
-                summary_file.clone(),
-                node.clone(),
-                is_initial_progress_finished.clone(),
-                reward_address,
-                blocks_pruning,
+        let solution_sub_handle = match node.local() {
+            Some(node) => spawn_task(
+                "solution_subscriber",
+                subscribe_to_solutions(
+                    summary_file.clone(),
+                    node,
+                    farmer.clone(),
+                    is_initial_progress_finished.clone(),
+                    reward_address,
+                    blocks_pruning,
+                    output,
+                ),
             ),
-        );
+            None => spawn_task(
+                "solution_subscriber",
+                subscribe_to_farm_solutions(
+                    farmer.clone(),
+                    is_initial_progress_finished.clone(),
+                    output,
+                ),
+            ),
+        };
 
         Some((plotting_sub_handle, solution_sub_handle))
     } else {
@@ -127,69 +381,345 @@ pub(crate) async fn farm(is_verbose: bool, enable_domains: bool, no_rotation: bo
         None
     };
 
-    wait_on_farmer(maybe_handles, farmer, node).await.context("waiting on farmer failed")?;
+    let result = wait_on_farmer(
+        maybe_handles,
+        status_server_handle,
+        farmer,
+        node,
+        farms_receiver,
+        output,
+        shutdown_timeout,
+    )
+    .await
+    .context("waiting on farmer failed");
+    plotting_control_handle.abort();
+    #[cfg(unix)]
+    reload_handle.abort();
+    result?;
 
     Ok(())
 }
 
+/// what to do with a farm which belongs to another chain, or whose identity
+/// doesn't match
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MismatchedFarmAction {
+    /// deletes the farm, so that it is created again
+    Wipe,
+    /// farms without it until the next start
+    Skip,
+    /// stops the farmer
+    Abort,
+}
+
+pub(crate) fn mismatched_farm_action_parser(answer: &str) -> Result<MismatchedFarmAction> {
+    match answer.to_lowercase().as_str() {
+        "w" | "wipe" => Ok(MismatchedFarmAction::Wipe),
+        "s" | "skip" => Ok(MismatchedFarmAction::Skip),
+        "a" | "abort" => Ok(MismatchedFarmAction::Abort),
+        _ => Err(eyre!("could not interpret your answer. Please provide `w`, `s` or `a`.")),
+    }
+}
+
+/// builds the farmer with the farms of the config
+///
+/// farms which were created for another chain, or with another identity, are
+/// wiped or skipped if the user wants to (with the text output only)
+///
+/// returns the farmer config without the skipped farms
+async fn build_farmer(
+    node: &FarmingNode,
+    mut farmer_config: FarmerConfig,
+    farmer_rpc: Option<SocketAddr>,
+    is_text_output: bool,
+) -> Result<(Farmer, FarmerConfig)> {
+    loop {
+        let farmer = match node {
+            FarmingNode::Local(node) =>
+                farmer_config.clone().build(node.as_ref(), farmer_rpc).await,
+            FarmingNode::Remote(node) => farmer_config.clone().build(node, farmer_rpc).await,
+        };
+        let error = match farmer {
+            Ok(farmer) => return Ok((farmer, farmer_config)),
+            Err(error) => error,
+        };
+
+        let directory = match error.downcast_ref::<BuildError>() {
+            Some(
+                BuildError::ChainMismatch { directory, .. }
+                | BuildError::IdentityMismatch { directory, .. },
+            ) if is_text_output => directory.clone(),
+            _ => return Err(error.wrap_err("farmer couldn't be build")),
+        };
+        println!("{}", format!("{error:#}").red());
+        let prompt = "Do you want to [w]ipe this farm (its plotted data is deleted), [s]kip it \
+                      until the next start, or [a]bort? [w/s/a]: ";
+        match get_user_input(prompt, None, mismatched_farm_action_parser)
+            .context("prompt failed")?
+        {
+            MismatchedFarmAction::Wipe => {
+                let farm = farmer_config
+                    .farms
+                    .iter()
+                    .find(|farm| farm.directory == directory)
+                    .cloned()
+                    .ok_or_else(|| {
+                        eyre!("farm at `{}` is not in the config", directory.display())
+                    })?;
+                FarmDescription::from(farm).wipe().await.with_context(|| {
+                    format!("couldn't wipe the farm at `{}`", directory.display())
+                })?;
+                println!("Farm at `{}` is wiped, it is created again", directory.display());
+            }
+            MismatchedFarmAction::Skip => {
+                farmer_config.farms.retain(|farm| farm.directory != directory);
+                if farmer_config.farms.is_empty() {
+                    return Err(eyre!("there are no farms left to farm with"));
+                }
+                println!("Farm at `{}` is skipped", directory.display());
+            }
+            MismatchedFarmAction::Abort => return Err(error.wrap_err("farmer couldn't be build")),
+        }
+        println!("Starting farmer ...");
+    }
+}
+
 #[instrument]
 async fn wait_on_farmer(
     mut maybe_handles: MaybeHandles,
-    farmer: Arc<Farmer>,
-    node: Arc<Node>,
+    status_server_handle: Option<JoinHandle<Result<()>>>,
+    mut farmer: Arc<Farmer>,
+    node: FarmingNode,
+    mut farms_receiver: mpsc::UnboundedReceiver<Vec<FarmConfig>>,
+    output: OutputFormat,
+    shutdown_timeout: Duration,
 ) -> Result<()> {
     // node subscription can be gracefully closed with `ctrl_c` without any problem
     // (no code needed). We need graceful closing for farmer subscriptions.
-    if let Some((plotting_handle, solution_handle)) = maybe_handles.as_mut() {
-        futures::select! {
-            _ = signal::ctrl_c().fuse() => {
-               println!(
-                    "\nWill try to gracefully exit the application now. Please wait for a couple of seconds... If you press ctrl+c again, it will \
-                    try to forcefully close the app!"
-                );
-                plotting_handle.abort();
-                solution_handle.abort();
+    let shutdown = shutdown_signal();
+    tokio::pin!(shutdown);
+    loop {
+        let solution_handle =
+            OptionFuture::from(maybe_handles.as_mut().map(|(_, solution_handle)| solution_handle));
+        tokio::select! {
+            result = &mut shutdown => {
+                result?;
+                break;
             }
-            res = solution_handle.fuse() => {
+            Some(farms) = farms_receiver.recv() => update_farms(&mut farmer, farms, output).await,
+            Some(res) = solution_handle => {
                 return res.context("couldn't join subscription handle")?.context("solution subscription crashed");
             }
             // cannot inspect plotting sub for errors, since it may end and quit from select
         }
-    } else {
-        // if there are not subscriptions, just wait on the kill signal
-        signal::ctrl_c().await.context("failed to listen ctrl-c event")?
+    }
+    if let Some((plotting_handle, solution_handle)) = maybe_handles.as_mut() {
+        match output {
+            OutputFormat::Text => println!(
+                "\nWill try to gracefully exit the application now. Please wait for a couple of \
+                 seconds... If you press ctrl+c again, it will try to forcefully close the app!"
+            ),
+            OutputFormat::Json => emit(FarmEvent::ShuttingDown),
+        }
+        plotting_handle.abort();
+        solution_handle.abort();
+    }
+
+    // the status server holds references to the farmer and the node
+    if let Some(status_server_handle) = &status_server_handle {
+        status_server_handle.abort();
     }
 
     // shutting down the farmer and the node
     let graceful_close_handle = spawn_task("graceful_shutdown_listener", async move {
+        if let Some(status_server_handle) = status_server_handle {
+            let _ = status_server_handle.await;
+        }
+
         // if one of the subscriptions have not aborted yet, wait
         // Plotting might end, so we ignore result here
         if let Some((plotting_handle, solution_handle)) = maybe_handles {
             let _ = plotting_handle.await;
-            solution_handle.await.expect_err("Solution subscription never ends");
+            let _ = solution_handle.await;
         }
 
-        Arc::try_unwrap(farmer)
-            .expect("there should have been only 1 strong farmer counter")
-            .close()
-            .await
-            .expect("cannot close farmer");
-        Arc::try_unwrap(node)
-            .expect("there should have been only 1 strong node counter")
-            .close()
-            .await
-            .expect("cannot close node");
+        // the node is closed even if the farmer couldn't be
+        let mut errors = vec![];
+        match Arc::try_unwrap(farmer) {
+            Ok(farmer) =>
+                if let Err(error) = farmer.close().await {
+                    errors.push(format!("couldn't close the farmer: {error:#}"));
+                },
+            Err(_) => errors.push("farmer is still in use, so it couldn't be closed".to_owned()),
+        }
+        if let Err(error) = node.close().await {
+            errors.push(format!("{error:#}"));
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(eyre!(errors.join(", ")))
+        }
     });
 
     tokio::select! {
-        _ = graceful_close_handle => println!("gracefully closed the app!"),
-        _ = signal::ctrl_c() => println!("\nforcefully closing the app!"),
+        result = graceful_close_handle => {
+            result.context("shutdown task panicked")??;
+            if output == OutputFormat::Text {
+                println!("gracefully closed the app!");
+            }
+        }
+        result = shutdown_signal() => {
+            result?;
+            tracing::warn!("forcefully closing the app");
+            match output {
+                OutputFormat::Text => println!("\nforcefully closing the app!"),
+                OutputFormat::Json => emit(FarmEvent::Error { message: "forcefully closing the app".to_owned() }),
+            }
+        }
+        _ = tokio::time::sleep(shutdown_timeout) => {
+            tracing::error!(?shutdown_timeout, "farmer and node couldn't be closed in time, abandoning");
+            return Err(eyre!(
+                "farmer and node couldn't be closed in {}, abandoning the graceful shutdown",
+                humantime::format_duration(shutdown_timeout)
+            ));
+        }
     }
     Ok(())
 }
 
+/// adds and removes farms, so that the farmer farms the farms of the reloaded
+/// config
+///
+/// the size of the farms which are already farmed is not changed
+async fn update_farms(farmer: &mut Arc<Farmer>, farms: Vec<FarmConfig>, output: OutputFormat) {
+    let report_error = |message: String| {
+        tracing::warn!(%message, "couldn't update the farms");
+        match output {
+            OutputFormat::Text => println!("{message}"),
+            OutputFormat::Json => emit(FarmEvent::Error { message }),
+        }
+    };
+    let Some(farmer) = Arc::get_mut(farmer) else {
+        report_error("couldn't update the farms, the farmer is still starting".to_owned());
+        return;
+    };
+
+    let farmed = farmer.iter_farms().await.map(|farm| farm.directory().clone()).collect::<Vec<_>>();
+    let FarmChanges { added, removed } = farm_changes(&farmed, farms);
+
+    // farms are added first, as the last farm cannot be removed
+    for farm in added {
+        let directory = farm.directory.clone();
+        match farmer.add_farm(farm.into()).await {
+            Ok(()) => match output {
+                OutputFormat::Text =>
+                    println!("Farm at `{}` is added, plotting will start soon", directory.display()),
+                OutputFormat::Json => emit(FarmEvent::FarmAdded { directory: &directory }),
+            },
+            Err(error) =>
+                report_error(format!("couldn't add the farm at `{}`: {error}", directory.display())),
+        }
+    }
+    for directory in removed {
+        match farmer.remove_farm(&directory).await {
+            Ok(()) => match output {
+                OutputFormat::Text => println!("Farm at `{}` is removed", directory.display()),
+                OutputFormat::Json => emit(FarmEvent::FarmRemoved { directory: &directory }),
+            },
+            Err(error) => report_error(format!(
+                "couldn't remove the farm at `{}`: {error:#}",
+                directory.display()
+            )),
+        }
+    }
+}
+
+/// farms to add and remove, so that the farmed farms match the config
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct FarmChanges {
+    pub(crate) added: Vec<FarmConfig>,
+    pub(crate) removed: Vec<PathBuf>,
+}
+
+/// compares the farmed farms with the farms of the config, by their directory
+pub(crate) fn farm_changes(farmed: &[PathBuf], configured: Vec<FarmConfig>) -> FarmChanges {
+    let removed = farmed
+        .iter()
+        .filter(|directory| !configured.iter().any(|farm| farm.directory == **directory))
+        .cloned()
+        .collect();
+    let added = configured.into_iter().filter(|farm| !farmed.contains(&farm.directory)).collect();
+    FarmChanges { added, removed }
+}
+
+/// resolves once the app is asked to stop, either with ctrl-c or `SIGTERM`
+async fn shutdown_signal() -> Result<()> {
+    #[cfg(unix)]
+    {
+        let mut terminate = signal::unix::signal(signal::unix::SignalKind::terminate())
+            .context("failed to listen SIGTERM event")?;
+        tokio::select! {
+            result = signal::ctrl_c() => result.context("failed to listen ctrl-c event"),
+            _ = terminate.recv() => Ok(()),
+        }
+    }
+
+    #[cfg(not(unix))]
+    signal::ctrl_c().await.context("failed to listen ctrl-c event")
+}
+
+/// reloads the config on `SIGHUP`, applies its log level, and sends its farms
+/// to be applied to the farmer
+///
+/// the other changes of the config take effect after a restart
+#[cfg(unix)]
+async fn reload_on_hangup(
+    reload_log_filter: LogFilterReloader,
+    farms_sender: mpsc::UnboundedSender<Vec<FarmConfig>>,
+    output: OutputFormat,
+) -> Result<()> {
+    let mut hangup = signal::unix::signal(signal::unix::SignalKind::hangup())
+        .context("failed to listen SIGHUP event")?;
+
+    while hangup.recv().await.is_some() {
+        let result = validate_config().and_then(|config| {
+            reload_log_filter(config.log_level.as_deref())?;
+            // farmer is gone if the app is shutting down
+            let _ = farms_sender.send(config.farmer.farms);
+            Ok(config.log_level)
+        });
+        match (result, output) {
+            (Ok(log_level), OutputFormat::Text) => {
+                tracing::info!(?log_level, "config is reloaded");
+                println!(
+                    "\nConfig is reloaded, changes other than `log_level` and the farms take \
+                     effect after a restart"
+                );
+            }
+            (Ok(log_level), OutputFormat::Json) => {
+                tracing::info!(?log_level, "config is reloaded");
+                emit(FarmEvent::ConfigReloaded { log_level: log_level.as_deref() });
+            }
+            (Err(error), OutputFormat::Text) => {
+                tracing::warn!(%error, "couldn't reload the config");
+                println!("\ncouldn't reload the config: {error:#}");
+            }
+            (Err(error), OutputFormat::Json) => {
+                tracing::warn!(%error, "couldn't reload the config");
+                emit(FarmEvent::Error {
+                    message: format!("couldn't reload the config: {error:#}"),
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
 #[instrument]
-async fn subscribe_to_node_syncing(node: &Node) -> Result<()> {
+async fn subscribe_to_node_syncing(node: &Node, output: OutputFormat) -> Result<()> {
     let mut syncing_progress = node
         .subscribe_syncing_progress()
         .await
@@ -198,6 +728,15 @@ async fn subscribe_to_node_syncing(node: &Node) -> Result<()> {
         .into_eyre()
         .map_ok(|SyncingProgress { at, target, status: _ }| (target as _, at as _));
 
+    if output == OutputFormat::Json {
+        while let Some(stream_result) = syncing_progress.next().await {
+            let (target, at) = stream_result.context("Sync failed")?;
+            emit(FarmEvent::SyncProgress { at, target });
+        }
+        emit(FarmEvent::Synced);
+        return Ok(());
+    }
+
     if let Some(syncing_result) = syncing_progress.next().await {
         let (target_block, current_block) = syncing_result.context("Sync failed")?;
         let syncing_progress_bar = syncing_progress_bar(current_block, target_block);
@@ -219,58 +758,238 @@ async fn subscribe_to_plotting_progress(
     farmer: Arc<Farmer>,
     is_initial_progress_finished: Arc<AtomicBool>,
     sector_size_bytes: u64,
+    output: OutputFormat,
 ) -> Result<()> {
-    for (farm_id, farm) in farmer.iter_farms().await.enumerate() {
-        println!("Initial plotting for farm: #{farm_id} ({})", farm.directory().display());
+    // farms are plotted concurrently, so each of them gets its own progress bar
+    let multi_progress = MultiProgress::new();
+
+    // farms are subscribed to upfront, so that the farmer is not held while
+    // plotting and its farms can be changed
+    let farms = future::join_all(farmer.iter_farms().await.map(|farm| async move {
+        (
+            (
+                farm.directory().clone(),
+                farm.allocated_space().as_u64(),
+                farm.subscribe_initial_plotting_progress().await,
+            ),
+            (farm.directory().clone(), farm.subscribe_sector_updates().await),
+        )
+    }))
+    .await;
+    drop(farmer);
+    let (farms, replotted_farms): (Vec<_>, Vec<_>) = farms.into_iter().unzip();
+
+    let farms_progress = farms.into_iter().enumerate().map(
+        |(farm_id, (directory, allocated_space, mut plotting_progress))| {
+            let multi_progress = &multi_progress;
+            async move {
+                if output == OutputFormat::Json {
+                    while let Some(progress) = plotting_progress.next().await {
+                        emit(FarmEvent::PlottingProgress {
+                            farm: farm_id,
+                            directory: &directory,
+                            plotted_sectors: progress.current_sector,
+                            sectors_in_flight: progress.sectors_in_flight,
+                            total_sectors: progress.total_sectors,
+                        });
+                    }
+                    emit(FarmEvent::PlottingFinished { farm: farm_id, directory: &directory });
+                    return;
+                }
 
-        let mut plotting_progress = farm.subscribe_initial_plotting_progress().await;
-        let progress_bar;
+                let _ = multi_progress.println(format!(
+                    "Initial plotting for farm: #{farm_id} ({})",
+                    directory.display()
+                ));
 
-        if let Some(plotting_result) = plotting_progress.next().await {
-            let current_size = plotting_result.current_sector * sector_size_bytes;
-            progress_bar = plotting_progress_bar(current_size, farm.allocated_space().as_u64());
+                let progress_bar;
 
-            while let Some(stream_result) = plotting_progress.next().await {
-                let current_size = stream_result.current_sector * sector_size_bytes;
-                progress_bar.set_position(current_size);
+                if let Some(plotting_result) = plotting_progress.next().await {
+                    let current_size = plotting_result.current_sector * sector_size_bytes;
+                    progress_bar =
+                        multi_progress.add(plotting_progress_bar(current_size, allocated_space));
+                    progress_bar.set_message(plotting_message(farm_id, &plotting_result));
+
+                    while let Some(stream_result) = plotting_progress.next().await {
+                        let current_size = stream_result.current_sector * sector_size_bytes;
+                        progress_bar.set_position(current_size);
+                        progress_bar.set_message(plotting_message(farm_id, &stream_result));
+                    }
+                } else {
+                    // means initial plotting was already finished
+                    progress_bar =
+                        multi_progress.add(plotting_progress_bar(allocated_space, allocated_space));
+                }
+                progress_bar.set_style(
+                    ProgressStyle::with_template(
+                        "[{elapsed_precise}] {percent}% [{bar:40.green/blue}] \
+                         ({bytes}/{total_bytes}) {msg}",
+                    )
+                    .expect("hardcoded template is correct"),
+                );
+                progress_bar
+                    .finish_with_message(format!("Initial plotting finished for farm #{farm_id}!"));
             }
-        } else {
-            // means initial plotting was already finished
-            progress_bar = plotting_progress_bar(
-                farm.allocated_space().as_u64(),
-                farm.allocated_space().as_u64(),
-            );
-        }
-        progress_bar.set_style(
-            ProgressStyle::with_template(
-                "[{elapsed_precise}] {percent}% [{bar:40.green/blue}] ({bytes}/{total_bytes}) \
-                 {msg}",
-            )
-            .expect("hardcoded template is correct"),
-        );
-        progress_bar.finish_with_message("Initial plotting finished!\n");
-    }
+        },
+    );
+    future::join_all(farms_progress).await;
+
     is_initial_progress_finished.store(true, Ordering::Relaxed);
     summary_file
         .update(SummaryUpdateFields { is_plotting_finished: true, ..Default::default() })
         .await
         .context("couldn't update the summary")?;
 
+    // expired sectors are replotted until the app is closed
+    future::join_all(replotted_farms.into_iter().enumerate().map(
+        |(farm_id, (directory, sector_updates))| {
+            show_replotting_progress(farm_id, directory, sector_updates, &multi_progress, output)
+        },
+    ))
+    .await;
+
     Ok(())
 }
 
+/// sectors of a farm which wait for replotting, and the ones which are
+/// replotted since there were none
+#[derive(Debug, Default)]
+pub(crate) struct ReplottingProgress {
+    pending_sectors: HashSet<u16>,
+    pub(crate) replotted_sectors: u64,
+}
+
+impl ReplottingProgress {
+    /// applies the update of the sector, and returns whether the progress is
+    /// changed
+    pub(crate) fn update(&mut self, sector_index: u16, kind: SectorEventKind) -> bool {
+        match kind {
+            SectorEventKind::AboutToExpire | SectorEventKind::Expired =>
+                self.pending_sectors.insert(sector_index),
+            SectorEventKind::Finished { replotted: true, .. } => {
+                let is_pending = self.pending_sectors.remove(&sector_index);
+                if is_pending {
+                    self.replotted_sectors += 1;
+                }
+                is_pending
+            }
+            _ => false,
+        }
+    }
+
+    /// replaces the sectors which wait for replotting with the ones of the
+    /// current expirations, after the updates lagged behind, and returns
+    /// whether the progress is changed
+    pub(crate) fn resync(
+        &mut self,
+        expirations: impl IntoIterator<Item = (u16, SectorEventKind)>,
+    ) -> bool {
+        let pending_sectors = expirations
+            .into_iter()
+            .filter(|(_, kind)| {
+                matches!(kind, SectorEventKind::AboutToExpire | SectorEventKind::Expired)
+            })
+            .map(|(sector_index, _)| sector_index)
+            .collect::<HashSet<_>>();
+        let is_changed = pending_sectors != self.pending_sectors;
+        self.pending_sectors = pending_sectors;
+        is_changed
+    }
+
+    /// replotted sectors along with the ones which wait for replotting
+    pub(crate) fn total_sectors(&self) -> u64 {
+        self.replotted_sectors + self.pending_sectors.len() as u64
+    }
+
+    /// whether there are no sectors left to replot
+    pub(crate) fn is_finished(&self) -> bool {
+        self.pending_sectors.is_empty()
+    }
+}
+
+/// shows a progress bar (or emits the progress) whenever sectors of the farm
+/// are replotted, until the farm is closed
+async fn show_replotting_progress(
+    farm_id: usize,
+    directory: PathBuf,
+    mut sector_updates: impl Stream<Item = SectorUpdates> + Unpin,
+    multi_progress: &MultiProgress,
+    output: OutputFormat,
+) {
+    let mut progress = ReplottingProgress::default();
+    let mut progress_bar = None;
+    while let Some(update) = sector_updates.next().await {
+        let is_changed = match update {
+            SectorUpdates::Event(SectorEvent { sector_index, kind, .. }) =>
+                progress.update(sector_index, kind),
+            SectorUpdates::Resynced(expirations) => progress.resync(
+                expirations
+                    .into_iter()
+                    .map(|SectorEvent { sector_index, kind, .. }| (sector_index, kind)),
+            ),
+            _ => false,
+        };
+        if !is_changed {
+            continue;
+        }
+
+        match output {
+            OutputFormat::Json => emit(FarmEvent::ReplottingProgress {
+                farm: farm_id,
+                directory: &directory,
+                replotted_sectors: progress.replotted_sectors,
+                total_sectors: progress.total_sectors(),
+            }),
+            OutputFormat::Text => {
+                let progress_bar = progress_bar.get_or_insert_with(|| {
+                    let progress_bar = multi_progress.add(replotting_progress_bar());
+                    progress_bar.set_message(format!("replotting farm #{farm_id}"));
+                    progress_bar
+                });
+                progress_bar.set_length(progress.total_sectors());
+                progress_bar.set_position(progress.replotted_sectors);
+            }
+        }
+
+        if progress.is_finished() {
+            if let Some(progress_bar) = progress_bar.take() {
+                progress_bar
+                    .finish_with_message(format!("Replotting finished for farm #{farm_id}!"));
+            }
+            progress = ReplottingProgress::default();
+        }
+    }
+}
+
 async fn subscribe_to_solutions(
     summary_file: SummaryFile,
     node: Arc<Node>,
+    farmer: Arc<Farmer>,
     is_initial_progress_finished: Arc<AtomicBool>,
     reward_address: PublicKey,
     blocks_pruning: bool,
+    output: OutputFormat,
 ) -> Result<()> {
-    // necessary for spacing
-    println!();
+    if output == OutputFormat::Text {
+        // necessary for spacing
+        println!();
+    }
 
     let Summary { last_processed_block_num, .. } =
         summary_file.parse().await.context("parsing the summary failed")?;
+    let farms = Arc::new(
+        farmer
+            .get_info()
+            .await
+            .into_eyre()
+            .context("failed to get farmer info")?
+            .farms_info
+            .into_iter()
+            .map(|(directory, farm_info)| (farm_info.public_key, directory))
+            .collect::<FarmsByPublicKey>(),
+    );
+    // the farmer is not needed anymore, so that it can be closed on shutdown
+    drop(farmer);
 
     // first, process the stream in a parallelized fashion,
     // after that, there will be new blocks arrived
@@ -281,6 +1000,8 @@ async fn subscribe_to_solutions(
         blocks_pruning,
         summary_file.clone(),
         reward_address,
+        farms.clone(),
+        output,
         BATCH_BLOCKS,
         N_TASKS,
     )
@@ -288,18 +1009,21 @@ async fn subscribe_to_solutions(
     .context("parallel block stream couldn't be processed")?;
 
     loop {
-        let Summary { authored_count, last_processed_block_num, .. } =
+        let Summary { authored_count, vote_count, total_rewards, last_processed_block_num, .. } =
             summary_file.parse().await.context("couldn't parse summary")?;
 
         if is_initial_progress_finished.load(Ordering::Relaxed) {
-            // use carriage return to overwrite the current value
-            // instead of inserting a new line
-            print!(
-                "\rYou have farmed {authored_count} block(s), This data is derived from the first \
-                 {last_processed_block_num} blocks.",
-            );
-            // flush the stdout to make sure values are printed
-            std::io::stdout().flush().expect("Failed to flush stdout");
+            if output == OutputFormat::Text {
+                // use carriage return to overwrite the current value
+                // instead of inserting a new line
+                print!(
+                    "\rYou have farmed {authored_count} block(s) and {vote_count} vote(s), \
+                     earning {total_rewards} SSC. This data is derived from the first \
+                     {last_processed_block_num} blocks.",
+                );
+                // flush the stdout to make sure values are printed
+                std::io::stdout().flush().expect("Failed to flush stdout");
+            }
 
             // now, process the blocks without paralellization
             process_block_stream(
@@ -308,6 +1032,8 @@ async fn subscribe_to_solutions(
                 blocks_pruning,
                 summary_file.clone(),
                 reward_address,
+                farms.clone(),
+                output,
                 1,
                 1,
             )
@@ -319,6 +1045,41 @@ async fn subscribe_to_solutions(
     }
 }
 
+/// counts the solutions found by the farms
+///
+/// blocks can't be scanned for the rewards without a local node, so the
+/// farming history is not updated
+async fn subscribe_to_farm_solutions(
+    farmer: Arc<Farmer>,
+    is_initial_progress_finished: Arc<AtomicBool>,
+    output: OutputFormat,
+) -> Result<()> {
+    let mut solutions = stream::select_all(
+        future::join_all(farmer.iter_farms().await.map(|farm| farm.subscribe_new_solutions()))
+            .await,
+    );
+    // the farmer is not needed anymore, so that it can be closed on shutdown
+    drop(farmer);
+
+    if output == OutputFormat::Text {
+        println!(
+            "\nFarming with a remote node, blocks and votes are not recorded in the farming \
+             history"
+        );
+    }
+
+    let mut solution_count = 0_u64;
+    while solutions.next().await.is_some() {
+        solution_count += 1;
+        if output == OutputFormat::Text && is_initial_progress_finished.load(Ordering::Relaxed) {
+            print!("\rYour farms have found {solution_count} solution(s).");
+            std::io::stdout().flush().expect("Failed to flush stdout");
+        }
+    }
+
+    Ok(())
+}
+
 fn not_yet_processed_block_nums_stream(
     node: std::sync::Arc<Node>,
     mut last_processed_block_num: subspace_sdk::node::BlockNumber,
@@ -344,12 +1105,15 @@ fn not_yet_processed_block_nums_stream(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn process_block_stream(
-    last_processed_block_num: subspace_sdk::node::BlockNumber,
+    last_processed_block_num: BlockNumber,
     node: Arc<Node>,
     blocks_pruning: bool,
     summary_file: SummaryFile,
     reward_address: PublicKey,
+    farms: Arc<FarmsByPublicKey>,
+    output: OutputFormat,
     batch_blocks: usize,
     n_tasks: usize,
 ) -> Result<()> {
@@ -359,7 +1123,7 @@ async fn process_block_stream(
 
     stream
         .try_filter_map(|block| match node.block_hash(block).into_eyre() {
-            Ok(Some(block_hash)) => future::ok(Some(block_hash)),
+            Ok(Some(block_hash)) => future::ok(Some((block, block_hash))),
             Ok(None) if blocks_pruning => future::ok(None),
             Ok(None) =>
                 future::err(eyre!("node database is probably corrupted, try wiping the node")),
@@ -372,22 +1136,29 @@ async fn process_block_stream(
         .try_for_each(|blocks| {
             let node_clone = node.clone();
             let summary_clone = summary_file.clone();
+            let farms = farms.clone();
             async move {
                 let block_count = blocks.len() as u32;
                 // We iterate over hashes
-                let author = get_author_info_from_blocks(
+                let new_events = get_farming_events_from_blocks(
                     node_clone,
                     blocks,
                     reward_address,
+                    &farms,
                     n_tasks,
                     blocks_pruning,
                 )
                 .await
-                .context("couldn't get author info")?;
+                .context("couldn't get farming events")?;
+                if output == OutputFormat::Json {
+                    for event in &new_events {
+                        emit(FarmEvent::from(event));
+                    }
+                }
 
                 summary_clone
                     .update(SummaryUpdateFields {
-                        new_authored_count: author,
+                        new_events,
                         new_parsed_blocks: block_count,
                         ..Default::default()
                     })
@@ -400,43 +1171,132 @@ async fn process_block_stream(
         .await
 }
 
-async fn get_author_info_from_blocks(
+/// directories of the local farms, by their public keys
+type FarmsByPublicKey = HashMap<PublicKey, PathBuf>;
+
+async fn get_farming_events_from_blocks(
     node: Arc<Node>,
-    blocks: Vec<Hash>,
+    blocks: Vec<(BlockNumber, Hash)>,
     reward_address: PublicKey,
+    farms: &FarmsByPublicKey,
     n_tasks: usize,
     blocks_pruning: bool,
-) -> Result<u64> {
-    let author = futures::stream::iter(blocks)
-        // We scan each hash and find 3 things:
-        // - Total amount of rewards
-        // - Number of votes
-        // - Number of times we authored a block
-        .map(|hash| {
-            let is_author = match node
-                .block_header(hash)
-                .into_eyre()
-                .context("failed to retrieve block header from node")?
-            {
-                Some(block_header) => Ok(block_header
-                    .pre_digest
-                    .map(|pre_digest| pre_digest.solution().reward_address == reward_address)
-                    .unwrap_or_default()),
-                None if blocks_pruning => Ok(false),
-                None => Err(eyre!("node database is probably corrupted, try wiping the node")),
-            }
-            .context("couldn't get the author info from block header")?;
-
-            Result::Ok(futures::future::ok::<u64, Report>(if is_author { 1 } else { 0 }))
+) -> Result<Vec<FarmingEvent>> {
+    let mut events = futures::stream::iter(blocks)
+        // We scan each block for the blocks authored and the votes cast by us,
+        // along with their rewards
+        .map(|(number, hash)| {
+            get_farming_events_from_block(
+                &node,
+                number,
+                hash,
+                reward_address,
+                farms,
+                blocks_pruning,
+            )
         })
-        // We calculate each block in parallel
-        .try_buffer_unordered(n_tasks)
-        // After that we sum up result
-        .try_fold(0, |author, new_author| futures::future::ok(author + new_author))
+        // We scan each block in parallel
+        .buffer_unordered(n_tasks)
+        // After that we collect the results
+        .try_concat()
+        .await
+        .context("error in stream encountered in try_concat step")?;
+
+    // blocks are scanned out of order
+    events.sort_by_key(|event| event.block_number);
+    Ok(events)
+}
+
+async fn get_farming_events_from_block(
+    node: &Node,
+    block_number: BlockNumber,
+    block_hash: Hash,
+    reward_address: PublicKey,
+    farms: &FarmsByPublicKey,
+    blocks_pruning: bool,
+) -> Result<Vec<FarmingEvent>> {
+    let pre_digest = match node
+        .block_header(block_hash)
+        .into_eyre()
+        .context("failed to retrieve block header from node")?
+    {
+        Some(block_header) => block_header.pre_digest,
+        None if blocks_pruning => return Ok(vec![]),
+        None => return Err(eyre!("node database is probably corrupted, try wiping the node")),
+    };
+    // slots aren't tied to the wall-clock time on proof-of-time chains, so
+    // the time is read from the timestamp inherent of the block instead
+    let timestamp = node
+        .block_timestamp(block_hash)
+        .await
+        .into_eyre()
+        .context("failed to retrieve block timestamp from node")?
+        .map(|timestamp_millis| timestamp_millis / 1000);
+    let author = pre_digest
+        .as_ref()
+        .map(|pre_digest| pre_digest.solution())
+        .filter(|solution| solution.reward_address == reward_address)
+        .map(|solution| solution.public_key);
+
+    let reward_account = AccountId32::from(reward_address);
+    let chain_events = node
+        .get_events(Some(block_hash))
         .await
-        .context("error in stream encountered in try_fold step")?;
+        .into_eyre()
+        .context("failed to retrieve block events from node")?;
+
+    let mut block_reward = None;
+    let mut voters = vec![];
+    let mut vote_rewards = vec![];
+    for event in chain_events {
+        match event {
+            Event::Rewards(RewardsEvent::BlockReward { reward, block_author })
+                if block_author == reward_account =>
+                *block_reward.get_or_insert(Rewards(0)) += Rewards(reward),
+            Event::Rewards(RewardsEvent::VoteReward { reward, voter })
+                if voter == reward_account =>
+                vote_rewards.push(Rewards(reward)),
+            Event::Subspace(SubspaceEvent::FarmerVote { public_key, reward_address, .. })
+                if reward_address == reward_account =>
+                voters.push(<[u8; 32]>::try_from(public_key.as_slice()).ok().map(PublicKey::from)),
+            _ => {}
+        }
+    }
 
-    Ok(author)
+    let event = |kind, public_key: Option<PublicKey>, reward| FarmingEvent {
+        kind,
+        block_number,
+        block_hash,
+        farm: public_key.and_then(|public_key| farms.get(&public_key).cloned()),
+        timestamp: timestamp.unwrap_or_default(),
+        reward,
+    };
+    let mut events = vec![];
+    if author.is_some() || block_reward.is_some() {
+        events.push(event(FarmingEventKind::Block, author, block_reward.unwrap_or_default()));
+    }
+    // votes are rewarded in the order they are cast
+    let mut vote_rewards = vote_rewards.into_iter();
+    for voter in voters {
+        events.push(event(FarmingEventKind::Vote, voter, vote_rewards.next().unwrap_or_default()));
+    }
+
+    Ok(events)
+}
+
+/// progress bar for the replotting of the expired sectors
+fn replotting_progress_bar() -> ProgressBar {
+    let pb = ProgressBar::new(0);
+    pb.set_style(
+        ProgressStyle::with_template(
+            " {spinner:2.green} [{elapsed_precise}] {percent}% [{wide_bar:.cyan}] ({pos}/{len} \
+             sectors), {msg}, ETA: {eta_precise} ",
+        )
+        .expect("hardcoded template is correct")
+        .tick_strings(&["◜", "◠", "◝", "◞", "◡", "◟"])
+        .progress_chars("█▉▊▋▌▍▎▏  "),
+    );
+    pb
 }
 
 /// nice looking progress bar for the initial plotting :)
@@ -460,6 +1320,16 @@ fn plotting_progress_bar(current_size: u64, total_size: u64) -> ProgressBar {
     pb
 }
 
+/// message of the initial plotting bar, with the sectors which are being
+/// plotted
+fn plotting_message(farm_id: usize, progress: &InitialPlottingProgress) -> String {
+    match progress.sectors_in_flight {
+        0 => format!("plotting farm #{farm_id}"),
+        1 => format!("plotting farm #{farm_id} (1 sector in flight)"),
+        sectors => format!("plotting farm #{farm_id} ({sectors} sectors in flight)"),
+    }
+}
+
 /// nice looking progress bar for the syncing :)
 fn syncing_progress_bar(current_block: u64, total_blocks: u64) -> ProgressBar {
     let pb = ProgressBar::new(total_blocks);
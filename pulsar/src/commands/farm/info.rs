@@ -0,0 +1,50 @@
+use std::path::PathBuf;
+
+use color_eyre::eyre::{Context, Result};
+use subspace_sdk::farmer::{inspect, FarmInspection};
+
+use crate::config::ChainConfig;
+use crate::utils::IntoEyre;
+
+/// arguments of the `farm info` command
+#[derive(Debug, clap::Args)]
+pub(crate) struct InfoArgs {
+    /// directory of the farm, it doesn't have to be in the config
+    pub(crate) directory: PathBuf,
+}
+
+/// implementation of the `farm info` command
+///
+/// reads the farm from the disk, without starting the node or the farmer
+pub(crate) fn info(args: InfoArgs) -> Result<()> {
+    let InfoArgs { directory } = args;
+    let inspection = inspect(&directory)
+        .into_eyre()
+        .with_context(|| format!("couldn't inspect the farm at `{}`", directory.display()))?;
+    println!("{}", describe_farm(&inspection));
+    Ok(())
+}
+
+/// describes the farm for the user
+pub(crate) fn describe_farm(inspection: &FarmInspection) -> String {
+    let FarmInspection { directory, info, plotted_sectors, piece_cache_capacity, .. } = inspection;
+    let genesis_hash =
+        info.genesis_hash.iter().map(|byte| format!("{byte:02x}")).collect::<String>();
+    let chain = match ChainConfig::from_genesis_hash(&genesis_hash) {
+        Some(chain) => format!("{chain:?}"),
+        None => "unknown (or a dev chain)".to_string(),
+    };
+
+    [
+        format!("Farm at `{}`:", directory.display()),
+        format!("ID: {}", info.id),
+        format!("Genesis hash: 0x{genesis_hash}"),
+        format!("Chain: {chain}"),
+        format!("Public key: {}", info.public_key),
+        format!("Allocated space: {}", info.allocated_space),
+        format!("Pieces in sector: {}", info.pieces_in_sector),
+        format!("Plotted sectors: {plotted_sectors}"),
+        format!("Piece cache: {} ({piece_cache_capacity} pieces)", info.piece_cache_size),
+    ]
+    .join("\n  ")
+}
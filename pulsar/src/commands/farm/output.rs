@@ -0,0 +1,145 @@
+//! Machine readable output of the `farm` command.
+//!
+//! In the JSON output mode, progress bars and the other terminal output are
+//! replaced with newline-delimited JSON events on the stdout, so that the
+//! farming process can be consumed by log shippers and supervisors.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use subspace_sdk::node::{BlockNumber, Hash};
+
+use crate::summary::history::{FarmingEvent, FarmingEventKind};
+use crate::summary::Rewards;
+
+/// output format of the `farm` command
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum OutputFormat {
+    /// progress bars and messages for the terminal
+    #[default]
+    Text,
+    /// newline-delimited JSON events
+    Json,
+}
+
+/// event emitted in the JSON output mode
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub(crate) enum FarmEvent<'a> {
+    NodeStarted,
+    /// connected to the node configured with `farmer.node_rpc_url`
+    NodeConnected {
+        url: &'a str,
+    },
+    SyncProgress {
+        at: u64,
+        target: u64,
+    },
+    Synced,
+    FarmerStarted {
+        farms: &'a [PathBuf],
+    },
+    /// `sectors_in_flight` are being plotted, and are not counted in
+    /// `plotted_sectors` yet
+    PlottingProgress {
+        farm: usize,
+        directory: &'a Path,
+        plotted_sectors: u64,
+        sectors_in_flight: u64,
+        total_sectors: u64,
+    },
+    PlottingFinished {
+        farm: usize,
+        directory: &'a Path,
+    },
+    /// expired sectors of the farm are replotted, `total_sectors` counts the
+    /// replotted sectors along with the ones which wait for replotting
+    ReplottingProgress {
+        farm: usize,
+        directory: &'a Path,
+        replotted_sectors: u64,
+        total_sectors: u64,
+    },
+    /// block authored or vote cast by one of the farms, `block_timestamp` is
+    /// the unix timestamp of the block in seconds
+    Farmed {
+        kind: FarmingEventKind,
+        block_number: BlockNumber,
+        block_hash: Hash,
+        farm: Option<&'a Path>,
+        block_timestamp: u64,
+        reward: Rewards,
+    },
+    /// farm is added on `SIGHUP`, as it is added to the config
+    FarmAdded {
+        directory: &'a Path,
+    },
+    /// farm is removed on `SIGHUP`, as it is removed from the config
+    FarmRemoved {
+        directory: &'a Path,
+    },
+    /// plotting is paused with `farm pause` (`paused`), or outside of the
+    /// plotting schedule (`outside_schedule`)
+    PlottingPaused {
+        reason: &'static str,
+    },
+    /// plotting is resumed with `farm resume`, or by the plotting schedule
+    PlottingResumed,
+    /// config is reloaded on `SIGHUP`
+    ConfigReloaded {
+        log_level: Option<&'a str>,
+    },
+    Error {
+        message: String,
+    },
+    ShuttingDown,
+    Stopped,
+}
+
+impl<'a> From<&'a FarmingEvent> for FarmEvent<'a> {
+    fn from(event: &'a FarmingEvent) -> Self {
+        // the time of the block is renamed, since `timestamp` is the time of the
+        // line
+        let FarmingEvent { kind, block_number, block_hash, farm, timestamp, reward } = event;
+        Self::Farmed {
+            kind: *kind,
+            block_number: *block_number,
+            block_hash: *block_hash,
+            farm: farm.as_deref(),
+            block_timestamp: *timestamp,
+            reward: *reward,
+        }
+    }
+}
+
+/// single line of the JSON output
+#[derive(Debug, Serialize)]
+struct EventLine<'a> {
+    /// time of the event in RFC 3339 format
+    timestamp: String,
+    #[serde(flatten)]
+    event: FarmEvent<'a>,
+}
+
+/// serializes the event as a single JSON line, timestamped with the current
+/// time
+pub(crate) fn event_line(event: FarmEvent<'_>) -> serde_json::Result<String> {
+    serde_json::to_string(&EventLine {
+        timestamp: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+        event,
+    })
+}
+
+/// writes the event to the stdout as a single JSON line
+pub(crate) fn emit(event: FarmEvent<'_>) {
+    match event_line(event) {
+        Ok(line) => {
+            let mut stdout = std::io::stdout().lock();
+            // there is no one to report to if the stdout is closed
+            let _ = writeln!(stdout, "{line}");
+            let _ = stdout.flush();
+        }
+        Err(error) => tracing::warn!(%error, "couldn't serialize the farm event"),
+    }
+}
@@ -0,0 +1,154 @@
+use std::num::NonZeroUsize;
+use std::time::Duration;
+
+use chrono::NaiveTime;
+use color_eyre::eyre::{Context, Result};
+use subspace_sdk::farmer::{PlottingController, PlottingStatus};
+
+use crate::commands::farm::output::{emit, FarmEvent, OutputFormat};
+use crate::config::PlottingWindow;
+use crate::utils::plotting_paused_marker_getter;
+
+/// how often `farm pause`/`farm resume` and the plotting schedule are checked
+/// by the running farmer
+const PLOTTING_CONTROL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// implementation of the `farm pause` command
+///
+/// pauses the plotting of the running farmer, or of the next one, until
+/// `farm resume`
+pub(crate) fn pause() -> Result<()> {
+    let marker = plotting_paused_marker_getter();
+    if let Some(parent) = marker.parent() {
+        std::fs::create_dir_all(parent).context("couldn't create the data directory")?;
+    }
+    std::fs::write(&marker, "").context("couldn't pause the plotting")?;
+
+    println!(
+        "Plotting is paused, sectors which are already being plotted are finished. Run `pulsar \
+         farm resume` to resume it"
+    );
+    Ok(())
+}
+
+/// implementation of the `farm resume` command
+pub(crate) fn resume() -> Result<()> {
+    match std::fs::remove_file(plotting_paused_marker_getter()) {
+        Ok(()) => println!("Plotting is resumed"),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound =>
+            println!("Plotting is not paused"),
+        Err(error) => return Err(error).context("couldn't resume the plotting"),
+    }
+    Ok(())
+}
+
+/// how the farms should be plotted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PlottingState {
+    /// paused with `farm pause`
+    Paused,
+    /// paused, since it is outside of the windows of `farmer.plotting_schedule`
+    OutsideSchedule,
+    /// plotted, with the concurrency of the current window if it has one
+    Running {
+        sector_encoding_concurrency: Option<NonZeroUsize>,
+        sector_downloading_concurrency: Option<NonZeroUsize>,
+    },
+}
+
+impl PlottingState {
+    /// plotted with the concurrency the farmer is built with
+    const UNTHROTTLED: Self =
+        Self::Running { sector_encoding_concurrency: None, sector_downloading_concurrency: None };
+}
+
+/// how the farms should be plotted at `time`
+pub(crate) fn plotting_state(
+    schedule: &[PlottingWindow],
+    is_paused: bool,
+    time: NaiveTime,
+) -> PlottingState {
+    if is_paused {
+        return PlottingState::Paused;
+    }
+    if schedule.is_empty() {
+        return PlottingState::UNTHROTTLED;
+    }
+
+    match schedule.iter().find(|window| window.contains(time)) {
+        Some(window) => PlottingState::Running {
+            sector_encoding_concurrency: window.sector_encoding_concurrency,
+            sector_downloading_concurrency: window.sector_downloading_concurrency,
+        },
+        None => PlottingState::OutsideSchedule,
+    }
+}
+
+/// applies `farm pause`/`farm resume` and the plotting schedule to the farmer
+///
+/// never returns, so it should be aborted on shutdown
+pub(crate) async fn control_plotting(
+    controller: PlottingController,
+    schedule: Vec<PlottingWindow>,
+    output: OutputFormat,
+) {
+    let initial_status = controller.status();
+    let mut applied_state = PlottingState::UNTHROTTLED;
+    loop {
+        let is_paused = plotting_paused_marker_getter().exists();
+        let state = plotting_state(&schedule, is_paused, chrono::Local::now().time());
+        if state != applied_state {
+            apply_plotting_state(&controller, state, initial_status);
+            report_plotting_state(applied_state, state, output);
+            applied_state = state;
+        }
+
+        tokio::time::sleep(PLOTTING_CONTROL_INTERVAL).await;
+    }
+}
+
+/// applies the state to the farmer, the concurrency the farmer is built with
+/// is used unless the window has its own
+fn apply_plotting_state(
+    controller: &PlottingController,
+    state: PlottingState,
+    initial_status: PlottingStatus,
+) {
+    match state {
+        PlottingState::Paused | PlottingState::OutsideSchedule => controller.pause(),
+        PlottingState::Running { sector_encoding_concurrency, sector_downloading_concurrency } => {
+            controller.set_sector_encoding_concurrency(
+                sector_encoding_concurrency.unwrap_or(initial_status.sector_encoding_concurrency),
+            );
+            controller.set_sector_downloading_concurrency(
+                sector_downloading_concurrency
+                    .unwrap_or(initial_status.sector_downloading_concurrency),
+            );
+            controller.resume();
+        }
+    }
+}
+
+/// tells the user that the plotting is paused or resumed
+fn report_plotting_state(
+    previous_state: PlottingState,
+    state: PlottingState,
+    output: OutputFormat,
+) {
+    match (state, output) {
+        (PlottingState::Paused, OutputFormat::Text) =>
+            println!("Plotting is paused, run `pulsar farm resume` to resume it"),
+        (PlottingState::OutsideSchedule, OutputFormat::Text) =>
+            println!("Plotting is paused until the next window of the plotting schedule"),
+        (PlottingState::Paused, OutputFormat::Json) =>
+            emit(FarmEvent::PlottingPaused { reason: "paused" }),
+        (PlottingState::OutsideSchedule, OutputFormat::Json) =>
+            emit(FarmEvent::PlottingPaused { reason: "outside_schedule" }),
+        // only the concurrency is changed
+        (PlottingState::Running { .. }, _)
+            if matches!(previous_state, PlottingState::Running { .. }) =>
+            tracing::info!(?state, "plotting concurrency is changed"),
+        (PlottingState::Running { .. }, OutputFormat::Text) => println!("Plotting is resumed"),
+        (PlottingState::Running { .. }, OutputFormat::Json) => emit(FarmEvent::PlottingResumed),
+    }
+}
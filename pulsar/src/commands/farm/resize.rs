@@ -0,0 +1,84 @@
+use std::path::PathBuf;
+
+use color_eyre::eyre::{eyre, Context, Result};
+use single_instance::SingleInstance;
+use subspace_sdk::farmer::FarmResize;
+use subspace_sdk::FarmDescription;
+
+use crate::commands::farm::SINGLE_INSTANCE;
+use crate::config::{parse_config, write_config};
+use crate::utils::{get_user_input, size_parser, yes_or_no_parser, IntoEyre};
+
+/// arguments of the `farm resize` command
+#[derive(Debug, clap::Args)]
+pub(crate) struct ResizeArgs {
+    /// directory of the farm, as it is in the config
+    pub(crate) directory: PathBuf,
+    /// new size of the farm (i.e. `2TB`)
+    pub(crate) size: String,
+    /// shrinks the farm without asking for a confirmation
+    #[arg(short, long, action)]
+    pub(crate) yes: bool,
+}
+
+/// implementation of the `farm resize` command
+///
+/// resizes the farm while the farmer is stopped, and updates its size in the
+/// config, the farm is grown or shrunk once it is farmed again
+pub(crate) fn resize(args: ResizeArgs) -> Result<()> {
+    let ResizeArgs { directory, size, yes } = args;
+    let size = size_parser(&size).with_context(|| format!("invalid size `{size}`"))?;
+
+    let instance =
+        SingleInstance::new(SINGLE_INSTANCE).context("failed to initialize single instance")?;
+    if !instance.is_single() {
+        return Err(eyre!("farms cannot be resized while farming, please stop the farmer first"));
+    }
+
+    let mut config = parse_config().context("couldn't parse the config")?;
+    let cache_size = config.farmer.advanced.cache_size;
+    let farm = config
+        .farmer
+        .farms
+        .iter_mut()
+        .find(|farm| farm.directory == directory)
+        .ok_or_else(|| eyre!("there is no farm at `{}` in the config", directory.display()))?;
+
+    let description = FarmDescription::from(farm.clone());
+    let plan = description.plan_resize(size, cache_size).into_eyre()?;
+    println!("{}", describe_resize(&plan));
+    if plan.is_shrinking() && !yes {
+        let prompt = "Plotted sectors which don't fit are lost, do you want to continue? [y/n]: ";
+        if !get_user_input(prompt, None, yes_or_no_parser).context("prompt failed")? {
+            println!("Farm is not resized");
+            return Ok(());
+        }
+    }
+
+    description.resize(size, cache_size).into_eyre()?;
+    farm.size = size;
+    write_config(&config).context("couldn't update the size of the farm in the config")?;
+
+    println!(
+        "Farm at `{}` is resized to {size}! The change takes effect the next time it is farmed",
+        directory.display()
+    );
+    Ok(())
+}
+
+/// describes the change of the farm for the user
+pub(crate) fn describe_resize(plan: &FarmResize) -> String {
+    let FarmResize { current_space, new_space, current_sectors, new_sectors, .. } = *plan;
+    if plan.is_shrinking() {
+        format!(
+            "Farm is shrunk from {current_space} ({current_sectors} sectors) to {new_space} \
+             ({new_sectors} sectors), up to {} plotted sectors are dropped",
+            plan.dropped_sectors()
+        )
+    } else {
+        format!(
+            "Farm is grown from {current_space} ({current_sectors} sectors) to {new_space} \
+             ({new_sectors} sectors), the new sectors are plotted when it is farmed"
+        )
+    }
+}
@@ -0,0 +1,98 @@
+use std::path::PathBuf;
+
+use color_eyre::eyre::{eyre, Context, Result};
+use owo_colors::OwoColorize;
+use single_instance::SingleInstance;
+use subspace_sdk::farmer::ScrubReport;
+use subspace_sdk::FarmDescription;
+
+use crate::commands::farm::SINGLE_INSTANCE;
+use crate::config::parse_config;
+use crate::utils::IntoEyre;
+
+/// arguments of the `farm scrub` command
+#[derive(Debug, clap::Args)]
+pub(crate) struct ScrubArgs {
+    /// directory of the farm, as it is in the config, every farm is checked
+    /// if it is not given
+    pub(crate) directory: Option<PathBuf>,
+    /// marks corrupted sectors for replotting, and clears corrupted piece
+    /// cache elements
+    #[arg(long, action)]
+    pub(crate) repair: bool,
+}
+
+/// implementation of the `farm scrub` command
+///
+/// checks the farms while the farmer is stopped, and repairs them if asked to
+pub(crate) async fn scrub(args: ScrubArgs) -> Result<()> {
+    let ScrubArgs { directory, repair } = args;
+
+    let instance =
+        SingleInstance::new(SINGLE_INSTANCE).context("failed to initialize single instance")?;
+    if !instance.is_single() {
+        return Err(eyre!("farms cannot be scrubbed while farming, please stop the farmer first"));
+    }
+
+    let farms = parse_config().context("couldn't parse the config")?.farmer.farms;
+    let farms = match directory {
+        Some(directory) => vec![farms
+            .into_iter()
+            .find(|farm| farm.directory == directory)
+            .ok_or_else(|| eyre!("there is no farm at `{}` in the config", directory.display()))?],
+        None => farms,
+    };
+
+    let mut reports = Vec::with_capacity(farms.len());
+    for farm in farms {
+        let directory = farm.directory.clone();
+        println!("Scrubbing the farm at `{}`...", directory.display());
+        let report = FarmDescription::from(farm)
+            .scrub(repair)
+            .await
+            .into_eyre()
+            .with_context(|| format!("couldn't scrub the farm at `{}`", directory.display()))?;
+        println!("{}", describe_scrub(&report));
+        reports.push(report);
+    }
+
+    ensure_repaired(&reports)
+}
+
+/// fails if any of the farms has issues which are not repaired, so that the
+/// command exits with a non-zero code
+pub(crate) fn ensure_repaired(reports: &[ScrubReport]) -> Result<()> {
+    let unhealthy_farms =
+        reports.iter().filter(|report| !report.is_healthy() && !report.repaired).count();
+    if unhealthy_farms > 0 {
+        return Err(eyre!(
+            "{unhealthy_farms} farm(s) have issues, run `pulsar farm scrub --repair` to repair \
+             them"
+        ));
+    }
+    Ok(())
+}
+
+/// describes the findings of the scrub for the user
+pub(crate) fn describe_scrub(report: &ScrubReport) -> String {
+    let checked = format!(
+        "checked {} plotted sectors and {} piece cache elements",
+        report.plotted_sectors, report.piece_cache_elements
+    );
+    if report.is_healthy() {
+        return format!("{} {checked}, no issues found", "Healthy:".green());
+    }
+
+    let mut description =
+        format!("{} {checked}, found {} issue(s)", "Unhealthy:".red(), report.issues.len());
+    for issue in &report.issues {
+        description.push_str(&format!("\n  - {issue}"));
+    }
+    if report.repaired {
+        description.push_str(
+            "\nRepairable issues are repaired, corrupted and missing sectors are replotted the \
+             next time the farm is farmed",
+        );
+    }
+    description
+}
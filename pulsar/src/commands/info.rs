@@ -1,13 +1,40 @@
-use color_eyre::eyre::{Context, Result};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use color_eyre::eyre::{eyre, Context, Result};
 use single_instance::SingleInstance;
 
 use crate::commands::farm::SINGLE_INSTANCE;
-use crate::summary::{Summary, SummaryFile};
+use crate::summary::history::{FarmingTotals, HistoryFile};
+use crate::summary::{history_path, Summary, SummaryFile};
+use crate::utils::date_parser;
+
+/// arguments of the `info` command
+#[derive(Debug, Default, clap::Args)]
+pub(crate) struct InfoArgs {
+    /// only reports the blocks and votes since the given date (i.e.
+    /// `2024-01-31`)
+    #[arg(long, conflicts_with = "last")]
+    pub(crate) since: Option<String>,
+    /// only reports the blocks and votes in the given duration until now (i.e.
+    /// `24h`, `7days`)
+    #[arg(long)]
+    pub(crate) last: Option<String>,
+}
 
 /// implementation of the `init` command.
 ///
 /// informs the user about the current farming instance
-pub(crate) async fn info() -> Result<()> {
+pub(crate) async fn info(args: InfoArgs) -> Result<()> {
+    let since = match (args.since, args.last) {
+        (Some(since), _) => Some(date_parser(&since)?),
+        (None, Some(last)) => {
+            let last = humantime::parse_duration(&last)
+                .map_err(|error| eyre!("could not parse the duration `{last}`: {error}"))?;
+            Some(unix_now().saturating_sub(last).as_secs())
+        }
+        (None, None) => None,
+    };
+
     let instance =
         SingleInstance::new(SINGLE_INSTANCE).context("failed to initialize single instance")?;
     if !instance.is_single() {
@@ -31,11 +58,22 @@ pub(crate) async fn info() -> Result<()> {
 
     println!("You have pledged to the network: {user_space_pledged}");
 
-    println!("Farmed {authored_count} block(s)");
+    if let Some(since) = since {
+        let FarmingTotals { authored_count, vote_count, rewards } = FarmingTotals::from_events(
+            &HistoryFile::read_events(&history_path(), Some(since), None)
+                .await
+                .context("couldn't read the farming history")?,
+        );
+        println!("Farmed {authored_count} block(s) in the given period");
+        println!("Voted on {vote_count} block(s) in the given period");
+        println!("{rewards} SSC earned in the given period!");
+    } else {
+        println!("Farmed {authored_count} block(s)");
 
-    println!("Voted on {vote_count} block(s)");
+        println!("Voted on {vote_count} block(s)");
 
-    println!("{total_rewards} SSC(s) earned!",);
+        println!("{total_rewards} SSC earned!");
+    }
 
     println!("This data is derived from the first {last_block_parsed} blocks in the chain!",);
 
@@ -47,3 +85,8 @@ pub(crate) async fn info() -> Result<()> {
 
     Ok(())
 }
+
+/// time elapsed since the unix epoch
+fn unix_now() -> Duration {
+    SystemTime::now().duration_since(UNIX_EPOCH).expect("system time is after the unix epoch; qed")
+}
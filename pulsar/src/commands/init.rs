@@ -1,4 +1,5 @@
 use std::io::{BufRead, Write};
+use std::path::PathBuf;
 use std::str::FromStr;
 
 use color_eyre::eyre::{eyre, Context, Error, Result};
@@ -10,21 +11,80 @@ use strum::IntoEnumIterator;
 use subspace_sdk::PublicKey;
 use zeroize::Zeroizing;
 
+use crate::config::migration::CONFIG_VERSION;
 use crate::config::{
-    create_config, AdvancedFarmerSettings, AdvancedNodeSettings, ChainConfig, Config, FarmerConfig,
-    NodeConfig, DEFAULT_FARM_SIZE,
+    config_path, create_config, parse_config_contents, write_config, AdvancedFarmerSettings,
+    AdvancedNodeSettings, ChainConfig, Config, FarmConfig, FarmerConfig, NodeConfig,
+    DEFAULT_FARM_SIZE, MIN_FARM_SIZE,
 };
 use crate::utils::{
     directory_parser, farm_directory_getter, get_user_input, node_directory_getter,
-    node_name_parser, print_ascii_art, print_run_executable_command, print_version,
-    reward_address_parser, size_parser, yes_or_no_parser,
+    node_name_parser, non_interactive_directory_parser, print_ascii_art,
+    print_run_executable_command, print_version, reward_address_parser, size_parser,
+    yes_or_no_parser,
 };
 
+/// arguments of the `init` command
+///
+/// if any of them is supplied, `init` runs non-interactively, and fails instead
+/// of prompting the user for the missing or invalid values
+#[derive(Debug, Default, clap::Args)]
+pub(crate) struct InitArgs {
+    /// farmer/reward address (SS58)
+    #[arg(long, env = "PULSAR_REWARD_ADDRESS")]
+    pub(crate) reward_address: Option<String>,
+    /// farm directory, can be repeated (or comma separated) for multiple farms
+    #[arg(long = "farm-dir", env = "PULSAR_FARM_DIR", value_delimiter = ',')]
+    pub(crate) farm_directories: Vec<PathBuf>,
+    /// farm size, either given once per `--farm-dir`, or omitted for the
+    /// default size
+    #[arg(long = "farm-size", env = "PULSAR_FARM_SIZE", value_delimiter = ',')]
+    pub(crate) farm_sizes: Vec<String>,
+    /// node directory
+    #[arg(long = "node-dir", env = "PULSAR_NODE_DIR")]
+    pub(crate) node_directory: Option<PathBuf>,
+    /// chain to farm, or the path of a chain spec JSON file
+    #[arg(long, env = "PULSAR_CHAIN")]
+    pub(crate) chain: Option<String>,
+    /// node name to be identified on the network
+    #[arg(long, env = "PULSAR_NODE_NAME")]
+    pub(crate) node_name: Option<String>,
+    /// seed `settings.toml`, the other arguments override its values
+    #[arg(long, env = "PULSAR_FROM_FILE")]
+    pub(crate) from_file: Option<PathBuf>,
+    /// create the missing farm and node directories
+    #[arg(long, env = "PULSAR_CREATE_DIRS")]
+    pub(crate) create_dirs: bool,
+}
+
+impl InitArgs {
+    /// whether any of the arguments is supplied
+    pub(crate) fn is_non_interactive(&self) -> bool {
+        self.reward_address.is_some()
+            || !self.farm_directories.is_empty()
+            || !self.farm_sizes.is_empty()
+            || self.node_directory.is_some()
+            || self.chain.is_some()
+            || self.node_name.is_some()
+            || self.from_file.is_some()
+            || self.create_dirs
+    }
+}
+
 /// implementation of the `init` command
 ///
 /// prints a very cool ascii art,
-/// creates a config file from the user inputs
-pub(crate) fn init() -> Result<()> {
+/// creates a config file from the user inputs, or from the given arguments
+pub(crate) fn init(args: InitArgs) -> Result<()> {
+    if args.is_non_interactive() {
+        // build the config first, so that the existing config is left untouched
+        // on invalid arguments
+        let config = get_config_from_args(args)?;
+        write_config(&config)?;
+        println!("Configuration has been generated at {}", config_path().display());
+        return Ok(());
+    }
+
     let (mut config_file, config_path) = create_config()?;
     print_ascii_art();
     print_version();
@@ -43,6 +103,131 @@ pub(crate) fn init() -> Result<()> {
     Ok(())
 }
 
+/// builds the config from the arguments (and the seed file, if supplied),
+/// validating every value with the same parsers used in the interactive mode
+pub(crate) fn get_config_from_args(args: InitArgs) -> Result<Config> {
+    let InitArgs {
+        reward_address,
+        farm_directories,
+        farm_sizes,
+        node_directory,
+        chain,
+        node_name,
+        from_file,
+        create_dirs,
+    } = args;
+
+    let seed = from_file
+        .map(|path| -> Result<Config> {
+            let content = std::fs::read_to_string(&path)
+                .with_context(|| format!("couldn't read the seed file `{}`", path.display()))?;
+            parse_config_contents(&content)
+                .with_context(|| format!("couldn't parse the seed file `{}`", path.display()))
+        })
+        .transpose()?;
+    let (seed_farmer, seed_node, seed_chain) = match seed {
+        Some(Config { farmer, node, chain, .. }) => (Some(farmer), Some(node), Some(chain)),
+        None => (None, None, None),
+    };
+
+    let reward_address = match (reward_address, &seed_farmer) {
+        (Some(address), _) =>
+            reward_address_parser(&address).context("invalid `--reward-address`")?,
+        (None, Some(seed_farmer)) => seed_farmer.reward_address,
+        (None, None) => return Err(eyre!("`--reward-address` is required")),
+    };
+
+    let farms = if farm_directories.is_empty() {
+        if !farm_sizes.is_empty() {
+            return Err(eyre!("`--farm-size` can't be used without `--farm-dir`"));
+        }
+        match &seed_farmer {
+            Some(seed_farmer) => seed_farmer.farms.clone(),
+            None => vec![FarmConfig {
+                directory: farm_directory_getter(),
+                size: DEFAULT_FARM_SIZE,
+                cache_size: None,
+            }],
+        }
+    } else {
+        let sizes = match farm_sizes.len() {
+            0 => vec![DEFAULT_FARM_SIZE; farm_directories.len()],
+            n if n == farm_directories.len() => farm_sizes
+                .iter()
+                .map(|size| {
+                    size_parser(size).with_context(|| format!("invalid `--farm-size` `{size}`"))
+                })
+                .collect::<Result<_>>()?,
+            _ => return Err(eyre!("`--farm-size` should be given once per `--farm-dir`")),
+        };
+        farm_directories
+            .into_iter()
+            .zip(sizes)
+            .map(|(directory, size)| FarmConfig { directory, size, cache_size: None })
+            .collect()
+    };
+    let mut validated_farms: Vec<FarmConfig> = Vec::with_capacity(farms.len());
+    for (farm_index, FarmConfig { directory, size, cache_size }) in farms.into_iter().enumerate() {
+        let directory = non_interactive_directory_parser(&directory, create_dirs)
+            .with_context(|| format!("invalid directory for farm #{farm_index}"))?;
+        if validated_farms.iter().any(|farm| farm.directory == directory) {
+            return Err(eyre!("directory of farm #{farm_index} is already used by another farm"));
+        }
+        if size < MIN_FARM_SIZE {
+            return Err(eyre!("size of farm #{farm_index} should be bigger than {MIN_FARM_SIZE}!"));
+        }
+        validated_farms.push(FarmConfig { directory, size, cache_size });
+    }
+
+    let node_name = match (node_name, &seed_node) {
+        (Some(node_name), _) => node_name_parser(&node_name).context("invalid `--node-name`")?,
+        (None, Some(seed_node)) =>
+            node_name_parser(&seed_node.name).context("invalid node name in the seed file")?,
+        (None, None) => node_name_parser(&whoami::username())
+            .context("`--node-name` is required, the default node name can't be used")?,
+    };
+
+    let node_directory = node_directory
+        .or_else(|| seed_node.as_ref().map(|node| node.directory.clone()))
+        .unwrap_or_else(node_directory_getter);
+    let node_directory = non_interactive_directory_parser(&node_directory, create_dirs)
+        .context("invalid node directory")?;
+
+    let chain = match (chain, seed_chain) {
+        (Some(chain), _) => chain_parser(&chain).context("invalid `--chain`")?,
+        (None, Some(seed_chain @ ChainConfig::Custom { .. })) => {
+            seed_chain.chain_spec().context("invalid chain in the seed file")?;
+            seed_chain
+        }
+        (None, Some(seed_chain)) => seed_chain,
+        (None, None) => ChainConfig::default(),
+    };
+
+    let (node_rpc_url, plotting_schedule, advanced) = seed_farmer
+        .map(|farmer| (farmer.node_rpc_url, farmer.plotting_schedule, farmer.advanced))
+        .unwrap_or_default();
+    let farmer_config = FarmerConfig {
+        farms: validated_farms,
+        reward_address,
+        node_rpc_url,
+        plotting_schedule,
+        advanced,
+    };
+    let node_config = NodeConfig {
+        name: node_name,
+        directory: node_directory,
+        advanced: seed_node.map(|node| node.advanced).unwrap_or_default(),
+    };
+
+    Ok(Config {
+        version: CONFIG_VERSION,
+        farmer: farmer_config,
+        node: node_config,
+        chain,
+        log_level: None,
+    })
+}
+
 /// gets the necessary information from user, and writes them to the given
 /// configuration file
 fn get_config_from_user_inputs() -> Result<Config> {
@@ -67,17 +252,6 @@ fn get_config_from_user_inputs() -> Result<Config> {
         node_name_parser,
     )?;
 
-    // get farm directory
-    let default_farm_loc = farm_directory_getter();
-    let farm_directory = get_user_input(
-        &format!(
-            "Specify a path for storing farm files (press enter to use the default: \
-             `{default_farm_loc:?}`): ",
-        ),
-        Some(default_farm_loc),
-        directory_parser,
-    )?;
-
     let default_node_loc = node_directory_getter();
     let node_directory = get_user_input(
         &format!(
@@ -88,32 +262,26 @@ fn get_config_from_user_inputs() -> Result<Config> {
         directory_parser,
     )?;
 
-    // get farm size
-    let farm_size = get_user_input(
-        &format!(
-            "Specify a farm size (defaults to `{DEFAULT_FARM_SIZE}`, press enter to use the \
-             default): "
-        ),
-        Some(DEFAULT_FARM_SIZE),
-        size_parser,
-    )?;
+    // get farms
+    let farms = get_farms_from_user_inputs()?;
 
     // get chain
     let default_chain = ChainConfig::Gemini3h;
     let chain = get_user_input(
         &format!(
-            "Specify the chain to farm. Available options are: {:?}. \n Defaults to \
-             `{default_chain:?}`, press enter to use the default:",
+            "Specify the chain to farm. Available options are: {:?}, or the path of a chain spec \
+             JSON file. \n Defaults to `{default_chain:?}`, press enter to use the default:",
             ChainConfig::iter().collect::<Vec<_>>()
         ),
         Some(default_chain),
-        ChainConfig::from_str,
+        chain_parser,
     )?;
 
     let farmer_config = FarmerConfig {
-        farm_size,
-        farm_directory,
+        farms,
         reward_address,
+        node_rpc_url: None,
+        plotting_schedule: vec![],
         advanced: AdvancedFarmerSettings::default(),
     };
     let node_config = NodeConfig {
@@ -122,7 +290,74 @@ fn get_config_from_user_inputs() -> Result<Config> {
         advanced: AdvancedNodeSettings::default(),
     };
 
-    Ok(Config { farmer: farmer_config, node: node_config, chain })
+    Ok(Config {
+        version: CONFIG_VERSION,
+        farmer: farmer_config,
+        node: node_config,
+        chain,
+        log_level: None,
+    })
+}
+
+/// parses the chain, custom chain specs are loaded to make sure they are valid
+fn chain_parser(chain: &str) -> Result<ChainConfig> {
+    let chain = ChainConfig::from_str(chain)?;
+    if let ChainConfig::Custom { .. } = chain {
+        chain.chain_spec()?;
+    }
+    Ok(chain)
+}
+
+/// gets the farm directories and sizes from the user, until user says no more
+/// farms should be added
+fn get_farms_from_user_inputs() -> Result<Vec<FarmConfig>> {
+    let mut farms: Vec<FarmConfig> = vec![];
+
+    loop {
+        let farm_index = farms.len();
+
+        // only the first farm has a default location
+        let directory = if farm_index == 0 {
+            let default_farm_loc = farm_directory_getter();
+            get_user_input(
+                &format!(
+                    "Specify a path for storing farm files (press enter to use the default: \
+                     `{default_farm_loc:?}`): ",
+                ),
+                Some(default_farm_loc),
+                directory_parser,
+            )?
+        } else {
+            get_user_input(
+                &format!("Specify a path for storing files of farm #{farm_index}: "),
+                None,
+                |location| {
+                    let directory = directory_parser(location)?;
+                    if farms.iter().any(|farm| farm.directory == directory) {
+                        return Err(eyre!("this directory is already used by another farm!"));
+                    }
+                    Ok(directory)
+                },
+            )?
+        };
+
+        let size = get_user_input(
+            &format!(
+                "Specify a farm size (defaults to `{DEFAULT_FARM_SIZE}`, press enter to use the \
+                 default): "
+            ),
+            Some(DEFAULT_FARM_SIZE),
+            size_parser,
+        )?;
+
+        farms.push(FarmConfig { directory, size, cache_size: None });
+
+        let add_another_farm =
+            get_user_input("Do you want to add another farm? [y/n]: ", None, yes_or_no_parser)?;
+        if !add_another_farm {
+            return Ok(farms);
+        }
+    }
 }
 
 fn generate_or_get_reward_address(reward_address_exist: bool) -> Result<PublicKey> {
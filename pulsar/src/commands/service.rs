@@ -0,0 +1,314 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use clap::Subcommand;
+use color_eyre::eyre::{eyre, Context, Result};
+
+use crate::config::{config_path, parse_config, parse_config_contents, Config};
+
+/// name of the systemd unit
+const SERVICE_NAME: &str = "pulsar.service";
+
+/// open files limit of the service, farming needs a lot of file descriptors
+/// (see [`crate::utils::raise_fd_limit`])
+const OPEN_FILES_LIMIT: u64 = 1_048_576;
+
+/// config path relative to the home directory, at the default location of
+/// [`config_path`] on Linux
+const USER_CONFIG_PATH: &str = ".config/pulsar/settings.toml";
+
+/// seconds to wait before restarting the service after a failure
+const RESTART_DELAY_SECS: u64 = 10;
+
+/// seconds systemd waits for the farmer and the node to close, before killing
+/// them
+const STOP_TIMEOUT_SECS: u64 = 120;
+
+/// subcommands of the `service` command
+#[derive(Debug, Subcommand)]
+pub(crate) enum ServiceCommands {
+    /// installs and enables a systemd unit running `pulsar farm --headless`
+    Install {
+        /// installs a system-wide unit, instead of a user unit
+        #[arg(long, action)]
+        system: bool,
+        /// prints the unit and the commands, instead of running them
+        #[arg(long, action)]
+        dry_run: bool,
+    },
+    /// stops, disables and removes the systemd unit
+    Uninstall {
+        /// uninstalls the system-wide unit, instead of the user unit
+        #[arg(long, action)]
+        system: bool,
+        /// prints the commands, instead of running them
+        #[arg(long, action)]
+        dry_run: bool,
+    },
+    /// prints the status of the systemd unit
+    Status {
+        /// prints the status of the system-wide unit, instead of the user unit
+        #[arg(long, action)]
+        system: bool,
+    },
+}
+
+/// whether the unit is installed for the user or system-wide
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ServiceScope {
+    User,
+    System,
+}
+
+impl ServiceScope {
+    fn new(system: bool) -> Self {
+        if system {
+            Self::System
+        } else {
+            Self::User
+        }
+    }
+
+    /// path of the unit file
+    fn unit_path(self) -> Result<PathBuf> {
+        Ok(match self {
+            Self::User => dirs::config_dir()
+                .ok_or_else(|| eyre!("couldn't get the config directory"))?
+                .join("systemd/user")
+                .join(SERVICE_NAME),
+            Self::System => Path::new("/etc/systemd/system").join(SERVICE_NAME),
+        })
+    }
+
+    /// `systemctl` with the given arguments
+    fn systemctl<'a>(self, args: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+        let mut command = vec!["systemctl".to_owned()];
+        if self == Self::User {
+            command.push("--user".to_owned());
+        }
+        command.extend(args.into_iter().map(ToOwned::to_owned));
+        command
+    }
+}
+
+/// implementation of the `service` command
+pub(crate) fn service(command: ServiceCommands) -> Result<()> {
+    match command {
+        ServiceCommands::Install { system, dry_run } => install(ServiceScope::new(system), dry_run),
+        ServiceCommands::Uninstall { system, dry_run } =>
+            uninstall(ServiceScope::new(system), dry_run),
+        ServiceCommands::Status { system } => {
+            let scope = ServiceScope::new(system);
+            let unit_path = scope.unit_path()?;
+            if !unit_path.exists() {
+                println!(
+                    "`{SERVICE_NAME}` is not installed (expected at `{}`)",
+                    unit_path.display()
+                );
+                return Ok(());
+            }
+            println!("`{SERVICE_NAME}` is installed at `{}`", unit_path.display());
+            // `systemctl status` exits with a non-zero code for stopped units
+            let _ = run(&scope.systemctl(["status", "--no-pager", SERVICE_NAME]));
+            Ok(())
+        }
+    }
+}
+
+fn install(scope: ServiceScope, dry_run: bool) -> Result<()> {
+    // system units are installed with `sudo`, but they run as the user who
+    // invoked it and not as root
+    let user = match scope {
+        ServiceScope::User => whoami::username(),
+        ServiceScope::System => std::env::var("SUDO_USER").unwrap_or_else(|_| whoami::username()),
+    };
+    let config = user_config(&user)?;
+    config.validate()?;
+    let executable = std::env::current_exe().context("couldn't get the path of pulsar")?;
+    let unit = unit_file(&config, &executable, scope, &user);
+    let unit_path = scope.unit_path()?;
+    let commands =
+        [scope.systemctl(["daemon-reload"]), scope.systemctl(["enable", "--now", SERVICE_NAME])];
+
+    if dry_run {
+        println!("# {}", unit_path.display());
+        print!("{unit}");
+        println!();
+        for command in &commands {
+            println!("$ {}", command.join(" "));
+        }
+        return Ok(());
+    }
+
+    ensure_systemd()?;
+    if let Some(parent) = unit_path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("couldn't create `{}`", parent.display()))?;
+    }
+    fs::write(&unit_path, unit)
+        .with_context(|| format!("couldn't write the unit to `{}`", unit_path.display()))?;
+    for command in &commands {
+        run(command)?;
+    }
+
+    println!("`{SERVICE_NAME}` is installed at `{}` and started!", unit_path.display());
+    if scope == ServiceScope::User {
+        println!(
+            "User services are stopped when you log out, run `loginctl enable-linger {user}` to \
+             keep farming"
+        );
+    }
+    println!("Check its status with `pulsar service status{}`", system_flag(scope));
+
+    Ok(())
+}
+
+/// parses the config of the user the unit runs as
+///
+/// under `sudo`, the config directory is the one of root, so the config is read
+/// from the home directory of the user instead, and it is migrated only in
+/// memory, so that it isn't rewritten as root
+fn user_config(user: &str) -> Result<Config> {
+    if user == whoami::username() {
+        return parse_config().with_context(|| {
+            format!(
+                "couldn't parse the config at `{}`, please run `pulsar init` first",
+                config_path().display()
+            )
+        });
+    }
+
+    let config_path = home_dir_of(user)?.join(USER_CONFIG_PATH);
+    let contents = fs::read_to_string(&config_path).with_context(|| {
+        format!(
+            "couldn't read the config of `{user}` at `{}`, please run `pulsar init` as `{user}` \
+             first",
+            config_path.display()
+        )
+    })?;
+    parse_config_contents(&contents)
+        .with_context(|| format!("couldn't parse the config at `{}`", config_path.display()))
+}
+
+/// home directory of the user, from the passwd database
+fn home_dir_of(user: &str) -> Result<PathBuf> {
+    let output = Command::new("getent")
+        .args(["passwd", user])
+        .output()
+        .with_context(|| format!("couldn't run `getent passwd {user}`"))?;
+    if !output.status.success() {
+        return Err(eyre!("couldn't find the user `{user}`"));
+    }
+    home_dir_from_passwd(&String::from_utf8_lossy(&output.stdout))
+        .ok_or_else(|| eyre!("couldn't find the home directory of `{user}`"))
+}
+
+/// home directory in the passwd entry
+/// (`name:password:uid:gid:gecos:home:shell`)
+pub(crate) fn home_dir_from_passwd(entry: &str) -> Option<PathBuf> {
+    entry.trim_end().split(':').nth(5).filter(|home| !home.is_empty()).map(PathBuf::from)
+}
+
+fn uninstall(scope: ServiceScope, dry_run: bool) -> Result<()> {
+    let unit_path = scope.unit_path()?;
+    let disable = scope.systemctl(["disable", "--now", SERVICE_NAME]);
+    let reload = scope.systemctl(["daemon-reload"]);
+
+    if dry_run {
+        println!("$ {}", disable.join(" "));
+        println!("$ rm {}", unit_path.display());
+        println!("$ {}", reload.join(" "));
+        return Ok(());
+    }
+
+    ensure_systemd()?;
+    if !unit_path.exists() {
+        return Err(eyre!("`{SERVICE_NAME}` is not installed at `{}`", unit_path.display()));
+    }
+    run(&disable)?;
+    fs::remove_file(&unit_path)
+        .with_context(|| format!("couldn't remove `{}`", unit_path.display()))?;
+    run(&reload)?;
+
+    println!("`{SERVICE_NAME}` is uninstalled!");
+    Ok(())
+}
+
+/// generates the systemd unit running `pulsar farm --headless`
+pub(crate) fn unit_file(
+    config: &Config,
+    executable: &Path,
+    scope: ServiceScope,
+    user: &str,
+) -> String {
+    let (user_line, wanted_by) = match scope {
+        ServiceScope::User => (String::new(), "default.target"),
+        ServiceScope::System => (format!("User={user}\n"), "multi-user.target"),
+    };
+
+    format!(
+        "[Unit]
+Description=Subspace farmer (pulsar)
+Wants=network-online.target
+After=network-online.target
+
+[Service]
+Type=simple
+{user_line}ExecStart={executable} farm --headless
+WorkingDirectory={working_directory}
+Restart=on-failure
+RestartSec={RESTART_DELAY_SECS}
+LimitNOFILE={OPEN_FILES_LIMIT}
+TimeoutStopSec={STOP_TIMEOUT_SECS}
+
+[Install]
+WantedBy={wanted_by}
+",
+        executable = quote_exec_path(executable),
+        working_directory = escape_specifiers(&config.node.directory),
+    )
+}
+
+/// quotes the path for `ExecStart=`, so that paths with spaces, quotes, systemd
+/// specifiers or variables are passed as is
+fn quote_exec_path(path: &Path) -> String {
+    let escaped =
+        escape_specifiers(path).replace('\\', "\\\\").replace('"', "\\\"").replace('$', "$$");
+    format!("\"{escaped}\"")
+}
+
+/// escapes systemd specifiers (i.e. `%h`) in the path
+fn escape_specifiers(path: &Path) -> String {
+    path.display().to_string().replace('%', "%%")
+}
+
+fn system_flag(scope: ServiceScope) -> &'static str {
+    match scope {
+        ServiceScope::User => "",
+        ServiceScope::System => " --system",
+    }
+}
+
+/// services are only supported with systemd
+fn ensure_systemd() -> Result<()> {
+    if !cfg!(target_os = "linux") || !Path::new("/run/systemd/system").exists() {
+        return Err(eyre!(
+            "services are only supported on Linux with systemd, use `--dry-run` to print the unit"
+        ));
+    }
+    Ok(())
+}
+
+/// runs the command, and fails if it exits with an error
+fn run(command: &[String]) -> Result<()> {
+    let (program, args) = command.split_first().expect("commands are never empty; qed");
+    let status = Command::new(program)
+        .args(args)
+        .status()
+        .with_context(|| format!("couldn't run `{}`", command.join(" ")))?;
+    if !status.success() {
+        return Err(eyre!("`{}` failed with {status}", command.join(" ")));
+    }
+    Ok(())
+}
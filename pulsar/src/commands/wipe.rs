@@ -69,13 +69,18 @@ async fn wipe(
             }
         };
 
-        // TODO: modify here when supporting multi-farm
-        // if config can be read, delete the farmer using the path in the config, else,
-        // delete the default location
+        // if config can be read, delete every farm using the paths in the config,
+        // else, delete the default location
         if let Some(config) = config {
-            let _ = FarmDescription::new(config.farmer.farm_directory, config.farmer.farm_size)
-                .wipe()
-                .await;
+            for farm in config.farmer.farms {
+                let farm_directory = farm.directory.clone();
+                if FarmDescription::from(farm).wipe().await.is_err() {
+                    println!(
+                        "Skipping wiping farm at `{}`, could not delete the directory...",
+                        farm_directory.display()
+                    );
+                }
+            }
         } else {
             let _ = tokio::fs::remove_dir_all(farm_directory_getter()).await;
         }
@@ -83,8 +88,8 @@ async fn wipe(
 
     if wipe_summary {
         match delete_summary() {
-            Ok(_) => println!("deleted the summary file"),
-            Err(_) => println!("Skipping wiping summary, could not find the file..."),
+            Ok(_) => println!("deleted the farming history"),
+            Err(_) => println!("Skipping wiping farming history, could not find the file..."),
         }
     }
 
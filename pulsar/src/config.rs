@@ -1,17 +1,26 @@
 use std::fs::{create_dir_all, remove_file, File};
-use std::num::NonZeroU8;
-use std::path::PathBuf;
+use std::io::Write;
+use std::net::SocketAddr;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
 
+use chrono::NaiveTime;
 use color_eyre::eyre::{eyre, Report, Result, WrapErr};
 use derivative::Derivative;
 use serde::{Deserialize, Serialize};
 use strum_macros::EnumIter;
-use subspace_sdk::farmer::Farmer;
-use subspace_sdk::node::{DomainConfigBuilder, DsnBuilder, NetworkBuilder, Node, Role};
-use subspace_sdk::{chain_spec, ByteSize, FarmDescription, PublicKey};
+use subspace_sdk::farmer::{Farmer, FarmerNode, PieceCacheSize, RemoteNode};
+use subspace_sdk::node::{ChainSpec, DomainConfigBuilder, DsnBuilder, NetworkBuilder, Node, Role};
+use subspace_sdk::{
+    chain_spec, ByteSize, FarmDescription, Multiaddr, MultiaddrWithPeerId, NodeBuilder, PublicKey,
+};
 use tracing::instrument;
 
-use crate::utils::IntoEyre;
+use crate::utils::{log_filter, IntoEyre};
+
+pub(crate) mod migration;
+
+use self::migration::{migrate_config, migrate_file, CONFIG_VERSION};
 
 /// defaults for the user config file
 pub(crate) const DEFAULT_FARM_SIZE: ByteSize = ByteSize::gb(2);
@@ -20,9 +29,16 @@ pub(crate) const MIN_FARM_SIZE: ByteSize = ByteSize::gb(2);
 /// structure of the config toml file
 #[derive(Deserialize, Serialize, Debug)]
 pub(crate) struct Config {
+    /// version of the config schema, see [`migration`]
+    #[serde(default)]
+    pub(crate) version: u32,
     pub(crate) chain: ChainConfig,
     pub(crate) farmer: FarmerConfig,
     pub(crate) node: NodeConfig,
+    /// log filter in the `RUST_LOG` format (i.e. `info,subspace_farmer=debug`),
+    /// reloaded on `SIGHUP` while farming
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) log_level: Option<String>,
 }
 
 /// Advanced Node Settings Wrapper for CLI
@@ -30,6 +46,21 @@ pub(crate) struct Config {
 pub(crate) struct AdvancedNodeSettings {
     #[serde(default, skip_serializing_if = "crate::utils::is_default")]
     pub(crate) enable_domains: bool,
+    /// substrate boot nodes, in addition to the ones in the chain spec
+    #[serde(default, skip_serializing_if = "crate::utils::is_default")]
+    pub(crate) boot_nodes: Vec<MultiaddrWithPeerId>,
+    /// DSN boot nodes, in addition to `dsnBootstrapNodes` of the chain spec
+    #[serde(default, skip_serializing_if = "crate::utils::is_default")]
+    pub(crate) dsn_boot_nodes: Vec<MultiaddrWithPeerId>,
+    /// overrides the substrate listen addresses of the chain
+    #[serde(default, skip_serializing_if = "crate::utils::is_default")]
+    pub(crate) listen_addresses: Vec<Multiaddr>,
+    /// overrides the DSN listen addresses of the chain
+    #[serde(default, skip_serializing_if = "crate::utils::is_default")]
+    pub(crate) dsn_listen_addresses: Vec<Multiaddr>,
+    /// overrides the id of the domain to run, when domains are enabled
+    #[serde(default, skip_serializing_if = "crate::utils::is_default")]
+    pub(crate) domain_id: Option<u32>,
     #[serde(default, flatten)]
     pub(crate) extra: toml::Table,
 }
@@ -45,61 +76,123 @@ pub(crate) struct NodeConfig {
 
 impl NodeConfig {
     pub async fn build(self, chain: ChainConfig, is_verbose: bool) -> Result<Node> {
-        let Self { directory, name, advanced: AdvancedNodeSettings { enable_domains, extra } } =
-            self;
-
-        let (mut node, chain_spec) = match chain {
-            ChainConfig::Gemini3h => {
-                let mut node = Node::gemini_3h()
-                    .network(NetworkBuilder::gemini_3h().name(name))
-                    .dsn(DsnBuilder::gemini_3h())
-                    .sync_from_dsn(true)
-                    .enable_subspace_block_relay(true);
-                if enable_domains {
-                    node = node.domain(Some(DomainConfigBuilder::gemini_3h().configuration()));
-                }
-                let chain_spec = chain_spec::gemini_3h();
-                (node, chain_spec)
-            }
-            ChainConfig::Dev => {
-                let mut node = Node::dev();
-                if enable_domains {
-                    node = node.domain(Some(
-                        DomainConfigBuilder::dev().role(Role::Authority).configuration(),
-                    ));
-                }
-                let chain_spec = chain_spec::dev_config();
-                (node, chain_spec)
-            }
-            ChainConfig::DevNet => {
-                let mut node = Node::devnet()
-                    .network(NetworkBuilder::devnet().name(name))
-                    .dsn(DsnBuilder::devnet())
-                    .sync_from_dsn(true)
-                    .enable_subspace_block_relay(true);
-                if enable_domains {
-                    node = node.domain(Some(DomainConfigBuilder::devnet().configuration()));
-                }
-                let chain_spec = chain_spec::devnet_config();
-                (node, chain_spec)
-            }
+        let chain_spec = chain.chain_spec()?;
+        let node = self.builder(&chain, &chain_spec, is_verbose);
+
+        crate::utils::apply_extra_options(&node.configuration(), self.advanced.extra)
+            .context("Failed to deserialize node config")?
+            .build(self.directory, chain_spec)
+            .await
+            .into_eyre()
+            .wrap_err("Failed to build subspace node")
+    }
+
+    /// connects to the node at `url` instead of building one, the DSN of the
+    /// farmer keeps its state in the node directory
+    pub(crate) async fn connect(self, chain: ChainConfig, url: &str) -> Result<RemoteNode> {
+        let mut dsn = self.dsn_builder(&chain);
+        if self.advanced.dsn_listen_addresses.is_empty() && !matches!(chain, ChainConfig::Dev) {
+            // the node may be running on the same machine, listening on the default port
+            dsn = dsn.listen_addresses(vec![
+                "/ip6/::/tcp/30533".parse().expect("hardcoded value is true"),
+                "/ip4/0.0.0.0/tcp/30533".parse().expect("hardcoded value is true"),
+            ]);
+        }
+
+        RemoteNode::connect(url, self.name, dsn.configuration(), self.directory.join("farmer-dsn"))
+            .await
+            .into_eyre()
+            .wrap_err_with(|| format!("Failed to connect to the node at `{url}`"))
+    }
+
+    /// checks that the extra options can be applied to the node config, and
+    /// returns the keys that have no effect
+    pub(crate) fn validate_extra(&self, chain: &ChainConfig) -> Result<Vec<String>> {
+        let chain_spec = chain.chain_spec()?;
+        crate::utils::validate_extra_options(
+            &self.builder(chain, &chain_spec, false).configuration(),
+            &self.advanced.extra,
+        )
+    }
+
+    /// node builder for the given chain, without the extra options
+    fn builder(
+        &self,
+        chain: &ChainConfig,
+        chain_spec: &ChainSpec,
+        is_verbose: bool,
+    ) -> NodeBuilder<Farmer> {
+        let AdvancedNodeSettings {
+            enable_domains, boot_nodes, listen_addresses, domain_id, ..
+        } = self.advanced.clone();
+        let name = self.name.clone();
+
+        let (mut node, mut network, mut domain) = match chain {
+            ChainConfig::Gemini3h => (
+                Node::gemini_3h().sync_from_dsn(true).enable_subspace_block_relay(true),
+                NetworkBuilder::gemini_3h().name(name),
+                DomainConfigBuilder::gemini_3h(),
+            ),
+            ChainConfig::Dev => (
+                Node::dev(),
+                NetworkBuilder::dev(),
+                DomainConfigBuilder::dev().role(Role::Authority),
+            ),
+            ChainConfig::DevNet => (
+                Node::devnet().sync_from_dsn(true).enable_subspace_block_relay(true),
+                NetworkBuilder::devnet().name(name),
+                DomainConfigBuilder::devnet(),
+            ),
+            // custom chains are configured like the devnet, boot nodes come from the
+            // chain spec
+            ChainConfig::Custom { .. } => (
+                Node::devnet().sync_from_dsn(true).enable_subspace_block_relay(true),
+                NetworkBuilder::devnet().name(name),
+                DomainConfigBuilder::devnet().chain_id(chain_spec.id()),
+            ),
         };
 
+        if !boot_nodes.is_empty() {
+            network = network.boot_nodes(boot_nodes);
+        }
+        if !listen_addresses.is_empty() {
+            network = network.listen_addresses(listen_addresses);
+        }
+        node = node.network(network).dsn(self.dsn_builder(chain));
+
+        if enable_domains {
+            if let Some(domain_id) = domain_id {
+                domain = domain.domain_id(domain_id);
+            }
+            node = node.domain(Some(domain.configuration()));
+        }
+
         if is_verbose {
             node = node.informant_enable_color(true);
         }
 
-        node = node
-            .role(Role::Authority)
+        node.role(Role::Authority)
             .impl_version(format!("{}-{}", env!("CARGO_PKG_VERSION"), env!("GIT_HASH")))
-            .impl_name("pulsar".to_string());
+            .impl_name("pulsar".to_string())
+    }
 
-        crate::utils::apply_extra_options(&node.configuration(), extra)
-            .context("Failed to deserialize node config")?
-            .build(directory, chain_spec)
-            .await
-            .into_eyre()
-            .wrap_err("Failed to build subspace node")
+    /// DSN builder for the given chain
+    fn dsn_builder(&self, chain: &ChainConfig) -> DsnBuilder {
+        let AdvancedNodeSettings { dsn_boot_nodes, dsn_listen_addresses, .. } =
+            self.advanced.clone();
+
+        let mut dsn = match chain {
+            ChainConfig::Gemini3h => DsnBuilder::gemini_3h(),
+            ChainConfig::Dev => DsnBuilder::dev(),
+            ChainConfig::DevNet | ChainConfig::Custom { .. } => DsnBuilder::devnet(),
+        };
+        if !dsn_boot_nodes.is_empty() {
+            dsn = dsn.boot_nodes(dsn_boot_nodes);
+        }
+        if !dsn_listen_addresses.is_empty() {
+            dsn = dsn.listen_addresses(dsn_listen_addresses);
+        }
+        dsn
     }
 }
 
@@ -107,43 +200,196 @@ impl NodeConfig {
 #[derive(Deserialize, Serialize, Clone, Derivative, Debug, PartialEq)]
 #[derivative(Default)]
 pub(crate) struct AdvancedFarmerSettings {
+    /// piece cache size of the farms which don't have their own, either a size
+    /// (i.e. `1GB`) or a percentage of the farm (i.e. `1%`)
     #[serde(default, skip_serializing_if = "crate::utils::is_default")]
-    //TODO: change this back to 1GB when DSN is working properly
-    #[derivative(Default(value = "subspace_sdk::ByteSize::gb(3)"))]
-    pub(crate) cache_size: ByteSize,
+    pub(crate) cache_size: PieceCacheSize,
     #[serde(default, flatten)]
     pub(crate) extra: toml::Table,
 }
 
+/// Single farm entry of the farmer config (`[[farmer.farms]]`)
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub(crate) struct FarmConfig {
+    pub(crate) directory: PathBuf,
+    pub(crate) size: ByteSize,
+    /// piece cache size of the farm, `farmer.advanced.cache_size` is used if it
+    /// is not set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) cache_size: Option<PieceCacheSize>,
+}
+
+impl From<FarmConfig> for FarmDescription {
+    fn from(FarmConfig { directory, size, cache_size }: FarmConfig) -> Self {
+        FarmDescription::new(directory, size).with_piece_cache_size(cache_size)
+    }
+}
+
+/// window of the day in which the farms are plotted
+/// (`[[farmer.plotting_schedule]]`), in the local time
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct PlottingWindow {
+    /// start of the window (i.e. `22:00`)
+    pub(crate) start: TimeOfDay,
+    /// end of the window (i.e. `07:00`), the window spans midnight if it is
+    /// not after the start
+    pub(crate) end: TimeOfDay,
+    /// number of sectors encoded concurrently in the window, all the plotting
+    /// thread pools are used if it is not set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) sector_encoding_concurrency: Option<NonZeroUsize>,
+    /// number of sectors downloaded concurrently in the window,
+    /// `farmer.advanced.sector_downloading_concurrency` is used if it is not
+    /// set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) sector_downloading_concurrency: Option<NonZeroUsize>,
+}
+
+impl PlottingWindow {
+    /// whether the window includes the given time
+    pub(crate) fn contains(&self, time: NaiveTime) -> bool {
+        let (TimeOfDay(start), TimeOfDay(end)) = (self.start, self.end);
+        if start < end {
+            start <= time && time < end
+        } else {
+            start <= time || time < end
+        }
+    }
+}
+
+/// time of the day, written as `HH:MM`
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(try_from = "String", into = "String")]
+pub(crate) struct TimeOfDay(pub(crate) NaiveTime);
+
+impl TryFrom<String> for TimeOfDay {
+    type Error = String;
+
+    fn try_from(time: String) -> Result<Self, Self::Error> {
+        NaiveTime::parse_from_str(&time, "%H:%M")
+            .map(Self)
+            .map_err(|error| format!("invalid time of the day `{time}`, expected `HH:MM`: {error}"))
+    }
+}
+
+impl From<TimeOfDay> for String {
+    fn from(TimeOfDay(time): TimeOfDay) -> Self {
+        time.format("%H:%M").to_string()
+    }
+}
+
 /// Farmer Options Wrapper for CLI
 #[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(try_from = "RawFarmerConfig")]
 pub(crate) struct FarmerConfig {
     pub(crate) reward_address: PublicKey,
-    pub(crate) farm_directory: PathBuf,
-    pub(crate) farm_size: ByteSize,
+    pub(crate) farms: Vec<FarmConfig>,
+    /// WebSocket RPC of a node (i.e. `ws://127.0.0.1:9944`) to farm with,
+    /// instead of running a node in the same process
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) node_rpc_url: Option<String>,
+    /// windows of the day in which the farms are plotted
+    /// (`[[farmer.plotting_schedule]]`), plotting is paused outside of them,
+    /// and it is never paused if there are none
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) plotting_schedule: Vec<PlottingWindow>,
     #[serde(default, skip_serializing_if = "crate::utils::is_default")]
     pub(crate) advanced: AdvancedFarmerSettings,
 }
 
+/// On-disk representation of [`FarmerConfig`]
+///
+/// Besides `[[farmer.farms]]` entries, it also accepts the single-farm
+/// `farm_directory`/`farm_size` pair written by older versions of pulsar
+#[derive(Deserialize)]
+struct RawFarmerConfig {
+    reward_address: PublicKey,
+    #[serde(default)]
+    farms: Vec<FarmConfig>,
+    farm_directory: Option<PathBuf>,
+    farm_size: Option<ByteSize>,
+    node_rpc_url: Option<String>,
+    #[serde(default)]
+    plotting_schedule: Vec<PlottingWindow>,
+    #[serde(default)]
+    advanced: AdvancedFarmerSettings,
+}
+
+impl TryFrom<RawFarmerConfig> for FarmerConfig {
+    type Error = String;
+
+    fn try_from(
+        RawFarmerConfig {
+            reward_address,
+            mut farms,
+            farm_directory,
+            farm_size,
+            node_rpc_url,
+            plotting_schedule,
+            advanced,
+        }: RawFarmerConfig,
+    ) -> Result<Self, Self::Error> {
+        match (farm_directory, farm_size) {
+            (Some(directory), Some(size)) =>
+                farms.insert(0, FarmConfig { directory, size, cache_size: None }),
+            (None, None) => (),
+            _ => return Err("`farm_directory` and `farm_size` should be set together".to_owned()),
+        }
+
+        Ok(Self { reward_address, farms, node_rpc_url, plotting_schedule, advanced })
+    }
+}
+
 impl FarmerConfig {
-    pub async fn build(self, node: &Node) -> Result<Farmer> {
-        let farm_description = &[FarmDescription::new(self.farm_directory, self.farm_size)];
+    /// builds the farmer, serving the `farmer_*` RPC methods at
+    /// `rpc_listen_address` if set
+    pub async fn build(
+        self,
+        node: &impl FarmerNode,
+        rpc_listen_address: Option<SocketAddr>,
+    ) -> Result<Farmer> {
+        let farm_descriptions =
+            self.farms.into_iter().map(FarmDescription::from).collect::<Vec<_>>();
 
         // currently we do not have different configuration for the farmer w.r.t
         // different chains, but we may in the future
         let farmer = Farmer::builder();
-        crate::utils::apply_extra_options(&farmer.configuration(), self.advanced.extra)
-            .context("Failed to deserialize node config")?
-            .build(
-                self.reward_address,
-                node,
-                farm_description,
-                // TODO: Make this configurable via user input
-                NonZeroU8::new(1).expect("static value should not fail; qed"),
-            )
+        let mut config =
+            crate::utils::apply_extra_options(&farmer.configuration(), self.advanced.extra)
+                .context("Failed to deserialize node config")?;
+        if rpc_listen_address.is_some() {
+            config.rpc_listen_address = rpc_listen_address;
+        }
+        config
+            .build(self.reward_address, node, &farm_descriptions, self.advanced.cache_size)
             .await
             .context("Failed to build a farmer")
     }
+
+    /// size of the thread pool the farms are audited and proven with, as it is
+    /// set with `farming_thread_pool_size` under `[farmer.advanced]`
+    pub(crate) fn farming_thread_pool_size(&self) -> Result<Option<NonZeroUsize>> {
+        let config = crate::utils::apply_extra_options(
+            &Farmer::builder().configuration(),
+            self.advanced.extra.clone(),
+        )
+        .context("Failed to deserialize farmer config")?;
+        Ok(config.farming_thread_pool_size)
+    }
+
+    /// checks that the extra options can be applied to the farmer config, and
+    /// returns the keys that have no effect
+    pub(crate) fn validate_extra(&self) -> Result<Vec<String>> {
+        crate::utils::validate_extra_options(
+            &Farmer::builder().configuration(),
+            &self.advanced.extra,
+        )
+    }
+
+    /// total space pledged by all the farms
+    pub(crate) fn total_farm_size(&self) -> ByteSize {
+        ByteSize::b(self.farms.iter().map(|farm| farm.size.as_u64()).sum())
+    }
 }
 
 /// Enum for Chain
@@ -153,12 +399,47 @@ pub(crate) enum ChainConfig {
     Gemini3h,
     Dev,
     DevNet,
+    /// chain described by a raw or plain chain spec JSON file
+    #[strum(disabled)]
+    Custom {
+        chain_spec_path: PathBuf,
+    },
+}
+
+/// genesis hash of Gemini 3h, as hex
+const GEMINI_3H_GENESIS_HASH: &str =
+    "0c121c75f4ef450f40619e1fca9d1e8e7fbabc42c895bc4790801e85d5a91c34";
+
+impl ChainConfig {
+    /// known chain with the given genesis hash (as hex), dev chains don't have
+    /// a fixed genesis hash
+    pub(crate) fn from_genesis_hash(genesis_hash: &str) -> Option<Self> {
+        match genesis_hash {
+            GEMINI_3H_GENESIS_HASH => Some(ChainConfig::Gemini3h),
+            _ => None,
+        }
+    }
+
+    /// loads the chain spec of the chain
+    pub(crate) fn chain_spec(&self) -> Result<ChainSpec> {
+        Ok(match self {
+            ChainConfig::Gemini3h => chain_spec::gemini_3h(),
+            ChainConfig::Dev => chain_spec::dev_config(),
+            ChainConfig::DevNet => chain_spec::devnet_config(),
+            ChainConfig::Custom { chain_spec_path } =>
+                chain_spec::from_json_file(chain_spec_path).into_eyre()?,
+        })
+    }
 }
 
 impl std::str::FromStr for ChainConfig {
     type Err = Report;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if Path::new(s).extension().is_some_and(|extension| extension == "json") {
+            return Ok(ChainConfig::Custom { chain_spec_path: s.into() });
+        }
+
         match s.to_lowercase().as_str() {
             "gemini3h" => Ok(ChainConfig::Gemini3h),
             "dev" => Ok(ChainConfig::Dev),
@@ -186,27 +467,89 @@ pub(crate) fn create_config() -> Result<(File, PathBuf)> {
     Ok((file, config_path))
 }
 
+/// path of the config file
+pub(crate) fn config_path() -> PathBuf {
+    dirs::config_dir()
+        .expect("couldn't get the default config directory!")
+        .join("pulsar")
+        .join("settings.toml")
+}
+
+/// overwrites the config file with the given [`Config`]
+pub(crate) fn write_config(config: &Config) -> Result<()> {
+    let content = toml::to_string_pretty(config).wrap_err("Failed to serialize config")?;
+    let (mut config_file, _) = create_config()?;
+    config_file.write_all(content.as_bytes()).wrap_err("Failed to write config")
+}
+
 /// parses the config, and returns [`Config`]
+///
+/// configs written by older versions of pulsar are migrated first
 #[instrument]
 pub(crate) fn parse_config() -> Result<Config> {
-    let config_path = dirs::config_dir().expect("couldn't get the default config directory!");
-    let config_path = config_path.join("pulsar").join("settings.toml");
+    let config_path = config_path();
+    migrate_file(&config_path, migrate_config).context("couldn't migrate the config")?;
 
     let config: Config = toml::from_str(&std::fs::read_to_string(config_path)?)?;
     Ok(config)
 }
 
+/// parses the given config contents, migrating them in memory if needed
+pub(crate) fn parse_config_contents(contents: &str) -> Result<Config> {
+    let mut config: toml::Table = toml::from_str(contents)?;
+    migrate_config(&mut config).context("couldn't migrate the config")?;
+    config.try_into().context("couldn't deserialize the config")
+}
+
 /// validates the config for farming
 #[instrument]
 pub(crate) fn validate_config() -> Result<Config> {
     let config = parse_config()?;
+    config.validate()?;
+    Ok(config)
+}
 
-    // validity checks
-    if config.farmer.farm_size < MIN_FARM_SIZE {
-        return Err(eyre!("farm size should be bigger than {MIN_FARM_SIZE}!"));
-    }
+impl Config {
+    /// validity checks which don't require accessing the disk
+    pub(crate) fn validate(&self) -> Result<()> {
+        if self.farmer.farms.is_empty() {
+            return Err(eyre!("at least one farm should be provided in the config!"));
+        }
+        for (farm_index, farm) in self.farmer.farms.iter().enumerate() {
+            if farm.size < MIN_FARM_SIZE {
+                return Err(eyre!(
+                    "size of farm #{farm_index} should be bigger than {MIN_FARM_SIZE}!"
+                ));
+            }
+            let cache_size = farm.cache_size.unwrap_or(self.farmer.advanced.cache_size);
+            if cache_size.percentage(farm.size).is_none() {
+                return Err(eyre!(
+                    "piece cache of farm #{farm_index} ({cache_size}) doesn't leave any space for \
+                     plotting in its {}!",
+                    farm.size
+                ));
+            }
+            if self.farmer.farms[..farm_index].iter().any(|other| other.directory == farm.directory)
+            {
+                return Err(eyre!(
+                    "farm directory `{}` is used by more than one farm!",
+                    farm.directory.display()
+                ));
+            }
+        }
+        if let Some(log_level) = &self.log_level {
+            log_filter(Some(log_level)).context("invalid `log_level`")?;
+        }
+        if let Some(url) = &self.farmer.node_rpc_url {
+            if !url.starts_with("ws://") && !url.starts_with("wss://") {
+                return Err(eyre!(
+                    "`node_rpc_url` should be a WebSocket url (i.e. `ws://127.0.0.1:9944`)!"
+                ));
+            }
+        }
 
-    Ok(config)
+        Ok(())
+    }
 }
 
 /// deletes the config file
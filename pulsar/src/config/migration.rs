@@ -0,0 +1,211 @@
+//! Migrations of the files written by older versions of pulsar, so that
+//! upgrading pulsar does not end up with a deserialization error.
+
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+use color_eyre::eyre::{eyre, Context, Result};
+use toml::{Table, Value};
+
+/// current version of the config schema
+pub(crate) const CONFIG_VERSION: u32 = 2;
+
+/// migrates the config from version `n` to `n + 1`, and returns the
+/// description of the changes
+type Migration = fn(&mut Table) -> Result<Vec<String>>;
+
+/// `MIGRATIONS[n]` migrates the config from version `n`
+const MIGRATIONS: [Migration; CONFIG_VERSION as usize] = [migrate_v0, migrate_v1];
+
+/// chains of the past testnets, which are renamed to the current one
+const RENAMED_CHAINS: &[(&str, &str)] = &[
+    ("Gemini2a", "Gemini3h"),
+    ("Gemini3c", "Gemini3h"),
+    ("Gemini3d", "Gemini3h"),
+    ("Gemini3e", "Gemini3h"),
+    ("Gemini3f", "Gemini3h"),
+    ("Gemini3g", "Gemini3h"),
+];
+
+/// summary fields which were not tracked by the older versions
+const SCANNED_SUMMARY_FIELDS: &[&str] = &["vote_count", "total_rewards"];
+
+/// migrates the config to [`CONFIG_VERSION`], and returns the description of
+/// the changes
+pub(crate) fn migrate_config(config: &mut Table) -> Result<Vec<String>> {
+    let version = match config.get("version") {
+        None => 0,
+        Some(Value::Integer(version)) =>
+            u32::try_from(*version).map_err(|_| eyre!("invalid config version `{version}`"))?,
+        Some(version) => return Err(eyre!("invalid config version `{version}`")),
+    };
+    if version > CONFIG_VERSION {
+        return Err(eyre!(
+            "config version `{version}` is newer than the supported version `{CONFIG_VERSION}`, \
+             please upgrade pulsar"
+        ));
+    }
+
+    let mut changes = vec![];
+    for (from, migration) in MIGRATIONS.iter().enumerate().skip(version as usize) {
+        changes.extend(
+            migration(config)
+                .with_context(|| format!("couldn't migrate the config from version `{from}`"))?,
+        );
+        config.insert("version".to_owned(), Value::Integer(from as i64 + 1));
+        changes.push(format!("config version is updated to `{}`", from + 1));
+    }
+
+    Ok(changes)
+}
+
+/// resets the block scanning progress of the summaries written before the
+/// votes and rewards were tracked, and returns the description of the changes
+pub(crate) fn migrate_summary(summary: &mut Table) -> Result<Vec<String>> {
+    if SCANNED_SUMMARY_FIELDS.iter().all(|field| summary.contains_key(*field)) {
+        return Ok(vec![]);
+    }
+
+    summary.insert("authored_count".to_owned(), Value::Integer(0));
+    summary.insert("vote_count".to_owned(), Value::Integer(0));
+    summary.insert("total_rewards".to_owned(), Value::String("0".to_owned()));
+    summary.insert("last_processed_block_num".to_owned(), Value::Integer(0));
+
+    Ok(vec!["votes and rewards are tracked now, so the blocks will be scanned again".to_owned()])
+}
+
+/// applies the given migration to the toml file at `path`
+///
+/// if anything is changed, the original file is kept as `<path>.bak`, and the
+/// changes are reported to the user
+pub(crate) fn migrate_file(
+    path: &Path,
+    migration: impl FnOnce(&mut Table) -> Result<Vec<String>>,
+) -> Result<()> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("couldn't read `{}`", path.display()))?;
+    let mut table: Table = toml::from_str(&contents)
+        .with_context(|| format!("couldn't parse `{}`", path.display()))?;
+
+    let changes = migration(&mut table)?;
+    if changes.is_empty() {
+        return Ok(());
+    }
+
+    let backup_path = backup_path(path);
+    std::fs::copy(path, &backup_path)
+        .with_context(|| format!("couldn't back up `{}`", path.display()))?;
+    std::fs::write(path, toml::to_string_pretty(&table).context("couldn't serialize the file")?)
+        .with_context(|| format!("couldn't write the migrated `{}`", path.display()))?;
+
+    println!(
+        "`{}` is migrated to the latest version (backup is saved at `{}`):",
+        path.display(),
+        backup_path.display()
+    );
+    for change in changes {
+        println!("  - {change}");
+    }
+
+    Ok(())
+}
+
+/// `<path>.bak`
+fn backup_path(path: &Path) -> PathBuf {
+    let mut backup_path = OsString::from(path.as_os_str());
+    backup_path.push(".bak");
+    backup_path.into()
+}
+
+/// unversioned config to version `1`
+fn migrate_v0(config: &mut Table) -> Result<Vec<String>> {
+    let mut changes = vec![];
+
+    if let Some(Value::String(chain)) = config.get_mut("chain") {
+        if let Some((_, new_chain)) =
+            RENAMED_CHAINS.iter().find(|(old_chain, _)| old_chain.eq_ignore_ascii_case(chain))
+        {
+            changes.push(format!("chain `{chain}` is renamed to `{new_chain}`"));
+            *chain = new_chain.to_string();
+        }
+    }
+
+    if let Some(Value::Table(farmer)) = config.get_mut("farmer") {
+        match (farmer.remove("farm_directory"), farmer.remove("farm_size")) {
+            (Some(directory), Some(size)) => {
+                let Value::Array(farms) =
+                    farmer.entry("farms").or_insert_with(|| Value::Array(vec![]))
+                else {
+                    return Err(eyre!("`farmer.farms` should be an array"));
+                };
+                let farm = Table::from_iter([
+                    ("directory".to_owned(), directory),
+                    ("size".to_owned(), size),
+                ]);
+                farms.insert(0, Value::Table(farm));
+                changes.push(
+                    "`farmer.farm_directory` and `farmer.farm_size` are moved to \
+                     `[[farmer.farms]]`"
+                        .to_owned(),
+                );
+            }
+            (None, None) => {}
+            _ =>
+                return Err(eyre!(
+                    "`farmer.farm_directory` and `farmer.farm_size` should be set together"
+                )),
+        }
+
+        changes.extend(move_to_advanced(farmer, "farmer", "cache_size")?);
+    }
+
+    if let Some(Value::Table(node)) = config.get_mut("node") {
+        changes.extend(move_to_advanced(node, "node", "enable_domains")?);
+    }
+
+    Ok(changes)
+}
+
+/// version `1` to `2`
+///
+/// `farmer.advanced.cache_size` was not applied before, the piece caches were
+/// always 1% of the farms, so it is dropped instead of resizing the caches of
+/// the existing farms
+fn migrate_v1(config: &mut Table) -> Result<Vec<String>> {
+    let Some(Value::Table(farmer)) = config.get_mut("farmer") else {
+        return Ok(vec![]);
+    };
+    let Some(Value::Table(advanced)) = farmer.get_mut("advanced") else {
+        return Ok(vec![]);
+    };
+    if advanced.remove("cache_size").is_none() {
+        return Ok(vec![]);
+    }
+
+    Ok(vec!["`farmer.advanced.cache_size` is removed, since it was not applied, piece caches \
+             stay at 1% of the farms"
+        .to_owned()])
+}
+
+/// moves `section.key` to `section.advanced.key`, unless it is already set
+/// there
+fn move_to_advanced(section: &mut Table, section_name: &str, key: &str) -> Result<Option<String>> {
+    let Some(value) = section.remove(key) else {
+        return Ok(None);
+    };
+
+    let Value::Table(advanced) =
+        section.entry("advanced").or_insert_with(|| Value::Table(Table::new()))
+    else {
+        return Err(eyre!("`{section_name}.advanced` should be a table"));
+    };
+    if advanced.contains_key(key) {
+        return Ok(Some(format!(
+            "`{section_name}.{key}` is removed, since `{section_name}.advanced.{key}` is already \
+             set"
+        )));
+    }
+    advanced.insert(key.to_owned(), value);
+
+    Ok(Some(format!("`{section_name}.{key}` is moved to `{section_name}.advanced.{key}`")))
+}
@@ -6,6 +6,7 @@
 
 mod commands;
 mod config;
+mod status;
 mod summary;
 mod utils;
 
@@ -25,9 +26,13 @@ use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 use tracing::instrument;
 
-use crate::commands::farm::farm;
-use crate::commands::info::info;
-use crate::commands::init::init;
+use crate::commands::benchmark::{benchmark, BenchmarkCommands};
+use crate::commands::config::{config, ConfigCommands};
+use crate::commands::export::{export, ExportArgs};
+use crate::commands::farm::{farm, FarmArgs};
+use crate::commands::info::{info, InfoArgs};
+use crate::commands::init::{init, InitArgs};
+use crate::commands::service::{service, ServiceCommands};
 use crate::commands::wipe::wipe_config;
 use crate::utils::{get_user_input, open_log_dir, support_message, yes_or_no_parser};
 
@@ -47,17 +52,11 @@ struct Cli {
 /// Available commands for the CLI
 #[derive(Debug, Subcommand, EnumIter)]
 enum Commands {
-    #[command(about = "initializes the config file required for the farming")]
-    Init,
+    #[command(about = "initializes the config file required for the farming (interactively, \
+                       unless any of the arguments are supplied)")]
+    Init(InitArgs),
     #[command(about = "starting the farming process (along with node in the background)")]
-    Farm {
-        #[arg(short, long, action)]
-        verbose: bool,
-        #[arg(short, long, action)]
-        enable_domains: bool,
-        #[arg(long, action)]
-        no_rotation: bool,
-    },
+    Farm(FarmArgs),
     #[command(about = "wipes the node and farm instance (along with your farms)")]
     Wipe {
         #[arg(long, action)]
@@ -67,8 +66,31 @@ enum Commands {
     },
     #[command(about = "displays info about the farmer instance (i.e. total amount of rewards, \
                        and status of initial plotting)")]
-    Info,
+    Info(InfoArgs),
     OpenLogs,
+    #[command(about = "shows, validates or edits the config file")]
+    #[strum(disabled)]
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommands,
+    },
+    #[command(about = "exports the blocks and votes of the farming history, along with their \
+                       rewards, as CSV or JSON")]
+    #[strum(disabled)]
+    Export(ExportArgs),
+    #[command(about = "installs, uninstalls or inspects the systemd service running the farmer")]
+    #[strum(disabled)]
+    Service {
+        #[command(subcommand)]
+        command: ServiceCommands,
+    },
+    #[command(about = "measures whether a farm is audited and proven fast enough to meet the \
+                       slot deadline")]
+    #[strum(disabled)]
+    Benchmark {
+        #[command(subcommand)]
+        command: BenchmarkCommands,
+    },
 }
 
 #[tokio::main]
@@ -76,14 +98,14 @@ enum Commands {
 async fn main() -> Result<(), Report> {
     let args = Cli::parse();
     match args.command {
-        Some(Commands::Info) => {
-            info().await.suggestion(support_message())?;
+        Some(Commands::Info(args)) => {
+            info(args).await.suggestion(support_message())?;
         }
-        Some(Commands::Init) => {
-            init().suggestion(support_message())?;
+        Some(Commands::Init(args)) => {
+            init(args).suggestion(support_message())?;
         }
-        Some(Commands::Farm { verbose, enable_domains, no_rotation }) => {
-            farm(verbose, enable_domains, no_rotation).await.suggestion(support_message())?;
+        Some(Commands::Farm(args)) => {
+            farm(args).await.suggestion(support_message())?;
         }
         Some(Commands::Wipe { farmer, node }) => {
             wipe_config(farmer, node).await.suggestion(support_message())?;
@@ -91,6 +113,18 @@ async fn main() -> Result<(), Report> {
         Some(Commands::OpenLogs) => {
             open_log_dir().suggestion(support_message())?;
         }
+        Some(Commands::Config { command }) => {
+            config(command).suggestion(support_message())?;
+        }
+        Some(Commands::Export(args)) => {
+            export(args).await.suggestion(support_message())?;
+        }
+        Some(Commands::Service { command }) => {
+            service(command).suggestion(support_message())?;
+        }
+        Some(Commands::Benchmark { command }) => {
+            benchmark(command).await.suggestion(support_message())?;
+        }
         None => arrow_key_mode().await.suggestion(support_message())?,
     }
 
@@ -153,7 +187,7 @@ async fn arrow_key_mode() -> Result<(), Report> {
 
     match selected {
         0 => {
-            init().suggestion(support_message())?;
+            init(InitArgs::default()).suggestion(support_message())?;
         }
         1 => {
             let prompt = "Do you want to initialize farmer in verbose mode? [y/n]: ";
@@ -168,13 +202,15 @@ async fn arrow_key_mode() -> Result<(), Report> {
             let no_rotation =
                 get_user_input(prompt, None, yes_or_no_parser).context("prompt failed")?;
 
-            farm(verbose, enable_domains, no_rotation).await.suggestion(support_message())?;
+            farm(FarmArgs { verbose, enable_domains, no_rotation, ..Default::default() })
+                .await
+                .suggestion(support_message())?;
         }
         2 => {
             wipe_config(false, false).await.suggestion(support_message())?;
         }
         3 => {
-            info().await.suggestion(support_message())?;
+            info(InfoArgs::default()).await.suggestion(support_message())?;
         }
         4 => {
             open_log_dir().suggestion(support_message())?;
@@ -218,11 +254,15 @@ fn print_options(
 impl std::fmt::Display for Commands {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match *self {
-            Commands::Farm { verbose: _, enable_domains: _, no_rotation: _ } => write!(f, "farm"),
+            Commands::Farm(_) => write!(f, "farm"),
             Commands::Wipe { farmer: _, node: _ } => write!(f, "wipe"),
-            Commands::Info => write!(f, "info"),
-            Commands::Init => write!(f, "init"),
+            Commands::Info(_) => write!(f, "info"),
+            Commands::Init(_) => write!(f, "init"),
             Commands::OpenLogs => write!(f, "open logs directory"),
+            Commands::Config { command: _ } => write!(f, "config"),
+            Commands::Export(_) => write!(f, "export"),
+            Commands::Service { command: _ } => write!(f, "service"),
+            Commands::Benchmark { command: _ } => write!(f, "benchmark"),
         }
     }
 }
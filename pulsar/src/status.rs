@@ -0,0 +1,362 @@
+//! Local HTTP server for inspecting a running farm.
+//! Serves the status of the node and the farms as a JSON document on
+//! `/status`, and the same information as Prometheus metrics on `/metrics`.
+use std::convert::Infallible;
+use std::fmt::Write;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use color_eyre::eyre::{Context, Result};
+use futures::future::OptionFuture;
+use futures::prelude::*;
+use hyper::header::CONTENT_TYPE;
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Method, Request, Response, Server, StatusCode};
+use serde::Serialize;
+use subspace_sdk::node::{BlockNumber, SyncingProgress};
+use subspace_sdk::{Farmer, Node};
+use tokio::sync::Mutex;
+use tracing::instrument;
+
+use crate::summary::{Summary, SummaryFile};
+use crate::utils::{IntoEyre, IntoEyreStream};
+
+/// how often the node info is refreshed
+const NODE_INFO_INTERVAL: Duration = Duration::from_secs(5);
+
+/// status document served on `/status`
+#[derive(Debug, Clone, Default, Serialize)]
+pub(crate) struct Status {
+    /// `None` when farming with a remote node
+    pub(crate) node: Option<NodeStatus>,
+    pub(crate) farms: Vec<FarmStatus>,
+    pub(crate) authored_count: u64,
+}
+
+/// status of the node
+#[derive(Debug, Clone, Default, Serialize)]
+pub(crate) struct NodeStatus {
+    pub(crate) best_block: BlockNumber,
+    pub(crate) finalized_block: BlockNumber,
+    /// target block of the ongoing sync, `None` once the node is synced
+    pub(crate) sync_target: Option<BlockNumber>,
+    pub(crate) is_synced: bool,
+    pub(crate) connected_peers: u64,
+    pub(crate) not_connected_peers: u64,
+}
+
+/// status of a single farm
+#[derive(Debug, Clone, Default, Serialize)]
+pub(crate) struct FarmStatus {
+    pub(crate) directory: PathBuf,
+    pub(crate) allocated_space: u64,
+    pub(crate) plotted_sectors: u64,
+    pub(crate) total_sectors: u64,
+    pub(crate) initial_plotting_finished: bool,
+    pub(crate) solutions: u64,
+}
+
+impl Status {
+    /// renders the status in the Prometheus text exposition format
+    pub(crate) fn to_metrics(&self) -> String {
+        let mut metrics = String::new();
+
+        if let Some(node) = &self.node {
+            write_metric(
+                &mut metrics,
+                "pulsar_node_best_block",
+                "gauge",
+                "Best block of the node",
+                [(String::new(), node.best_block.into())],
+            );
+            write_metric(
+                &mut metrics,
+                "pulsar_node_finalized_block",
+                "gauge",
+                "Finalized block of the node",
+                [(String::new(), node.finalized_block.into())],
+            );
+            write_metric(
+                &mut metrics,
+                "pulsar_node_is_synced",
+                "gauge",
+                "Whether the node is synced (1) or not (0)",
+                [(String::new(), node.is_synced.into())],
+            );
+            write_metric(
+                &mut metrics,
+                "pulsar_node_connected_peers",
+                "gauge",
+                "Number of peers connected to the node",
+                [(String::new(), node.connected_peers)],
+            );
+        }
+        write_metric(
+            &mut metrics,
+            "pulsar_authored_blocks_total",
+            "counter",
+            "Number of blocks authored by the farmer",
+            [(String::new(), self.authored_count)],
+        );
+
+        let farm_labels = self
+            .farms
+            .iter()
+            .enumerate()
+            .map(|(farm_index, farm)| {
+                format!(
+                    "{{farm=\"{farm_index}\",directory=\"{}\"}}",
+                    escape_label_value(&farm.directory.display().to_string())
+                )
+            })
+            .collect::<Vec<_>>();
+        let farm_metric = |metrics: &mut String,
+                           name: &str,
+                           kind: &str,
+                           help: &str,
+                           value: fn(&FarmStatus) -> u64| {
+            write_metric(
+                metrics,
+                name,
+                kind,
+                help,
+                farm_labels.iter().cloned().zip(self.farms.iter().map(value)),
+            )
+        };
+        farm_metric(
+            &mut metrics,
+            "pulsar_farm_allocated_space_bytes",
+            "gauge",
+            "Space allocated for the farm",
+            |farm| farm.allocated_space,
+        );
+        farm_metric(
+            &mut metrics,
+            "pulsar_farm_plotted_sectors",
+            "gauge",
+            "Number of sectors plotted during the initial plotting",
+            |farm| farm.plotted_sectors,
+        );
+        farm_metric(
+            &mut metrics,
+            "pulsar_farm_total_sectors",
+            "gauge",
+            "Number of sectors of the farm",
+            |farm| farm.total_sectors,
+        );
+        farm_metric(
+            &mut metrics,
+            "pulsar_farm_initial_plotting_finished",
+            "gauge",
+            "Whether the initial plotting of the farm is finished (1) or not (0)",
+            |farm| farm.initial_plotting_finished.into(),
+        );
+        farm_metric(
+            &mut metrics,
+            "pulsar_farm_solutions_total",
+            "counter",
+            "Number of solutions found by the farm",
+            |farm| farm.solutions,
+        );
+
+        metrics
+    }
+}
+
+/// writes a single metric family to `metrics`, samples are pairs of labels
+/// (i.e. `{farm="0"}`) and values
+fn write_metric(
+    metrics: &mut String,
+    name: &str,
+    kind: &str,
+    help: &str,
+    samples: impl IntoIterator<Item = (String, u64)>,
+) {
+    // writing to a `String` never fails
+    let _ = writeln!(metrics, "# HELP {name} {help}");
+    let _ = writeln!(metrics, "# TYPE {name} {kind}");
+    for (labels, value) in samples {
+        let _ = writeln!(metrics, "{name}{labels} {value}");
+    }
+}
+
+/// escapes the label value as required by the Prometheus text format
+fn escape_label_value(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+/// runs the status server on the given address, along with the subscriptions
+/// keeping the status up to date
+///
+/// never returns unless the server fails, so it should be aborted on shutdown
+///
+/// the node status is only served for a node running in the same process
+#[instrument(skip(node, farmer, summary_file))]
+pub(crate) async fn run_status_server(
+    address: SocketAddr,
+    node: Option<Arc<Node>>,
+    farmer: Arc<Farmer>,
+    summary_file: SummaryFile,
+) -> Result<()> {
+    let status = Arc::new(Mutex::new(Status {
+        node: node.is_some().then(NodeStatus::default),
+        ..Default::default()
+    }));
+
+    let mut farm_subscriptions = vec![];
+    for (farm_index, farm) in farmer.iter_farms().await.enumerate() {
+        status.lock().await.farms.push(FarmStatus {
+            directory: farm.directory().clone(),
+            allocated_space: farm.allocated_space().as_u64(),
+            ..Default::default()
+        });
+
+        let plotting_progress = farm
+            .subscribe_initial_plotting_progress()
+            .await
+            .map(|progress| (progress.current_sector, progress.total_sectors));
+        let solutions = farm.subscribe_new_solutions().await;
+        farm_subscriptions.push(track_farm(
+            status.clone(),
+            farm_index,
+            plotting_progress,
+            solutions,
+        ));
+    }
+    // the farmer is not needed anymore, so that it can be closed on shutdown
+    drop(farmer);
+
+    let server = Server::try_bind(&address)
+        .with_context(|| format!("couldn't bind the status server to `{address}`"))?
+        .serve(make_service_fn({
+            let status = status.clone();
+            move |_| {
+                let status = status.clone();
+                let summary_file = summary_file.clone();
+                future::ok::<_, Infallible>(service_fn(move |request| {
+                    handle_request(request, status.clone(), summary_file.clone())
+                }))
+            }
+        }))
+        .map(|result| result.context("status server failed"));
+    tracing::info!(%address, "status server is listening");
+
+    let (server_result, (), ()) = futures::join!(
+        server,
+        OptionFuture::from(node.map(|node| track_node(status, node))).map(drop),
+        future::join_all(farm_subscriptions).map(drop)
+    );
+    server_result
+}
+
+/// keeps the node status up to date
+async fn track_node(status: Arc<Mutex<Status>>, node: Arc<Node>) {
+    let syncing = async {
+        match node.subscribe_syncing_progress().await.into_eyre() {
+            Ok(syncing_progress) => {
+                let mut syncing_progress = syncing_progress.into_eyre();
+                while let Some(progress) = syncing_progress.next().await {
+                    match progress {
+                        Ok(SyncingProgress { target, .. }) =>
+                            if let Some(node_status) = &mut status.lock().await.node {
+                                node_status.sync_target = Some(target);
+                            },
+                        Err(error) => tracing::warn!(%error, "node syncing failed"),
+                    }
+                }
+            }
+            Err(error) => tracing::warn!(%error, "couldn't subscribe to node syncing"),
+        }
+
+        if let Some(node_status) = &mut status.lock().await.node {
+            node_status.sync_target = None;
+            node_status.is_synced = true;
+        }
+    };
+
+    let info = async {
+        loop {
+            match node.get_info().await.into_eyre() {
+                Ok(info) =>
+                    if let Some(node_status) = &mut status.lock().await.node {
+                        node_status.best_block = info.best_block.1;
+                        node_status.finalized_block = info.finalized_block.1;
+                        node_status.connected_peers = info.connected_peers;
+                        node_status.not_connected_peers = info.not_connected_peers;
+                    },
+                Err(error) => tracing::warn!(%error, "couldn't get the node info"),
+            }
+            tokio::time::sleep(NODE_INFO_INTERVAL).await;
+        }
+    };
+
+    futures::join!(syncing, info);
+}
+
+/// keeps the status of a farm up to date
+async fn track_farm<S>(
+    status: Arc<Mutex<Status>>,
+    farm_index: usize,
+    plotting_progress: impl Stream<Item = (u64, u64)>,
+    solutions: impl Stream<Item = S>,
+) {
+    let plotting = plotting_progress
+        .for_each(|(plotted_sectors, total_sectors)| {
+            let status = status.clone();
+            async move {
+                let mut status = status.lock().await;
+                let farm = &mut status.farms[farm_index];
+                farm.plotted_sectors = plotted_sectors;
+                farm.total_sectors = total_sectors;
+            }
+        })
+        .then(|()| async {
+            let mut status = status.lock().await;
+            let farm = &mut status.farms[farm_index];
+            farm.plotted_sectors = farm.total_sectors;
+            farm.initial_plotting_finished = true;
+        });
+    let solutions = solutions.for_each(|_| {
+        let status = status.clone();
+        async move { status.lock().await.farms[farm_index].solutions += 1 }
+    });
+
+    futures::join!(plotting, solutions);
+}
+
+/// serves `/status` and `/metrics`
+async fn handle_request(
+    request: Request<Body>,
+    status: Arc<Mutex<Status>>,
+    summary_file: SummaryFile,
+) -> Result<Response<Body>, Infallible> {
+    let response = match (request.method(), request.uri().path()) {
+        (&Method::GET, "/status" | "/metrics") => {
+            let mut status = status.lock().await.clone();
+            match summary_file.parse().await {
+                Ok(Summary { authored_count, .. }) => status.authored_count = authored_count,
+                Err(error) => tracing::warn!(%error, "couldn't parse the summary"),
+            }
+
+            if request.uri().path() == "/metrics" {
+                Response::builder()
+                    .header(CONTENT_TYPE, "text/plain; version=0.0.4")
+                    .body(Body::from(status.to_metrics()))
+            } else {
+                match serde_json::to_vec(&status) {
+                    Ok(status) => Response::builder()
+                        .header(CONTENT_TYPE, "application/json")
+                        .body(Body::from(status)),
+                    Err(error) => Response::builder()
+                        .status(StatusCode::INTERNAL_SERVER_ERROR)
+                        .body(Body::from(error.to_string())),
+                }
+            }
+        }
+        _ => Response::builder().status(StatusCode::NOT_FOUND).body(Body::empty()),
+    };
+
+    Ok(response.expect("responses are built from valid parts; qed"))
+}
@@ -3,29 +3,56 @@
 /// and also store the amount of potentially farmed blocks during the initial
 /// plotting progress, so that progress bar won't be affected with `println!`,
 /// and user will still know about them when initial plotting is finished.
+use std::fmt::{self, Display, Formatter};
 use std::fs::remove_file;
 use std::path::PathBuf;
 use std::sync::Arc;
 
-use color_eyre::eyre::{Context, Result};
-use derive_more::{AddAssign, Display, From, FromStr};
+use color_eyre::eyre::{eyre, Context, Result};
+use derive_more::{AddAssign, From};
 use serde::{Deserialize, Serialize};
 use subspace_sdk::node::BlockNumber;
 use subspace_sdk::ByteSize;
-use tokio::fs::{create_dir_all, File, OpenOptions};
-use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
-use tokio::sync::{Mutex, MutexGuard};
+use tokio::fs::create_dir_all;
+use tokio::sync::Mutex;
 use tracing::instrument;
 
+use crate::config::migration::{migrate_file, migrate_summary};
+use crate::summary::history::{FarmingEvent, FarmingTotals, HistoryFile, HistoryRecord};
+
+pub(crate) mod history;
+
+/// scanned blocks without any events are only recorded once this many of them
+/// are accumulated, as they are harmless to scan again after a crash
+const PROGRESS_RECORD_INTERVAL: BlockNumber = 100;
+
+/// number of decimals of SSC
+const SSC_DECIMALS: usize = 18;
+
+/// rewards in the smallest unit of SSC, displayed in SSC
 // TODO: delete this when https://github.com/toml-rs/toml/issues/540 is solved
-#[derive(Debug, Clone, Copy, Default, Display, AddAssign, FromStr, From)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, AddAssign, From)]
 pub(crate) struct Rewards(pub(crate) u128);
 
+impl Display for Rewards {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let unit = 10_u128.pow(SSC_DECIMALS as u32);
+        let (whole, fraction) = (self.0 / unit, self.0 % unit);
+        if fraction == 0 {
+            return write!(f, "{whole}");
+        }
+
+        let fraction = format!("{fraction:0SSC_DECIMALS$}");
+        write!(f, "{whole}.{}", fraction.trim_end_matches('0'))
+    }
+}
+
 /// struct for updating the fields of the summary
 #[derive(Default, Debug)]
 pub(crate) struct SummaryUpdateFields {
     pub(crate) is_plotting_finished: bool,
-    pub(crate) new_authored_count: u64,
+    /// blocks and votes found in the newly parsed blocks
+    pub(crate) new_events: Vec<FarmingEvent>,
     pub(crate) new_parsed_blocks: BlockNumber,
 }
 
@@ -35,11 +62,7 @@ pub(crate) struct SummaryUpdateFields {
 pub(crate) struct Summary {
     pub(crate) initial_plotting_finished: bool,
     pub(crate) authored_count: u64,
-    // Deprecated: Will be removed in next version
-    #[serde(skip)]
     pub(crate) vote_count: u64,
-    // Deprecated: Will be removed in next version
-    #[serde(skip)]
     pub(crate) total_rewards: Rewards,
     pub(crate) user_space_pledged: ByteSize,
     pub(crate) last_processed_block_num: BlockNumber,
@@ -49,74 +72,125 @@ pub(crate) struct Summary {
 /// `info` command
 #[derive(Debug, Clone)]
 pub(crate) struct SummaryFile {
-    inner: Arc<Mutex<File>>,
+    inner: Arc<Mutex<SummaryState>>,
+}
+
+/// history journal along with the summary derived from it
+#[derive(Debug)]
+struct SummaryState {
+    /// `None` if the history is opened read-only
+    history: Option<HistoryFile>,
+    /// summary of the recorded history
+    summary: Summary,
+    /// parsed blocks which are not recorded yet
+    pending_parsed_blocks: BlockNumber,
+}
+
+impl SummaryState {
+    fn new(history: Option<HistoryFile>, records: Vec<HistoryRecord>) -> Self {
+        let mut state = Self { history, summary: Summary::default(), pending_parsed_blocks: 0 };
+        for record in records {
+            state.apply(record);
+        }
+        state
+    }
+
+    /// applies the record to the summary
+    fn apply(&mut self, record: HistoryRecord) {
+        let summary = &mut self.summary;
+        match record {
+            HistoryRecord::Pledged { space } => summary.user_space_pledged = space,
+            HistoryRecord::PlottingFinished => summary.initial_plotting_finished = true,
+            HistoryRecord::Legacy { authored_count, vote_count, total_rewards } => {
+                summary.authored_count += authored_count;
+                summary.vote_count += vote_count;
+                summary.total_rewards += total_rewards;
+            }
+            HistoryRecord::Scanned { parsed_blocks, events } => {
+                let FarmingTotals { authored_count, vote_count, rewards } =
+                    FarmingTotals::from_events(&events);
+                summary.authored_count += authored_count;
+                summary.vote_count += vote_count;
+                summary.total_rewards += rewards;
+                summary.last_processed_block_num += parsed_blocks;
+            }
+        }
+    }
+
+    /// appends the record to the history, and applies it to the summary
+    async fn record(&mut self, record: HistoryRecord) -> Result<()> {
+        self.history
+            .as_mut()
+            .ok_or_else(|| eyre!("farming history is opened read-only"))?
+            .append(&record)
+            .await?;
+        self.apply(record);
+        Ok(())
+    }
+
+    /// summary, including the parsed blocks which are not recorded yet
+    fn summary(&self) -> Summary {
+        Summary {
+            last_processed_block_num: self.summary.last_processed_block_num
+                + self.pending_parsed_blocks,
+            ..self.summary
+        }
+    }
 }
 
 impl SummaryFile {
-    /// creates a new summary file Mutex
+    /// opens the farming history
     ///
-    /// if user_space_pledged is provided, it creates a new summary file
-    /// else, it tries to open the existing summary file
+    /// if user_space_pledged is provided, it creates the history if it doesn't
+    /// exist, and records the pledged space if it is changed, else, it opens
+    /// the existing history read-only, so that it can be read while the farmer
+    /// appends to it
+    ///
+    /// the legacy `summary.toml` is imported into the history if there is one
     #[instrument]
     pub(crate) async fn new(user_space_pledged: Option<ByteSize>) -> Result<SummaryFile> {
-        let summary_path = summary_path();
-        let summary_dir = summary_dir();
-
-        let mut summary_file;
-        // providing `Some` value for `user_space_pledged` means, we are creating a new
-        // file, so, first check if the file exists to not erase its content
-        if let Some(user_space_pledged) = user_space_pledged {
-            // File::create will truncate the existing file, so first
-            // check if the file exists, if not, `open` will return an error
-            // in this case, create the file and necessary directories
-            if File::open(&summary_path).await.is_err() {
-                let _ = create_dir_all(&summary_dir).await;
-                let _ = File::create(&summary_path).await;
-                let initialization = Summary {
-                    initial_plotting_finished: false,
-                    authored_count: 0,
-                    vote_count: 0,
-                    total_rewards: Rewards(0),
-                    user_space_pledged,
-                    last_processed_block_num: 0,
-                };
-                let summary_text =
-                    toml::to_string(&initialization).context("Failed to serialize Summary")?;
-                summary_file = OpenOptions::new()
-                    .read(true)
-                    .write(true)
-                    .truncate(true)
-                    .open(&summary_path)
+        let history_path = history_path();
+
+        if !history_path.exists() {
+            if legacy_summary_path().exists() {
+                import_legacy_summary().await.context("couldn't import the legacy summary")?;
+            } else if let Some(space) = user_space_pledged {
+                let _ = create_dir_all(summary_dir()).await;
+                HistoryFile::create(&history_path, &[HistoryRecord::Pledged { space }])
                     .await
-                    .context("couldn't open new summary file")?;
-                summary_file
-                    .write_all(summary_text.as_bytes())
+                    .context("couldn't create the farming history")?;
+            } else {
+                return Err(eyre!("farming history doesn't exist at `{}`", history_path.display()));
+            }
+        }
+
+        let mut state = match user_space_pledged {
+            Some(_) => {
+                let (history, records) = HistoryFile::open(&history_path)
                     .await
-                    .context("write to summary failed")?;
-                summary_file.flush().await.context("flush at creation failed")?;
-                summary_file
-                    .seek(std::io::SeekFrom::Start(0))
+                    .context("couldn't open the farming history")?;
+                SummaryState::new(Some(history), records)
+            }
+            None => {
+                let records = HistoryFile::read(&history_path)
                     .await
-                    .context("couldn't seek to the beginning of the summary file")?;
-
-                return Ok(SummaryFile { inner: Arc::new(Mutex::new(summary_file)) });
+                    .context("couldn't read the farming history")?;
+                SummaryState::new(None, records)
+            }
+        };
+        if let Some(space) = user_space_pledged {
+            if state.summary.user_space_pledged != space {
+                state.record(HistoryRecord::Pledged { space }).await?;
             }
         }
-        // for all the other cases, the SummaryFile should be there
-        summary_file = OpenOptions::new()
-            .read(true)
-            .write(true)
-            .open(&summary_path)
-            .await
-            .context("couldn't open existing summary file")?;
-        Ok(SummaryFile { inner: Arc::new(Mutex::new(summary_file)) })
+
+        Ok(SummaryFile { inner: Arc::new(Mutex::new(state)) })
     }
 
     /// Parses the summary file and returns [`Summary`]
     #[instrument]
     pub(crate) async fn parse(&self) -> Result<Summary> {
-        let (summary, _) = self.read_and_deserialize().await?;
-        Ok(summary)
+        Ok(self.inner.lock().await.summary())
     }
 
     /// updates the summary file, and returns the content of the new summary
@@ -127,71 +201,82 @@ impl SummaryFile {
     #[instrument]
     pub(crate) async fn update(
         &self,
-        SummaryUpdateFields {
-            is_plotting_finished,
-            new_authored_count,
-            new_parsed_blocks,
-        }: SummaryUpdateFields,
+        SummaryUpdateFields { is_plotting_finished, new_events, new_parsed_blocks }: SummaryUpdateFields,
     ) -> Result<Summary> {
-        let (mut summary, mut guard) = self.read_and_deserialize().await?;
+        let mut state = self.inner.lock().await;
 
-        if is_plotting_finished {
-            summary.initial_plotting_finished = true;
+        if is_plotting_finished && !state.summary.initial_plotting_finished {
+            state.record(HistoryRecord::PlottingFinished).await?;
         }
 
-        summary.authored_count += new_authored_count;
-
-        summary.last_processed_block_num += new_parsed_blocks;
+        state.pending_parsed_blocks += new_parsed_blocks;
+        if !new_events.is_empty() || state.pending_parsed_blocks >= PROGRESS_RECORD_INTERVAL {
+            let parsed_blocks = state.pending_parsed_blocks;
+            state.record(HistoryRecord::Scanned { parsed_blocks, events: new_events }).await?;
+            state.pending_parsed_blocks = 0;
+        }
 
-        let serialized_summary =
-            toml::to_string(&summary).context("Failed to serialize Summary")?;
+        Ok(state.summary())
+    }
+}
 
-        guard.set_len(0).await.context("couldn't truncate the summary file")?;
-        guard
-            .write_all(serialized_summary.as_bytes())
-            .await
-            .context("couldn't write to summary file")?;
-        guard.flush().await.context("flushing failed for summary file")?;
-        guard
-            .seek(std::io::SeekFrom::Start(0))
-            .await
-            .context("couldn't seek to the beginning of the summary file")?;
+/// imports the legacy `summary.toml` into the history, and keeps it as
+/// `summary.toml.bak`
+async fn import_legacy_summary() -> Result<()> {
+    let legacy_summary_path = legacy_summary_path();
+    migrate_file(&legacy_summary_path, migrate_summary).context("couldn't migrate summary file")?;
+    let contents = tokio::fs::read_to_string(&legacy_summary_path)
+        .await
+        .context("couldn't read the summary file")?;
+    let Summary {
+        initial_plotting_finished,
+        authored_count,
+        vote_count,
+        total_rewards,
+        user_space_pledged,
+        last_processed_block_num,
+    } = toml::from_str(&contents).context("couldn't parse the summary file")?;
 
-        Ok(summary)
+    let mut records = vec![
+        HistoryRecord::Pledged { space: user_space_pledged },
+        HistoryRecord::Legacy { authored_count, vote_count, total_rewards },
+        HistoryRecord::Scanned { parsed_blocks: last_processed_block_num, events: vec![] },
+    ];
+    if initial_plotting_finished {
+        records.push(HistoryRecord::PlottingFinished);
     }
+    HistoryFile::create(&history_path(), &records).await?;
 
-    /// Reads the file, serializes it into `Summary` and seeks to the beginning
-    /// of the file
-    #[instrument]
-    async fn read_and_deserialize(&self) -> Result<(Summary, MutexGuard<'_, File>)> {
-        let mut guard = self.inner.lock().await;
-        let mut contents = String::new();
-
-        guard
-            .read_to_string(&mut contents)
-            .await
-            .context("couldn't read the contents of the summary file")?;
-        let summary: Summary =
-            toml::from_str(&contents).context("couldn't serialize the summary content")?;
-
-        guard
-            .seek(std::io::SeekFrom::Start(0))
-            .await
-            .context("couldn't seek to the beginning of the summary file")?;
-
-        Ok((summary, guard))
-    }
+    let backup_path = legacy_summary_path.with_extension("toml.bak");
+    tokio::fs::rename(&legacy_summary_path, &backup_path)
+        .await
+        .context("couldn't back up the summary file")?;
+    println!(
+        "`{}` is imported into the farming history at `{}` (backup is saved at `{}`)",
+        legacy_summary_path.display(),
+        history_path().display(),
+        backup_path.display()
+    );
+
+    Ok(())
 }
 
-/// deletes the summary file
+/// deletes the farming history, along with the legacy summary file
 #[instrument]
 pub(crate) fn delete_summary() -> Result<()> {
-    remove_file(summary_path()).context("couldn't delete summary file")
+    let _ = remove_file(legacy_summary_path());
+    remove_file(history_path()).context("couldn't delete the farming history")
+}
+
+/// returns the path for the farming history
+#[instrument]
+pub(crate) fn history_path() -> PathBuf {
+    summary_dir().join("history.jsonl")
 }
 
-/// returns the path for the summary file
+/// returns the path for the summary file of the older versions
 #[instrument]
-pub(crate) fn summary_path() -> PathBuf {
+fn legacy_summary_path() -> PathBuf {
     summary_dir().join("summary.toml")
 }
 
@@ -0,0 +1,316 @@
+//! Append-only journal of the farming history, which replaces `summary.toml`.
+//!
+//! The journal is a JSON lines file, starting with a header holding the schema
+//! version. Every update is appended as a single line and synced to the disk,
+//! so a crash can only leave a partially written last line behind. The writer
+//! discards it when the journal is opened again, while readers only skip it,
+//! since it might be a record which is still being written.
+
+use std::path::{Path, PathBuf};
+
+use color_eyre::eyre::{eyre, Context, Result};
+use serde::{Deserialize, Serialize};
+use subspace_sdk::node::{BlockNumber, Hash};
+use subspace_sdk::ByteSize;
+use tokio::fs::{self, File, OpenOptions};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+use crate::summary::Rewards;
+
+/// current version of the history schema
+pub(crate) const HISTORY_VERSION: u32 = 1;
+
+/// first line of the journal
+#[derive(Debug, Serialize, Deserialize)]
+struct Header {
+    version: u32,
+}
+
+/// kind of a farming event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, derive_more::Display)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum FarmingEventKind {
+    /// block authored by one of our farms
+    #[display(fmt = "block")]
+    Block,
+    /// vote cast by one of our farms
+    #[display(fmt = "vote")]
+    Vote,
+}
+
+/// authored block or vote, credited to our reward address
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct FarmingEvent {
+    pub(crate) kind: FarmingEventKind,
+    /// number of the block which includes the event
+    pub(crate) block_number: BlockNumber,
+    /// hash of the block which includes the event
+    pub(crate) block_hash: Hash,
+    /// farm which won the event, `None` if it is not one of the local farms
+    pub(crate) farm: Option<PathBuf>,
+    /// unix timestamp of the block in seconds
+    pub(crate) timestamp: u64,
+    pub(crate) reward: Rewards,
+}
+
+/// single line of the journal
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub(crate) enum HistoryRecord {
+    /// space pledged to the network is set
+    Pledged { space: ByteSize },
+    /// initial plotting is finished
+    PlottingFinished,
+    /// totals imported from the legacy `summary.toml`, which doesn't have the
+    /// individual events
+    Legacy { authored_count: u64, vote_count: u64, total_rewards: Rewards },
+    /// given number of blocks are scanned, with the events found in them
+    Scanned {
+        parsed_blocks: BlockNumber,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        events: Vec<FarmingEvent>,
+    },
+}
+
+/// events of the records, with timestamps in the given range (inclusive, in
+/// unix seconds)
+pub(crate) fn events_in_range(
+    records: impl IntoIterator<Item = HistoryRecord>,
+    from: Option<u64>,
+    to: Option<u64>,
+) -> impl Iterator<Item = FarmingEvent> {
+    records
+        .into_iter()
+        .flat_map(|record| match record {
+            HistoryRecord::Scanned { events, .. } => events,
+            _ => vec![],
+        })
+        .filter(move |event| from.map_or(true, |from| event.timestamp >= from))
+        .filter(move |event| to.map_or(true, |to| event.timestamp <= to))
+}
+
+/// totals of the farming events
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct FarmingTotals {
+    pub(crate) authored_count: u64,
+    pub(crate) vote_count: u64,
+    pub(crate) rewards: Rewards,
+}
+
+impl FarmingTotals {
+    /// adds up the given events
+    pub(crate) fn from_events<'a>(events: impl IntoIterator<Item = &'a FarmingEvent>) -> Self {
+        let mut totals = Self::default();
+        for event in events {
+            match event.kind {
+                FarmingEventKind::Block => totals.authored_count += 1,
+                FarmingEventKind::Vote => totals.vote_count += 1,
+            }
+            totals.rewards += event.reward;
+        }
+        totals
+    }
+}
+
+/// append-only history journal
+#[derive(Debug)]
+pub(crate) struct HistoryFile {
+    path: PathBuf,
+    file: File,
+    /// length of the complete records, a failed append is truncated back to it
+    len: u64,
+}
+
+impl HistoryFile {
+    /// creates a new journal with the given records
+    ///
+    /// the journal is written to a temporary file first, and then renamed, so
+    /// that it either exists completely or not at all
+    pub(crate) async fn create(path: &Path, records: &[HistoryRecord]) -> Result<Self> {
+        let mut contents = to_line(&Header { version: HISTORY_VERSION })?;
+        for record in records {
+            contents.push_str(&to_line(record)?);
+        }
+
+        let temporary_path = path.with_extension("jsonl.tmp");
+        let mut file = File::create(&temporary_path)
+            .await
+            .with_context(|| format!("couldn't create `{}`", temporary_path.display()))?;
+        file.write_all(contents.as_bytes()).await.context("couldn't write the history")?;
+        file.sync_all().await.context("couldn't sync the history")?;
+        drop(file);
+        fs::rename(&temporary_path, path)
+            .await
+            .with_context(|| format!("couldn't move the history to `{}`", path.display()))?;
+
+        Self::open_for_append(path).await
+    }
+
+    /// opens the journal for appending and returns its records
+    ///
+    /// a partially written last record (i.e. after a crash) is discarded, so
+    /// only the process which appends to the journal should open it this way
+    pub(crate) async fn open(path: &Path) -> Result<(Self, Vec<HistoryRecord>)> {
+        let contents = read_contents(path).await?;
+        let (records, complete_len) = parse_contents(path, &contents)?;
+
+        if complete_len < contents.len() {
+            tracing::warn!(
+                partial = &contents[complete_len..],
+                "discarding the partially written history record"
+            );
+            let file = OpenOptions::new()
+                .write(true)
+                .open(path)
+                .await
+                .with_context(|| format!("couldn't open `{}`", path.display()))?;
+            file.set_len(complete_len as u64)
+                .await
+                .context("couldn't truncate the partially written history record")?;
+            file.sync_all().await.context("couldn't sync the history")?;
+        }
+
+        Ok((Self::open_for_append(path).await?, records))
+    }
+
+    /// reads the records of the journal without modifying it, so it can be
+    /// read while it is appended to
+    ///
+    /// a partially written last record is skipped
+    pub(crate) async fn read(path: &Path) -> Result<Vec<HistoryRecord>> {
+        let contents = read_contents(path).await?;
+        let (records, _) = parse_contents(path, &contents)?;
+        Ok(records)
+    }
+
+    /// reads the events of the journal with timestamps in the given range
+    /// (inclusive, in unix seconds), record by record, so that only the events
+    /// in the range are kept in memory
+    ///
+    /// the journal isn't modified, and a partially written last record is
+    /// skipped
+    pub(crate) async fn read_events(
+        path: &Path,
+        from: Option<u64>,
+        to: Option<u64>,
+    ) -> Result<Vec<FarmingEvent>> {
+        let file = File::open(path)
+            .await
+            .with_context(|| format!("couldn't open `{}`", path.display()))?;
+        let mut reader = BufReader::new(file);
+        let mut line = String::new();
+        let mut line_number = 0;
+        let mut events = vec![];
+        loop {
+            line.clear();
+            let read = reader
+                .read_line(&mut line)
+                .await
+                .with_context(|| format!("couldn't read `{}`", path.display()))?;
+            if read == 0 || !line.ends_with('\n') {
+                break;
+            }
+            line_number += 1;
+
+            if line_number == 1 {
+                parse_header(&line)?;
+            } else {
+                let record = parse_record(&line, line_number)?;
+                events.extend(events_in_range([record], from, to));
+            }
+        }
+        if line_number == 0 {
+            return Err(eyre!("history at `{}` is empty", path.display()));
+        }
+
+        Ok(events)
+    }
+
+    async fn open_for_append(path: &Path) -> Result<Self> {
+        let file = OpenOptions::new()
+            .append(true)
+            .open(path)
+            .await
+            .with_context(|| format!("couldn't open `{}`", path.display()))?;
+        let len = file.metadata().await.context("couldn't read the history metadata")?.len();
+        Ok(Self { path: path.to_owned(), file, len })
+    }
+
+    /// appends the record to the journal, and syncs it to the disk
+    ///
+    /// a record which fails to be written is truncated, so that it doesn't
+    /// corrupt the records appended after it
+    pub(crate) async fn append(&mut self, record: &HistoryRecord) -> Result<()> {
+        let line = to_line(record)?;
+        let result: Result<()> = async {
+            self.file
+                .write_all(line.as_bytes())
+                .await
+                .with_context(|| format!("couldn't append to `{}`", self.path.display()))?;
+            self.file.sync_data().await.context("couldn't sync the history")
+        }
+        .await;
+
+        match result {
+            Ok(()) => {
+                self.len += line.len() as u64;
+                Ok(())
+            }
+            Err(error) => {
+                if let Err(truncate_error) = self.file.set_len(self.len).await {
+                    tracing::error!(
+                        %truncate_error,
+                        "couldn't truncate the partially written history record"
+                    );
+                }
+                Err(error)
+            }
+        }
+    }
+}
+
+async fn read_contents(path: &Path) -> Result<String> {
+    fs::read_to_string(path).await.with_context(|| format!("couldn't read `{}`", path.display()))
+}
+
+/// parses the complete records of the journal, and returns them along with
+/// their length, a partially written last record isn't parsed
+fn parse_contents(path: &Path, contents: &str) -> Result<(Vec<HistoryRecord>, usize)> {
+    let complete_len = contents.rfind('\n').map_or(0, |end| end + 1);
+    let mut lines = contents[..complete_len].lines();
+
+    let header = lines.next().ok_or_else(|| eyre!("history at `{}` is empty", path.display()))?;
+    parse_header(header)?;
+    let records = lines
+        .enumerate()
+        .map(|(line, record)| parse_record(record, line + 2))
+        .collect::<Result<Vec<HistoryRecord>>>()?;
+
+    Ok((records, complete_len))
+}
+
+/// parses the header of the journal, and checks that its version is supported
+fn parse_header(header: &str) -> Result<()> {
+    let Header { version } =
+        serde_json::from_str(header).context("couldn't parse the history header")?;
+    if version > HISTORY_VERSION {
+        return Err(eyre!(
+            "history version `{version}` is newer than the supported version `{HISTORY_VERSION}`, \
+             please upgrade pulsar"
+        ));
+    }
+    Ok(())
+}
+
+/// parses the record at the given line of the journal
+fn parse_record(record: &str, line: usize) -> Result<HistoryRecord> {
+    serde_json::from_str(record)
+        .with_context(|| format!("couldn't parse the history record at line {line}"))
+}
+
+/// serializes the value as a single JSON line
+fn to_line(value: &impl Serialize) -> Result<String> {
+    let mut line = serde_json::to_string(value).context("couldn't serialize the history")?;
+    line.push('\n');
+    Ok(line)
+}
@@ -1,23 +1,56 @@
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::time::Duration;
 
+use chrono::NaiveTime;
 use rand::rngs::SmallRng;
 use rand::{Rng, SeedableRng};
+use subspace_sdk::farmer::{
+    BenchmarkKind, BenchmarkReport, FarmResize, ScrubIssue, ScrubReport, SectorEventKind,
+};
+use subspace_sdk::node::Hash;
 use subspace_sdk::ByteSize;
 
-use crate::config::ChainConfig;
+use crate::commands::benchmark::describe_benchmark;
+use crate::commands::config::{get_value, parse_value, set_value};
+use crate::commands::export::{export_records, to_csv};
+use crate::commands::farm::output::{event_line, FarmEvent};
+use crate::commands::farm::plotting::{plotting_state, PlottingState};
+use crate::commands::farm::resize::describe_resize;
+use crate::commands::farm::scrub::{describe_scrub, ensure_repaired};
+use crate::commands::farm::{farm_changes, FarmChanges, ReplottingProgress};
+use crate::commands::init::{get_config_from_args, InitArgs};
+use crate::commands::service::{home_dir_from_passwd, unit_file, ServiceScope};
+use crate::config::migration::{migrate_config, migrate_summary, CONFIG_VERSION};
+use crate::config::{parse_config_contents, ChainConfig, FarmConfig, FarmerConfig};
+use crate::status::{FarmStatus, NodeStatus, Status};
+use crate::summary::history::*;
 use crate::summary::*;
 use crate::utils::{
-    apply_extra_options, custom_log_dir, directory_parser, farm_directory_getter,
-    node_directory_getter, node_name_parser, reward_address_parser, size_parser, yes_or_no_parser,
+    apply_extra_options, custom_log_dir, date_parser, directory_parser, farm_directory_getter,
+    log_filter, node_directory_getter, node_name_parser, reward_address_parser, size_parser,
+    validate_extra_options, yes_or_no_parser,
 };
 
+fn random_farming_event(rng: &mut SmallRng) -> FarmingEvent {
+    FarmingEvent {
+        kind: if rng.gen() { FarmingEventKind::Block } else { FarmingEventKind::Vote },
+        block_number: rng.gen_range(1..1_000_000),
+        block_hash: Hash::repeat_byte(rng.gen()),
+        farm: rng.gen::<bool>().then(|| PathBuf::from("/tmp/farm")),
+        timestamp: rng.gen_range(1_700_000_000..1_800_000_000),
+        reward: Rewards(rng.gen_range(1..10_u128.pow(20))),
+    }
+}
+
 async fn update_summary_file_randomly(summary_file: SummaryFile) {
     let mut rng = SmallRng::from_entropy();
 
     for _ in 0..10 {
         let update_fields = SummaryUpdateFields {
             is_plotting_finished: false,
-            new_authored_count: rng.gen_range(1..10),
+            new_events: (0..rng.gen_range(0..5)).map(|_| random_farming_event(&mut rng)).collect(),
             new_parsed_blocks: rng.gen_range(1..100),
         };
         let result = summary_file.update(update_fields).await;
@@ -40,12 +73,16 @@ async fn summary_file_integration() {
         SummaryFile::new(Some(farm_size)).await.expect("Failed to create summary file");
 
     // sequential update trial
-    let update_fields = SummaryUpdateFields {
-        is_plotting_finished: true,
-        new_authored_count: 11,
-        new_parsed_blocks: 101,
-    };
-    summary_file.update(update_fields).await.expect("Failed to update summary file");
+    let mut rng = SmallRng::from_entropy();
+    let new_events = (0..3).map(|_| random_farming_event(&mut rng)).collect::<Vec<_>>();
+    let totals = FarmingTotals::from_events(&new_events);
+    let update_fields =
+        SummaryUpdateFields { is_plotting_finished: true, new_events, new_parsed_blocks: 101 };
+    let summary = summary_file.update(update_fields).await.expect("Failed to update summary file");
+    assert_eq!(summary.authored_count, totals.authored_count);
+    assert_eq!(summary.vote_count, totals.vote_count);
+    assert_eq!(summary.total_rewards, totals.rewards);
+    assert_eq!(summary.last_processed_block_num, 101);
 
     // create two concurrent tasks, they try to write to summary file 5 times each
     let task1 = tokio::spawn(update_summary_file_randomly(summary_file.clone()));
@@ -58,12 +95,187 @@ async fn summary_file_integration() {
     assert!(result2.is_ok(), "Task 2 encountered an error: {:?}", result2.unwrap_err());
 
     // parse the summary after updates
-    summary_file.parse().await.expect("Failed to parse the summary file after updates");
+    let summary =
+        summary_file.parse().await.expect("Failed to parse the summary file after updates");
+
+    // the summary should be restored from the history
+    drop(summary_file);
+    let reopened_summary = SummaryFile::new(None)
+        .await
+        .expect("Failed to reopen the summary file")
+        .parse()
+        .await
+        .expect("Failed to parse the reopened summary file");
+    assert!(reopened_summary.initial_plotting_finished);
+    assert_eq!(reopened_summary.authored_count, summary.authored_count);
+    assert_eq!(reopened_summary.vote_count, summary.vote_count);
+    assert_eq!(reopened_summary.total_rewards, summary.total_rewards);
+    // scanned blocks without events might not be recorded yet
+    assert!(reopened_summary.last_processed_block_num <= summary.last_processed_block_num);
 
     // Clean up the summary file
     delete_summary().expect("summary deletion failed");
 }
 
+#[tokio::test]
+async fn history_journal() {
+    let mut rng = SmallRng::from_entropy();
+    let path = std::env::temp_dir().join(format!("pulsar-history-test-{}.jsonl", rng.gen::<u64>()));
+    let records = vec![
+        HistoryRecord::Pledged { space: ByteSize::gb(1) },
+        HistoryRecord::Legacy { authored_count: 1, vote_count: 2, total_rewards: Rewards(3) },
+    ];
+
+    let mut history = HistoryFile::create(&path, &records).await.expect("couldn't create history");
+    let scanned = HistoryRecord::Scanned {
+        parsed_blocks: 10,
+        events: vec![random_farming_event(&mut rng), random_farming_event(&mut rng)],
+    };
+    history.append(&scanned).await.expect("couldn't append to history");
+    history.append(&HistoryRecord::PlottingFinished).await.expect("couldn't append to history");
+    drop(history);
+
+    // simulate a crash in the middle of writing a record
+    let mut contents = std::fs::read_to_string(&path).unwrap();
+    let complete_len = contents.len();
+    contents.push_str(r#"{"type":"scanned","parsed_bl"#);
+    std::fs::write(&path, &contents).unwrap();
+    let mut expected_records = records;
+    expected_records.extend([scanned, HistoryRecord::PlottingFinished]);
+
+    // readers skip the partial record, since it might still be written
+    let read_records = HistoryFile::read(&path).await.expect("couldn't read history");
+    assert_eq!(read_records, expected_records);
+    assert_eq!(events_in_range(read_records.clone(), None, None).count(), 2);
+    assert!(events_in_range(read_records, Some(u64::MAX), None).next().is_none());
+    assert_eq!(HistoryFile::read_events(&path, None, None).await.unwrap().len(), 2);
+    assert!(HistoryFile::read_events(&path, Some(u64::MAX), None).await.unwrap().is_empty());
+    assert_eq!(std::fs::metadata(&path).unwrap().len(), contents.len() as u64);
+
+    // the writer discards it
+    let (_, reopened_records) = HistoryFile::open(&path).await.expect("couldn't open history");
+    assert_eq!(reopened_records, expected_records);
+    assert_eq!(std::fs::metadata(&path).unwrap().len(), complete_len as u64);
+
+    // histories of the newer versions are rejected
+    std::fs::write(&path, format!("{{\"version\":{}}}\n", HISTORY_VERSION + 1)).unwrap();
+    assert!(HistoryFile::open(&path).await.is_err());
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn date_parsing() {
+    assert_eq!(date_parser("1970-01-02").unwrap(), 86400);
+    assert_eq!(date_parser("2024-01-31").unwrap(), 1_706_659_200);
+    assert_eq!(date_parser("2024-01-31T12:00:00Z").unwrap(), 1_706_702_400);
+    assert_eq!(date_parser("2024-01-31T12:00:00+01:00").unwrap(), 1_706_698_800);
+    assert!(date_parser("31/01/2024").is_err());
+    assert!(date_parser("1969-12-31").is_err());
+}
+
+#[tokio::test]
+async fn export_csv() {
+    let path = std::env::temp_dir()
+        .join(format!("pulsar-export-test-{}.jsonl", SmallRng::from_entropy().gen::<u64>()));
+    // events are timed by the timestamp inherents of their blocks, which are in
+    // milliseconds
+    let event = |block_number, timestamp_millis: u64, reward| FarmingEvent {
+        kind: FarmingEventKind::Vote,
+        block_number,
+        block_hash: Hash::zero(),
+        farm: None,
+        timestamp: timestamp_millis / 1000,
+        reward: Rewards(reward),
+    };
+    let events = vec![
+        // 2024-01-30T23:59:59.900Z
+        event(41, 1_706_659_199_900, 1),
+        // 2024-01-31T12:00:00.250Z
+        FarmingEvent {
+            kind: FarmingEventKind::Block,
+            block_hash: Hash::repeat_byte(0xab),
+            farm: Some(PathBuf::from("/farms/one, two")),
+            ..event(42, 1_706_702_400_250, 1_500_000_000_000_000_000)
+        },
+        // 2024-01-31T23:59:59.999Z
+        event(43, 1_706_745_599_999, 10_u128.pow(17)),
+        // 2024-02-01T00:00:00.001Z
+        event(44, 1_706_745_600_001, 1),
+    ];
+    HistoryFile::create(&path, &[HistoryRecord::Scanned { parsed_blocks: 4, events }])
+        .await
+        .unwrap();
+
+    let records = export_records(&path, Some("2024-01-31"), Some("2024-01-31")).await.unwrap();
+    let csv = to_csv(&records);
+    let lines = csv.lines().collect::<Vec<_>>();
+    assert_eq!(lines[0], "kind,block_number,block_hash,farm,timestamp,reward,reward_shannons");
+    assert_eq!(
+        lines[1],
+        format!(
+            "block,42,0x{},\"/farms/one, two\",2024-01-31T12:00:00Z,1.5,1500000000000000000",
+            "ab".repeat(32)
+        )
+    );
+    assert_eq!(
+        lines[2],
+        format!("vote,43,0x{},,2024-01-31T23:59:59Z,0.1,100000000000000000", "00".repeat(32))
+    );
+    assert_eq!(lines.len(), 3);
+
+    let records = export_records(&path, Some("2024-01-31T12:00:01Z"), None).await.unwrap();
+    assert_eq!(records.iter().map(|record| record.block_number).collect::<Vec<_>>(), [43, 44]);
+    assert!(export_records(&path, Some("2024-02-01"), Some("2024-01-31")).await.is_err());
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn farm_events_json() {
+    let directory = PathBuf::from("/farms/0");
+    let event = FarmEvent::PlottingProgress {
+        farm: 0,
+        directory: &directory,
+        plotted_sectors: 3,
+        sectors_in_flight: 2,
+        total_sectors: 10,
+    };
+    assert_eq!(
+        serde_json::to_value(&event).unwrap(),
+        serde_json::json!({
+            "event": "plotting_progress",
+            "farm": 0,
+            "directory": "/farms/0",
+            "plotted_sectors": 3,
+            "sectors_in_flight": 2,
+            "total_sectors": 10,
+        })
+    );
+
+    let farming_event = FarmingEvent {
+        kind: FarmingEventKind::Vote,
+        block_number: 7,
+        block_hash: Hash::zero(),
+        farm: None,
+        timestamp: 1_706_702_400,
+        reward: Rewards(10),
+    };
+    // the time of the line and the time of the block are both kept
+    let line = event_line(FarmEvent::from(&farming_event)).unwrap();
+    assert_eq!(line.matches("\"timestamp\":").count(), 1, "{line}");
+    let value = serde_json::from_str::<serde_json::Value>(&line).unwrap();
+    assert_eq!(value["event"], "farmed");
+    assert_eq!(value["kind"], "vote");
+    assert_eq!(value["block_timestamp"], 1_706_702_400);
+    assert_eq!(value["reward"], "10");
+    assert!(chrono::DateTime::parse_from_rfc3339(value["timestamp"].as_str().unwrap()).is_ok());
+    assert_eq!(
+        serde_json::to_value(FarmEvent::Synced).unwrap(),
+        serde_json::json!({ "event": "synced" })
+    );
+}
+
 #[test]
 fn extra_options() {
     let cargo_toml = toml::toml! {
@@ -94,6 +306,609 @@ fn extra_options() {
     assert_eq!(apply_extra_options(&cargo_toml, extra).unwrap(), result);
 }
 
+#[test]
+fn extra_options_validation() {
+    #[derive(serde::Serialize, serde::Deserialize)]
+    struct Options {
+        name: String,
+        #[serde(default)]
+        port: u16,
+    }
+    let options = Options { name: "pulsar".to_owned(), port: 0 };
+
+    let ineffective_keys = validate_extra_options(
+        &options,
+        &toml::toml! {
+            name = "pulsar-2"
+            unknown = true
+        },
+    )
+    .unwrap();
+    assert_eq!(ineffective_keys, vec!["unknown".to_owned()]);
+
+    let error = validate_extra_options(&options, &toml::toml! { port = "not a port" }).unwrap_err();
+    assert!(error.to_string().contains("`port`"));
+}
+
+#[test]
+fn config_keys() {
+    let mut config = toml::Value::Table(toml::toml! {
+        chain = "Gemini3h"
+
+        [node]
+        name = "pulsar"
+
+        [[farmer.farms]]
+        directory = "/farms/first"
+        size = "2GB"
+    });
+
+    assert_eq!(get_value(&config, "farmer.farms.0.size").unwrap().as_str(), Some("2GB"));
+    assert!(get_value(&config, "farmer.farms.1.size").is_err());
+    assert!(get_value(&config, "node.unknown").is_err());
+
+    // string values stay strings, even if they look like numbers
+    let name = parse_value("123", get_value(&config, "node.name").ok());
+    set_value(&mut config, "node.name", name).unwrap();
+    assert_eq!(get_value(&config, "node.name").unwrap().as_str(), Some("123"));
+
+    // missing tables are created
+    set_value(&mut config, "node.advanced.enable_domains", parse_value("true", None)).unwrap();
+    assert_eq!(get_value(&config, "node.advanced.enable_domains").unwrap().as_bool(), Some(true));
+
+    set_value(&mut config, "farmer.farms.0.size", parse_value("3GB", None)).unwrap();
+    assert_eq!(get_value(&config, "farmer.farms.0.size").unwrap().as_str(), Some("3GB"));
+    assert!(set_value(&mut config, "farmer.farms.1.size", parse_value("3GB", None)).is_err());
+    assert!(set_value(&mut config, "node..name", parse_value("x", None)).is_err());
+}
+
+fn farmer_config_table(extra: toml::Table) -> toml::Table {
+    let reward_address =
+        reward_address_parser("5FWr7j9DW4uy7K1JLmFN2R3eoae35PFDUfW7G42ARpBEUaN7").unwrap();
+    let mut table = toml::Table::new();
+    table.insert("reward_address".to_owned(), toml::Value::try_from(reward_address).unwrap());
+    table.extend(extra);
+    table
+}
+
+#[test]
+fn single_farm_config_compatibility() {
+    let table = farmer_config_table(toml::toml! {
+        farm_directory = "/farms/old"
+        farm_size = "2GB"
+    });
+    let config: FarmerConfig = table.try_into().unwrap();
+
+    assert_eq!(
+        config.farms,
+        vec![FarmConfig {
+            directory: "/farms/old".into(),
+            size: "2GB".parse().unwrap(),
+            cache_size: None
+        }]
+    );
+
+    let table = farmer_config_table(toml::toml! {
+        farm_directory = "/farms/old"
+    });
+    assert!(table.try_into::<FarmerConfig>().is_err());
+}
+
+#[test]
+fn multi_farm_config() {
+    let table = farmer_config_table(toml::toml! {
+        [[farms]]
+        directory = "/farms/first"
+        size = "2GB"
+
+        [[farms]]
+        directory = "/farms/second"
+        size = "3GB"
+    });
+    let config: FarmerConfig = table.try_into().unwrap();
+
+    assert_eq!(config.farms.len(), 2);
+    assert_eq!(config.total_farm_size(), ByteSize::gb(5));
+
+    // farms are written back as `[[farmer.farms]]` entries
+    let serialized = toml::Table::try_from(&config).unwrap();
+    assert!(serialized.get("farm_directory").is_none());
+    assert_eq!(serialized["farms"].as_array().unwrap().len(), 2);
+}
+
+#[test]
+fn piece_cache_size_config() {
+    let mut config = toml::toml! {
+        version = 2
+        chain = "Dev"
+
+        [node]
+        directory = "/node"
+        name = "pulsar"
+    };
+    let farmer = farmer_config_table(toml::toml! {
+        farms = [
+            { directory = "/farms/0", size = "2GB" },
+            { directory = "/farms/1", size = "4GB", cache_size = "1GB" },
+        ]
+        advanced = { cache_size = "10%" }
+    });
+    config.insert("farmer".to_owned(), toml::Value::Table(farmer));
+    let mut config = parse_config_contents(&toml::to_string(&config).unwrap()).unwrap();
+    assert_eq!(config.farmer.advanced.cache_size, "10%".parse().unwrap());
+    assert_eq!(config.farmer.farms[0].cache_size, None);
+    assert_eq!(config.farmer.farms[1].cache_size, Some(ByteSize::gb(1).into()));
+    assert!(config.validate().is_ok());
+
+    // the cache has to leave some space for plotting
+    config.farmer.farms[1].cache_size = Some(ByteSize::gb(4).into());
+    assert!(config.validate().is_err());
+    config.farmer.farms[1].cache_size = None;
+    config.farmer.advanced.cache_size = "100%".parse().unwrap();
+    assert!(config.validate().is_err());
+
+    // default cache size isn't written
+    config.farmer.advanced.cache_size = Default::default();
+    let serialized = toml::Table::try_from(&config.farmer).unwrap();
+    assert!(serialized.get("advanced").and_then(|advanced| advanced.get("cache_size")).is_none());
+}
+
+#[test]
+fn non_interactive_init() {
+    let base_dir = std::env::temp_dir().join(format!("pulsar-init-{}", std::process::id()));
+    let farm_dir = base_dir.join("farm");
+    let node_dir = base_dir.join("node");
+    let args = || InitArgs {
+        reward_address: Some("5FWr7j9DW4uy7K1JLmFN2R3eoae35PFDUfW7G42ARpBEUaN7".to_owned()),
+        farm_directories: vec![farm_dir.clone()],
+        farm_sizes: vec!["3GB".to_owned()],
+        node_directory: Some(node_dir.clone()),
+        chain: Some("dev".to_owned()),
+        node_name: Some("pulsar".to_owned()),
+        ..Default::default()
+    };
+
+    // directories are never created without `--create-dirs`
+    assert!(get_config_from_args(args()).is_err());
+    assert!(!farm_dir.exists());
+
+    let config = get_config_from_args(InitArgs { create_dirs: true, ..args() }).unwrap();
+    assert_eq!(
+        config.farmer.farms,
+        vec![FarmConfig { directory: farm_dir, size: ByteSize::gb(3), cache_size: None }]
+    );
+    assert_eq!(config.node.directory, node_dir);
+    assert!(matches!(config.chain, ChainConfig::Dev));
+
+    // invalid values are rejected instead of prompted again
+    assert!(
+        get_config_from_args(InitArgs { farm_sizes: vec!["1GB".to_owned()], ..args() }).is_err()
+    );
+    assert!(
+        get_config_from_args(InitArgs { node_name: Some("root".to_owned()), ..args() }).is_err()
+    );
+    assert!(get_config_from_args(InitArgs { reward_address: None, ..args() }).is_err());
+
+    // seed file values are overridden by the arguments
+    let seed_file = base_dir.join("settings.toml");
+    std::fs::write(&seed_file, toml::to_string_pretty(&config).unwrap()).unwrap();
+    let config = get_config_from_args(InitArgs {
+        from_file: Some(seed_file),
+        node_name: Some("seeded".to_owned()),
+        ..Default::default()
+    })
+    .unwrap();
+    assert_eq!(config.node.name, "seeded");
+    assert_eq!(config.farmer.farms.len(), 1);
+
+    std::fs::remove_dir_all(base_dir).unwrap();
+}
+
+#[test]
+fn config_migration() {
+    let mut config = toml::toml! {
+        chain = "Gemini3g"
+
+        [farmer]
+        farm_directory = "/farms/old"
+        farm_size = "2GB"
+        cache_size = "1GB"
+
+        [node]
+        directory = "/node"
+        name = "pulsar"
+        enable_domains = true
+    };
+
+    let farmer = config["farmer"].as_table().unwrap().clone();
+    config.insert("farmer".to_owned(), toml::Value::Table(farmer_config_table(farmer)));
+
+    let changes = migrate_config(&mut config).unwrap();
+    assert_eq!(changes.len(), 7);
+    assert_eq!(config["version"].as_integer(), Some(CONFIG_VERSION.into()));
+    assert_eq!(config["chain"].as_str(), Some("Gemini3h"));
+    assert_eq!(config["farmer"]["farms"][0]["directory"].as_str(), Some("/farms/old"));
+    // the cache size was not applied before version `2`, so it isn't kept
+    assert!(config["farmer"]["advanced"].get("cache_size").is_none());
+    assert_eq!(config["node"]["advanced"]["enable_domains"].as_bool(), Some(true));
+
+    // migrated config is up to date
+    assert!(migrate_config(&mut config).unwrap().is_empty());
+    let migrated = parse_config_contents(&toml::to_string(&config).unwrap()).unwrap();
+    assert_eq!(migrated.version, CONFIG_VERSION);
+    assert!(migrated.node.advanced.enable_domains);
+
+    config.insert("version".to_owned(), toml::Value::Integer(i64::from(CONFIG_VERSION) + 1));
+    assert!(migrate_config(&mut config).is_err());
+
+    let mut summary = toml::toml! {
+        initial_plotting_finished = true
+        authored_count = 1
+        user_space_pledged = "1.0 GB"
+        last_processed_block_num = 100
+    };
+    assert_eq!(migrate_summary(&mut summary).unwrap().len(), 1);
+    let migrated: Summary = summary.clone().try_into().unwrap();
+    assert_eq!(migrated.last_processed_block_num, 0);
+    assert_eq!(migrated.authored_count, 0);
+    assert!(migrated.initial_plotting_finished);
+
+    // migrated summary is up to date
+    assert!(migrate_summary(&mut summary).unwrap().is_empty());
+}
+
+#[test]
+fn service_unit() {
+    let mut config = toml::toml! {
+        version = 1
+        chain = "Dev"
+
+        [node]
+        directory = "/var/lib/pulsar/node"
+        name = "pulsar"
+    };
+    let farmer = farmer_config_table(toml::toml! {
+        farms = [{ directory = "/farms/0", size = "2GB" }]
+    });
+    config.insert("farmer".to_owned(), toml::Value::Table(farmer));
+    let mut config = parse_config_contents(&toml::to_string(&config).unwrap()).unwrap();
+    let executable = Path::new("/usr/local/bin/pulsar");
+
+    let unit = unit_file(&config, executable, ServiceScope::User, "farmer");
+    assert!(unit.contains("ExecStart=\"/usr/local/bin/pulsar\" farm --headless\n"));
+    assert!(unit.contains("WorkingDirectory=/var/lib/pulsar/node\n"));
+    assert!(unit.contains("LimitNOFILE="));
+    assert!(unit.contains("Restart=on-failure\n"));
+    assert!(unit.contains("WantedBy=default.target\n"));
+    assert!(!unit.contains("User="));
+
+    let unit = unit_file(&config, executable, ServiceScope::System, "farmer");
+    assert!(unit.contains("User=farmer\n"));
+    assert!(unit.contains("WantedBy=multi-user.target\n"));
+
+    let executable = Path::new("/home/farmer/my bin/50% \"pulsar\"");
+    let unit = unit_file(&config, executable, ServiceScope::User, "farmer");
+    assert!(
+        unit.contains("ExecStart=\"/home/farmer/my bin/50%% \\\"pulsar\\\"\" farm --headless\n")
+    );
+
+    config.node.directory = PathBuf::from("/home/farmer/%h node");
+    let unit = unit_file(&config, executable, ServiceScope::User, "farmer");
+    assert!(unit.contains("WorkingDirectory=/home/farmer/%%h node\n"));
+
+    // the config of the user who ran `sudo` is found through their home directory
+    assert_eq!(
+        home_dir_from_passwd("farmer:x:1000:1000:Farmer,,,:/home/farmer:/bin/bash\n"),
+        Some(PathBuf::from("/home/farmer"))
+    );
+    assert_eq!(home_dir_from_passwd("farmer:x:1000:1000"), None);
+}
+
+#[test]
+fn log_level_validation() {
+    assert!(log_filter(Some("info,subspace_farmer=debug")).is_ok());
+    assert!(log_filter(Some("subspace_farmer=loud")).is_err());
+
+    let mut config = toml::toml! {
+        version = 1
+        chain = "Dev"
+        log_level = "subspace_farmer=loud"
+
+        [node]
+        directory = "/node"
+        name = "pulsar"
+    };
+    let farmer = farmer_config_table(toml::toml! {
+        farms = [{ directory = "/farms/0", size = "2GB" }]
+    });
+    config.insert("farmer".to_owned(), toml::Value::Table(farmer));
+    let mut config = parse_config_contents(&toml::to_string(&config).unwrap()).unwrap();
+    assert!(config.validate().is_err());
+
+    config.log_level = Some("debug".to_owned());
+    assert!(config.validate().is_ok());
+}
+
+#[test]
+fn node_rpc_url_config() {
+    let mut config = toml::toml! {
+        version = 1
+        chain = "Dev"
+
+        [node]
+        directory = "/node"
+        name = "pulsar"
+    };
+    let farmer = farmer_config_table(toml::toml! {
+        node_rpc_url = "http://127.0.0.1:9944"
+        farms = [{ directory = "/farms/0", size = "2GB" }]
+    });
+    config.insert("farmer".to_owned(), toml::Value::Table(farmer));
+    let mut config = parse_config_contents(&toml::to_string(&config).unwrap()).unwrap();
+    assert_eq!(config.farmer.node_rpc_url.as_deref(), Some("http://127.0.0.1:9944"));
+    assert!(config.validate().is_err());
+
+    config.farmer.node_rpc_url = Some("ws://127.0.0.1:9944".to_owned());
+    assert!(config.validate().is_ok());
+
+    // the url is only written when it is set
+    config.farmer.node_rpc_url = None;
+    let serialized = toml::Table::try_from(&config.farmer).unwrap();
+    assert!(serialized.get("node_rpc_url").is_none());
+}
+
+#[test]
+fn farm_changes_on_reload() {
+    let farm = |directory: &str| FarmConfig {
+        directory: directory.into(),
+        size: ByteSize::gb(2),
+        cache_size: None,
+    };
+    let farmed = vec![PathBuf::from("/farms/0"), PathBuf::from("/farms/1")];
+
+    let changes = farm_changes(&farmed, vec![farm("/farms/1"), farm("/farms/2")]);
+    assert_eq!(changes.added, vec![farm("/farms/2")]);
+    assert_eq!(changes.removed, vec![PathBuf::from("/farms/0")]);
+
+    // farms are compared by their directory, resizing is not a change
+    let mut resized = farm("/farms/0");
+    resized.size = ByteSize::gb(4);
+    assert_eq!(farm_changes(&farmed, vec![resized, farm("/farms/1")]), FarmChanges::default());
+}
+
+#[test]
+fn farm_resize_description() {
+    let plan = |current_space, current_sectors, new_space, new_sectors| {
+        let mut plan = FarmResize::default();
+        plan.current_space = current_space;
+        plan.current_sectors = current_sectors;
+        plan.new_space = new_space;
+        plan.new_sectors = new_sectors;
+        plan
+    };
+
+    let shrink = describe_resize(&plan(ByteSize::gb(4), 4, ByteSize::gb(2), 2));
+    assert!(shrink.starts_with(&format!(
+        "Farm is shrunk from {} (4 sectors) to {} (2 sectors)",
+        ByteSize::gb(4),
+        ByteSize::gb(2)
+    )));
+    assert!(shrink.ends_with("up to 2 plotted sectors are dropped"));
+
+    let grow = describe_resize(&plan(ByteSize::gb(2), 2, ByteSize::gb(4), 4));
+    assert!(grow.starts_with(&format!(
+        "Farm is grown from {} (2 sectors) to {} (4 sectors)",
+        ByteSize::gb(2),
+        ByteSize::gb(4)
+    )));
+    assert!(grow.ends_with("the new sectors are plotted when it is farmed"));
+}
+
+#[test]
+fn farm_scrub_description() {
+    let mut report = ScrubReport::default();
+    report.plotted_sectors = 3;
+    report.piece_cache_elements = 5;
+    let healthy = describe_scrub(&report);
+    assert!(healthy.contains("checked 3 plotted sectors and 5 piece cache elements"));
+    assert!(healthy.ends_with("no issues found"));
+    assert!(ensure_repaired(&[report.clone()]).is_ok());
+
+    report.issues = vec![ScrubIssue::Sector(1), ScrubIssue::PieceCacheElement(4)];
+    let unhealthy = describe_scrub(&report);
+    assert!(unhealthy.contains("found 2 issue(s)"));
+    assert_eq!(
+        unhealthy.lines().skip(1).collect::<Vec<_>>(),
+        ["  - Sector 1 is corrupted", "  - Piece cache element 4 is corrupted"]
+    );
+    // the command exits with an error, unless the issues are repaired
+    let error = ensure_repaired(&[report.clone()]).unwrap_err();
+    assert!(error.to_string().contains("run `pulsar farm scrub --repair`"));
+
+    report.repaired = true;
+    let repaired = describe_scrub(&report);
+    assert!(repaired.ends_with(
+        "Repairable issues are repaired, corrupted and missing sectors are replotted the next \
+         time the farm is farmed"
+    ));
+    assert!(ensure_repaired(&[report]).is_ok());
+}
+
+#[test]
+fn benchmark_description() {
+    let times = [Duration::from_millis(300), Duration::from_millis(500)];
+    let report = BenchmarkReport::new(BenchmarkKind::Audit, 4, ByteSize::mib(1), 2, &times);
+    let description = describe_benchmark(&report);
+    let lines = description.lines().map(str::trim).collect::<Vec<_>>();
+    assert_eq!(
+        lines[0],
+        "Benchmarked the audit of 4 plotted sectors, 2 sample(s) with 2 farming thread(s):"
+    );
+    assert_eq!(lines[1], format!("Throughput: 10.00 sectors/s ({}/s)", ByteSize::mib(10)));
+    assert_eq!(lines[2], "Latency per sector: 100.00ms");
+    assert_eq!(lines[3], "Time per slot: 400.00ms");
+    assert!(lines[4].ends_with("600.00ms left until the slot deadline"));
+
+    let report = BenchmarkReport::new(
+        BenchmarkKind::Prove,
+        4,
+        ByteSize::mib(1),
+        2,
+        &[Duration::from_millis(1_250)],
+    );
+    let description = describe_benchmark(&report);
+    assert!(description.contains("Throughput: 0.80 proofs/s"));
+    let last_line = description.lines().last().unwrap();
+    assert!(last_line.contains("Too slow:"));
+    assert!(last_line.ends_with("the slot deadline is missed by 250.00ms, solutions are lost"));
+}
+
+#[test]
+fn farming_thread_pool_size_config() {
+    let table = farmer_config_table(toml::toml! {
+        farms = [{ directory = "/farms/0", size = "2GB" }]
+    });
+    let config: FarmerConfig = table.try_into().unwrap();
+    assert_eq!(config.farming_thread_pool_size().unwrap(), None);
+
+    let table = farmer_config_table(toml::toml! {
+        farms = [{ directory = "/farms/0", size = "2GB" }]
+        advanced = { farming_thread_pool_size = 4 }
+    });
+    let config: FarmerConfig = table.try_into().unwrap();
+    assert_eq!(config.farming_thread_pool_size().unwrap(), NonZeroUsize::new(4));
+}
+
+#[test]
+fn plotting_schedule() {
+    let table = farmer_config_table(toml::toml! {
+        farms = [{ directory = "/farms/0", size = "2GB" }]
+
+        [[plotting_schedule]]
+        start = "22:00"
+        end = "07:00"
+
+        [[plotting_schedule]]
+        start = "12:00"
+        end = "13:00"
+        sector_encoding_concurrency = 1
+    });
+    let config: FarmerConfig = table.try_into().unwrap();
+    let schedule = config.plotting_schedule;
+    assert_eq!(schedule.len(), 2);
+    let time = |time: &str| NaiveTime::parse_from_str(time, "%H:%M").unwrap();
+
+    // the first window spans midnight
+    assert!(schedule[0].contains(time("23:30")));
+    assert!(schedule[0].contains(time("00:00")));
+    assert!(!schedule[0].contains(time("07:00")));
+    assert_eq!(
+        plotting_state(&schedule, false, time("03:00")),
+        PlottingState::Running {
+            sector_encoding_concurrency: None,
+            sector_downloading_concurrency: None
+        }
+    );
+    assert_eq!(
+        plotting_state(&schedule, false, time("12:30")),
+        PlottingState::Running {
+            sector_encoding_concurrency: NonZeroUsize::new(1),
+            sector_downloading_concurrency: None
+        }
+    );
+    assert_eq!(plotting_state(&schedule, false, time("09:00")), PlottingState::OutsideSchedule);
+    assert_eq!(plotting_state(&schedule, true, time("03:00")), PlottingState::Paused);
+    assert_eq!(plotting_state(&[], true, time("03:00")), PlottingState::Paused);
+    assert!(matches!(plotting_state(&[], false, time("09:00")), PlottingState::Running { .. }));
+
+    // times are written back as `HH:MM`
+    let serialized = toml::Value::try_from(&schedule[1]).unwrap();
+    assert_eq!(serialized["start"].as_str(), Some("12:00"));
+
+    let table = farmer_config_table(toml::toml! {
+        farms = [{ directory = "/farms/0", size = "2GB" }]
+        plotting_schedule = [{ start = "25:00", end = "07:00" }]
+    });
+    assert!(table.try_into::<FarmerConfig>().is_err());
+}
+
+#[test]
+fn replotting_progress() {
+    let mut progress = ReplottingProgress::default();
+    let replotted = SectorEventKind::Finished { replotted: true, time: Duration::from_secs(1) };
+
+    assert!(progress.update(3, SectorEventKind::AboutToExpire));
+    assert!(progress.update(5, SectorEventKind::Expired));
+    // sector expires after it was about to expire
+    assert!(!progress.update(3, SectorEventKind::Expired));
+    assert!(!progress.update(7, SectorEventKind::PlottingStarted));
+    assert_eq!((progress.replotted_sectors, progress.total_sectors()), (0, 2));
+
+    assert!(progress.update(3, replotted));
+    assert!(!progress.is_finished());
+    // sectors which weren't waiting for replotting are not counted
+    assert!(!progress.update(7, replotted));
+    assert!(progress.update(5, replotted));
+    assert_eq!((progress.replotted_sectors, progress.total_sectors()), (2, 2));
+    assert!(progress.is_finished());
+
+    // lagging updates are replaced by the current expirations
+    assert!(progress.update(9, SectorEventKind::Expired));
+    let expirations = [(4, SectorEventKind::AboutToExpire), (6, SectorEventKind::Expired)];
+    assert!(progress.resync(expirations));
+    assert!(!progress.resync(expirations));
+    assert_eq!((progress.replotted_sectors, progress.total_sectors()), (2, 4));
+    assert!(progress.update(6, replotted));
+    assert!(!progress.update(9, replotted));
+    assert_eq!((progress.replotted_sectors, progress.total_sectors()), (3, 4));
+}
+
+#[test]
+fn known_chain_by_genesis_hash() {
+    let gemini_3h = "0c121c75f4ef450f40619e1fca9d1e8e7fbabc42c895bc4790801e85d5a91c34";
+    assert!(matches!(ChainConfig::from_genesis_hash(gemini_3h), Some(ChainConfig::Gemini3h)));
+    assert!(ChainConfig::from_genesis_hash(&"00".repeat(32)).is_none());
+}
+
+#[test]
+fn status_metrics() {
+    let status = Status {
+        node: Some(NodeStatus {
+            best_block: 42,
+            is_synced: true,
+            connected_peers: 3,
+            ..Default::default()
+        }),
+        farms: vec![FarmStatus {
+            directory: "/farms/\"quoted\"".into(),
+            allocated_space: 1024,
+            plotted_sectors: 1,
+            total_sectors: 2,
+            ..Default::default()
+        }],
+        authored_count: 7,
+    };
+
+    let metrics = status.to_metrics();
+    let samples = metrics.lines().filter(|line| !line.starts_with('#')).collect::<Vec<_>>();
+    assert_eq!(samples.len(), 10);
+    assert!(samples.contains(&"pulsar_node_best_block 42"));
+    assert!(samples.contains(&"pulsar_node_is_synced 1"));
+    assert!(samples.contains(&"pulsar_authored_blocks_total 7"));
+    assert!(
+        samples.contains(&r#"pulsar_farm_total_sectors{farm="0",directory="/farms/\"quoted\""} 2"#)
+    );
+    assert!(metrics.contains("# TYPE pulsar_farm_solutions_total counter"));
+}
+
+#[test]
+fn rewards_display() {
+    assert_eq!(Rewards(0).to_string(), "0");
+    assert_eq!(Rewards(3 * 10_u128.pow(18)).to_string(), "3");
+    assert_eq!(Rewards(1_500_000_000_000_000_000).to_string(), "1.5");
+    assert_eq!(Rewards(1).to_string(), "0.000000000000000001");
+}
+
 #[test]
 fn yes_no_checker() {
     assert!(yes_or_no_parser("yas").is_err());
@@ -101,6 +916,14 @@ fn yes_no_checker() {
     assert!(yes_or_no_parser("y").is_ok());
 }
 
+#[test]
+fn mismatched_farm_action_checker() {
+    assert_eq!(mismatched_farm_action_parser("w").ok(), Some(MismatchedFarmAction::Wipe));
+    assert_eq!(mismatched_farm_action_parser("Skip").ok(), Some(MismatchedFarmAction::Skip));
+    assert_eq!(mismatched_farm_action_parser("a").ok(), Some(MismatchedFarmAction::Abort));
+    assert!(mismatched_farm_action_parser("y").is_err());
+}
+
 #[test]
 fn directory_checker() {
     assert!(directory_parser("./").is_ok());
@@ -132,6 +955,13 @@ fn size_checker() {
 fn chain_checker() {
     assert!(ChainConfig::from_str("gemini3h").is_ok());
     assert!(ChainConfig::from_str("devv").is_err());
+
+    let chain = ChainConfig::from_str("/chains/private.json").unwrap();
+    assert!(
+        matches!(&chain, ChainConfig::Custom { chain_spec_path } if chain_spec_path.ends_with("private.json"))
+    );
+    // custom chain specs are loaded from the disk
+    assert!(chain.chain_spec().is_err());
 }
 
 #[test]
@@ -14,7 +14,7 @@ use tracing_bunyan_formatter::{BunyanFormattingLayer, JsonStorageLayer};
 use tracing_error::ErrorLayer;
 use tracing_subscriber::fmt::format::FmtSpan;
 use tracing_subscriber::prelude::*;
-use tracing_subscriber::{fmt, EnvFilter, Layer};
+use tracing_subscriber::{fmt, reload, EnvFilter, Layer};
 
 use crate::config::MIN_FARM_SIZE;
 use crate::summary::Rewards;
@@ -119,6 +119,29 @@ pub(crate) fn directory_parser(location: &str) -> Result<PathBuf> {
     }
 }
 
+/// the provided path should be an existing directory, it is only created if
+/// `create_dirs` is set. Never prompts the user.
+pub(crate) fn non_interactive_directory_parser(
+    location: &Path,
+    create_dirs: bool,
+) -> Result<PathBuf> {
+    if location.is_dir() {
+        return Ok(location.to_owned());
+    }
+    if location.exists() {
+        return Err(eyre!("`{}` exists, but it is not a directory!", location.display()));
+    }
+    if !create_dirs {
+        return Err(eyre!(
+            "directory `{}` does not exist! Create it or pass `--create-dirs`.",
+            location.display()
+        ));
+    }
+    create_dir_all(location)
+        .with_context(|| format!("couldn't create the directory `{}`", location.display()))?;
+    Ok(location.to_owned())
+}
+
 /// utilize `ByteSize` crate for the validation
 pub(crate) fn size_parser(size: &str) -> Result<ByteSize> {
     let Ok(size) = size.parse::<ByteSize>() else {
@@ -131,6 +154,24 @@ pub(crate) fn size_parser(size: &str) -> Result<ByteSize> {
     }
 }
 
+/// parses a date (i.e. `2024-01-31`) or a date with time in RFC 3339 format
+/// (i.e. `2024-01-31T12:00:00Z`) into a unix timestamp in seconds, dates
+/// without time are taken as the start of the day in UTC
+pub(crate) fn date_parser(date: &str) -> Result<u64> {
+    let timestamp = match chrono::DateTime::parse_from_rfc3339(date) {
+        Ok(date_time) => date_time.timestamp(),
+        Err(_) => chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map_err(|_| {
+                eyre!("could not parse the date `{date}`, please use `YYYY-MM-DD` or RFC 3339")
+            })?
+            .and_hms_opt(0, 0, 0)
+            .expect("midnight is a valid time; qed")
+            .and_utc()
+            .timestamp(),
+    };
+    u64::try_from(timestamp).map_err(|_| eyre!("date `{date}` is before the unix epoch"))
+}
+
 pub(crate) fn yes_or_no_parser(answer: &str) -> Result<bool> {
     match answer.to_lowercase().as_str() {
         "y" | "yes" => Ok(true),
@@ -149,6 +190,11 @@ pub(crate) fn node_directory_getter() -> PathBuf {
     data_dir_getter().join("node")
 }
 
+/// marker of `farm pause`, the plotting is paused while it exists
+pub(crate) fn plotting_paused_marker_getter() -> PathBuf {
+    data_dir_getter().join("plotting.paused")
+}
+
 fn data_dir_getter() -> PathBuf {
     dirs::data_dir().expect("data folder must be present in every major OS").join("pulsar")
 }
@@ -208,8 +254,26 @@ pub(crate) fn raise_fd_limit() {
     }
 }
 
-/// install a logger for the application
-pub(crate) fn install_tracing(is_verbose: bool, no_rotation: bool) {
+/// replaces the log filter of the installed logger, `None` restores the
+/// default filter (from `RUST_LOG`, or `info`)
+pub(crate) type LogFilterReloader = Box<dyn Fn(Option<&str>) -> Result<()> + Send + Sync>;
+
+/// log filter from the given directives (in the `RUST_LOG` format), or from
+/// the `RUST_LOG` environment variable if there are none
+pub(crate) fn log_filter(directives: Option<&str>) -> Result<EnvFilter> {
+    let builder = EnvFilter::builder().with_default_directive(LevelFilter::INFO.into());
+    let filter = match directives {
+        Some(directives) => builder
+            .parse(directives)
+            .with_context(|| format!("couldn't parse the log filter `{directives}`"))?,
+        None => builder.from_env_lossy(),
+    };
+    Ok(filter.add_directive("regalloc2=off".parse().expect("hardcoded value is true")))
+}
+
+/// install a logger for the application, and returns the reloader of its
+/// log filter
+pub(crate) fn install_tracing(is_verbose: bool, no_rotation: bool) -> LogFilterReloader {
     let log_dir = custom_log_dir();
     let _ = create_dir_all(&log_dir);
 
@@ -223,12 +287,9 @@ pub(crate) fn install_tracing(is_verbose: bool, no_rotation: bool) {
     .expect("building should always succeed");
 
     // filter for logging
-    let filter = || {
-        EnvFilter::builder()
-            .with_default_directive(LevelFilter::INFO.into())
-            .from_env_lossy()
-            .add_directive("regalloc2=off".parse().expect("hardcoded value is true"))
-    };
+    let filter = || log_filter(None).expect("default filter is always valid");
+    let (file_filter, file_filter_handle) = reload::Layer::new(filter());
+    let (stdout_filter, stdout_filter_handle) = reload::Layer::new(filter());
 
     // start logger, after we acquire the bundle identifier
     #[cfg(tokio_unstable)]
@@ -241,7 +302,7 @@ pub(crate) fn install_tracing(is_verbose: bool, no_rotation: bool) {
         .with(
             BunyanFormattingLayer::new("pulsar".to_owned(), file_appender)
                 .and_then(JsonStorageLayer)
-                .with_filter(filter()),
+                .with_filter(file_filter),
         )
         .with(ErrorLayer::default());
 
@@ -252,12 +313,24 @@ pub(crate) fn install_tracing(is_verbose: bool, no_rotation: bool) {
                 fmt::layer()
                     .with_ansi(!cfg!(windows))
                     .with_span_events(FmtSpan::CLOSE)
-                    .with_filter(filter()),
+                    .with_filter(stdout_filter),
             )
             .init();
     } else {
         tracing_layer.init();
     }
+
+    Box::new(move |directives| {
+        file_filter_handle
+            .reload(log_filter(directives)?)
+            .context("couldn't reload the log filter")?;
+        if is_verbose {
+            stdout_filter_handle
+                .reload(log_filter(directives)?)
+                .context("couldn't reload the log filter")?;
+        }
+        Ok(())
+    })
 }
 
 pub fn is_default<T: Default + PartialEq>(t: &T) -> bool {
@@ -293,8 +366,30 @@ pub fn apply_extra_options<T: serde::Serialize + serde::de::DeserializeOwned>(
 
     apply_extra_options_inner(&mut table, extra);
 
-    Ok(toml::from_str(&toml::to_string(&table).context("Failed to deserialize extra options")?)
-        .expect("At this stage we know that config is always toml deserializable"))
+    toml::from_str(&toml::to_string(&table).context("Failed to serialize extra options")?)
+        .context("Failed to deserialize extra options")
+}
+
+/// applies every key of `extra` to `config` separately, so that the error
+/// points at the invalid key. Returns the keys that have no effect on `config`
+/// (either unknown, or set to their default values)
+pub(crate) fn validate_extra_options<T: serde::Serialize + serde::de::DeserializeOwned>(
+    config: &T,
+    extra: &toml::Table,
+) -> Result<Vec<String>> {
+    let original = toml::Table::try_from(config).context("Failed to serialize config")?;
+    let mut ineffective_keys = vec![];
+
+    for (key, value) in extra {
+        let single_option = toml::Table::from_iter([(key.clone(), value.clone())]);
+        let applied = apply_extra_options(config, single_option)
+            .with_context(|| format!("invalid value for `{key}`"))?;
+        if toml::Table::try_from(&applied).context("Failed to serialize config")? == original {
+            ineffective_keys.push(key.clone());
+        }
+    }
+
+    Ok(ineffective_keys)
 }
 
 #[cfg(tokio_unstable)]
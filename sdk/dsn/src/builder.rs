@@ -1,5 +1,5 @@
 use std::collections::HashSet;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Weak};
 
 use anyhow::Context;
@@ -22,7 +22,7 @@ use subspace_networking::{
 };
 
 use super::local_provider_record_utils::MaybeLocalRecordProvider;
-use super::LocalRecordProvider;
+use super::{FarmerMetrics, LocalRecordProvider};
 
 /// Wrapper with default value for listen address
 #[derive(
@@ -162,7 +162,23 @@ pub struct DsnOptions<C, PieceByIndex, SegmentHeaderByIndexes> {
     pub get_segment_header_by_segment_indexes: SegmentHeaderByIndexes,
     /// Segment header store
     pub segment_header_store: SegmentHeadersStore<C>,
-    /// Is libp2p metrics enabled
+    /// Is libp2p and farmer metrics enabled
+    pub is_metrics_enabled: bool,
+}
+
+/// Options for DSN of a farmer, which is not embedded in a node
+pub struct StandaloneDsnOptions<PieceByIndex> {
+    /// Genesis hash of the chain, used as the protocol version
+    pub genesis_hash: [u8; 32],
+    /// Path for dsn
+    pub base_path: PathBuf,
+    /// Keypair for networking
+    pub keypair: subspace_networking::libp2p::identity::Keypair,
+    /// Bootstrap addresses reported by the node, in addition to the boot nodes
+    pub bootstrap_addresses: Vec<subspace_networking::libp2p::Multiaddr>,
+    /// Get piece by hash handler
+    pub get_piece_by_index: PieceByIndex,
+    /// Is libp2p and farmer metrics enabled
     pub is_metrics_enabled: bool,
 }
 
@@ -176,9 +192,21 @@ pub struct DsnShared {
     pub farmer_readers_and_pieces: Arc<parking_lot::Mutex<Option<ReadersAndPieces>>>,
     /// Farmer piece cache
     pub farmer_piece_cache: Arc<parking_lot::RwLock<Option<FarmerPieceCache>>>,
+    /// Farmer metrics, `None` if metrics are disabled
+    pub farmer_metrics: Option<FarmerMetrics>,
     _destructors: DestructorSet,
 }
 
+/// Networking config along with the state shared with the farmer, before the
+/// networking is constructed
+struct PreparedDsn {
+    config: subspace_networking::Config<LocalRecordProvider>,
+    farmer_readers_and_pieces: Arc<parking_lot::Mutex<Option<ReadersAndPieces>>>,
+    farmer_piece_cache: Arc<parking_lot::RwLock<Option<FarmerPieceCache>>>,
+    farmer_metrics: Option<FarmerMetrics>,
+    metrics_registry: Option<Registry>,
+}
+
 impl Dsn {
     /// Build dsn
     pub fn build_dsn<B, C, PieceByIndex, F1, SegmentHeaderByIndexes>(
@@ -215,12 +243,97 @@ impl Dsn {
             segment_header_store,
             is_metrics_enabled,
         } = options;
-        let farmer_readers_and_pieces = Arc::new(parking_lot::Mutex::new(None));
         let protocol_version = hex::encode(client.info().genesis_hash);
+
+        let mut prepared = self.prepare(
+            protocol_version,
+            &base_path,
+            keypair,
+            vec![],
+            get_piece_by_index,
+            is_metrics_enabled,
+        )?;
+        prepared.config.request_response_protocols.push(
+            SegmentHeaderBySegmentIndexesRequestHandler::create(move |_, req| {
+                futures::future::ready(get_segment_header_by_segment_indexes(
+                    req,
+                    &segment_header_store,
+                ))
+            }),
+        );
+
+        prepared.construct()
+    }
+
+    /// Build dsn for a farmer connected to a node over RPC
+    ///
+    /// Only pieces are served to the network, segment headers are served by
+    /// the nodes
+    pub fn build_standalone_dsn<PieceByIndex, F1>(
+        self,
+        options: StandaloneDsnOptions<PieceByIndex>,
+    ) -> anyhow::Result<(
+        DsnShared,
+        subspace_networking::NodeRunner<LocalRecordProvider>,
+        Option<Registry>,
+    )>
+    where
+        PieceByIndex: Fn(
+                &PieceByIndexRequest,
+                Weak<parking_lot::Mutex<Option<ReadersAndPieces>>>,
+                Arc<parking_lot::RwLock<Option<FarmerPieceCache>>>,
+            ) -> F1
+            + Send
+            + Sync
+            + 'static,
+        F1: Future<Output = Option<PieceByIndexResponse>> + Send + 'static,
+    {
+        let StandaloneDsnOptions {
+            genesis_hash,
+            base_path,
+            keypair,
+            bootstrap_addresses,
+            get_piece_by_index,
+            is_metrics_enabled,
+        } = options;
+
+        self.prepare(
+            hex::encode(genesis_hash),
+            &base_path,
+            keypair,
+            bootstrap_addresses,
+            get_piece_by_index,
+            is_metrics_enabled,
+        )?
+        .construct()
+    }
+
+    fn prepare<PieceByIndex, F1>(
+        self,
+        protocol_version: String,
+        base_path: &Path,
+        keypair: subspace_networking::libp2p::identity::Keypair,
+        extra_bootstrap_addresses: Vec<subspace_networking::libp2p::Multiaddr>,
+        get_piece_by_index: PieceByIndex,
+        is_metrics_enabled: bool,
+    ) -> anyhow::Result<PreparedDsn>
+    where
+        PieceByIndex: Fn(
+                &PieceByIndexRequest,
+                Weak<parking_lot::Mutex<Option<ReadersAndPieces>>>,
+                Arc<parking_lot::RwLock<Option<FarmerPieceCache>>>,
+            ) -> F1
+            + Send
+            + Sync
+            + 'static,
+        F1: Future<Output = Option<PieceByIndexResponse>> + Send + 'static,
+    {
+        let farmer_readers_and_pieces = Arc::new(parking_lot::Mutex::new(None));
         let farmer_piece_cache = Arc::new(parking_lot::RwLock::new(None));
         let local_records_provider = MaybeLocalRecordProvider::new(farmer_piece_cache.clone());
 
         let mut metrics_registry = Registry::default();
+        let farmer_metrics = is_metrics_enabled.then(|| FarmerMetrics::new(&mut metrics_registry));
 
         tracing::debug!(genesis_hash = protocol_version, "Setting DSN protocol version...");
 
@@ -236,7 +349,11 @@ impl Dsn {
             external_addresses,
         } = self;
 
-        let bootstrap_nodes = boot_nodes.into_iter().map(Into::into).collect::<Vec<_>>();
+        let mut bootstrap_nodes = boot_nodes
+            .into_iter()
+            .map(Into::into)
+            .collect::<Vec<subspace_networking::libp2p::Multiaddr>>();
+        bootstrap_nodes.extend(extra_bootstrap_addresses);
 
         let listen_on = listen_addresses.0.into_iter().map(Into::into).collect();
 
@@ -263,27 +380,16 @@ impl Dsn {
             listen_on,
             allow_non_global_addresses_in_dht,
             networking_parameters_registry,
-            request_response_protocols: vec![
-                PieceByIndexRequestHandler::create({
-                    let weak_readers_and_pieces = Arc::downgrade(&farmer_readers_and_pieces);
+            request_response_protocols: vec![PieceByIndexRequestHandler::create({
+                let weak_readers_and_pieces = Arc::downgrade(&farmer_readers_and_pieces);
+                let farmer_piece_cache = farmer_piece_cache.clone();
+                move |_, req| {
+                    let weak_readers_and_pieces = weak_readers_and_pieces.clone();
                     let farmer_piece_cache = farmer_piece_cache.clone();
-                    move |_, req| {
-                        let weak_readers_and_pieces = weak_readers_and_pieces.clone();
-                        let farmer_piece_cache = farmer_piece_cache.clone();
-
-                        get_piece_by_index(req, weak_readers_and_pieces, farmer_piece_cache)
-                    }
-                }),
-                SegmentHeaderBySegmentIndexesRequestHandler::create({
-                    let segment_header_store = segment_header_store.clone();
-                    move |_, req| {
-                        futures::future::ready(get_segment_header_by_segment_indexes(
-                            req,
-                            &segment_header_store,
-                        ))
-                    }
-                }),
-            ],
+
+                    get_piece_by_index(req, weak_readers_and_pieces, farmer_piece_cache)
+                }
+            })],
             reserved_peers: reserved_nodes.into_iter().map(Into::into).collect(),
             max_established_incoming_connections,
             max_established_outgoing_connections,
@@ -295,6 +401,32 @@ impl Dsn {
             ..default_networking_config
         };
 
+        Ok(PreparedDsn {
+            config,
+            farmer_readers_and_pieces,
+            farmer_piece_cache,
+            farmer_metrics,
+            metrics_registry: is_metrics_enabled.then_some(metrics_registry),
+        })
+    }
+}
+
+impl PreparedDsn {
+    fn construct(
+        self,
+    ) -> anyhow::Result<(
+        DsnShared,
+        subspace_networking::NodeRunner<LocalRecordProvider>,
+        Option<Registry>,
+    )> {
+        let Self {
+            config,
+            farmer_readers_and_pieces,
+            farmer_piece_cache,
+            farmer_metrics,
+            metrics_registry,
+        } = self;
+
         let (node, runner) = subspace_networking::construct(config)?;
 
         let mut destructors = DestructorSet::new_without_async("dsn-destructors");
@@ -318,9 +450,10 @@ impl Dsn {
                 farmer_readers_and_pieces,
                 _destructors: destructors,
                 farmer_piece_cache,
+                farmer_metrics,
             },
             runner,
-            is_metrics_enabled.then_some(metrics_registry),
+            metrics_registry,
         ))
     }
 }
@@ -12,8 +12,10 @@
 
 mod builder;
 mod local_provider_record_utils;
+mod metrics;
 
 pub use builder::*;
+pub use metrics::{FarmLabels, FarmerMetrics};
 use subspace_farmer::piece_cache::PieceCache as FarmerPieceCache;
 use tracing::warn;
 
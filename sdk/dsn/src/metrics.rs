@@ -0,0 +1,64 @@
+use std::path::Path;
+
+use prometheus_client::metrics::counter::Counter;
+use prometheus_client::metrics::family::Family;
+use prometheus_client::metrics::gauge::Gauge;
+use prometheus_client::registry::Registry;
+
+/// Labels identifying a farm in the farmer metrics
+pub type FarmLabels = Vec<(String, String)>;
+
+/// Farmer metrics, registered in the DSN metrics registry, so that they are
+/// exposed on the same endpoint as the node and DSN metrics
+#[derive(Debug, Clone)]
+pub struct FarmerMetrics {
+    /// Space allocated for each farm in bytes
+    pub allocated_space: Family<FarmLabels, Gauge>,
+    /// Number of sectors plotted for each farm
+    pub sectors_plotted: Family<FarmLabels, Counter>,
+    /// Number of sectors replotted for each farm
+    pub sectors_replotted: Family<FarmLabels, Counter>,
+    /// Number of solutions found by each farm
+    pub solutions: Family<FarmLabels, Counter>,
+}
+
+impl FarmerMetrics {
+    /// Create farmer metrics and register them in the registry
+    pub fn new(registry: &mut Registry) -> Self {
+        let registry = registry.sub_registry_with_prefix("sdk_farmer");
+        let metrics = Self {
+            allocated_space: Default::default(),
+            sectors_plotted: Default::default(),
+            sectors_replotted: Default::default(),
+            solutions: Default::default(),
+        };
+
+        registry.register(
+            "allocated_space_bytes",
+            "Space allocated for the farm",
+            metrics.allocated_space.clone(),
+        );
+        registry.register(
+            "sectors_plotted",
+            "Number of sectors plotted by the farm",
+            metrics.sectors_plotted.clone(),
+        );
+        registry.register(
+            "sectors_replotted",
+            "Number of sectors replotted by the farm",
+            metrics.sectors_replotted.clone(),
+        );
+        registry.register(
+            "solutions",
+            "Number of solutions found by the farm",
+            metrics.solutions.clone(),
+        );
+
+        metrics
+    }
+
+    /// Labels of the farm at the given directory
+    pub fn farm_labels(directory: &Path) -> FarmLabels {
+        vec![("farm".to_owned(), directory.display().to_string())]
+    }
+}
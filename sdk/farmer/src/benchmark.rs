@@ -0,0 +1,253 @@
+//! Farming performance of the farm, see [`crate::Farm::benchmark`]
+
+use std::fs::File;
+use std::hint::black_box;
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Context};
+use derive_more::Display;
+use sdk_utils::ByteSize;
+use subspace_core_primitives::crypto::kzg::{embedded_kzg_settings, Kzg};
+use subspace_core_primitives::{Blake3Hash, PosSeed, PublicKey, Record, SolutionRange};
+use subspace_erasure_coding::ErasureCoding;
+use subspace_farmer::single_disk_farm::SingleDiskFarmInfo;
+use subspace_farmer::utils::all_cpu_cores;
+use subspace_farmer_components::auditing::audit_plot_sync;
+use subspace_farmer_components::sector::{sector_size, SectorMetadataChecksummed};
+use subspace_proof_of_space::{Table, TableGenerator};
+use tracing::info;
+
+use crate::layout::{self, PLOT_FILE};
+
+/// Time between two slots. Every slot all the sectors of the farm are audited,
+/// and the solution has to be proven before the farm can audit the next slot.
+pub const SLOT_DURATION: Duration = Duration::from_secs(1);
+
+/// Audits of the farm which are done to find the sectors to prove, before the
+/// proving benchmark gives up
+const PROVING_ROUNDS_PER_SAMPLE: u64 = 10;
+
+/// What [`crate::Farm::benchmark`] measures
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq)]
+pub enum BenchmarkKind {
+    /// Audit of all the plotted sectors with a challenge, which the farm does
+    /// every slot. Every sector is audited as a solution candidate, so the
+    /// results are an upper bound of the audit on the chain, where only a few
+    /// sectors are candidates.
+    #[display(fmt = "audit")]
+    Audit,
+    /// Proving of a solution found in a plotted sector, which the farm does
+    /// whenever the audit wins
+    #[display(fmt = "prove")]
+    Prove,
+}
+
+/// Result of [`crate::Farm::benchmark`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub struct BenchmarkReport {
+    /// What was measured
+    pub kind: BenchmarkKind,
+    /// Number of plotted sectors of the farm
+    pub plotted_sectors: u64,
+    /// Size of a sector
+    pub sector_size: ByteSize,
+    /// Number of audits of the farm, or of proven sectors
+    pub samples: u64,
+    /// Number of threads the audit and the proving were run with
+    pub farming_thread_pool_size: usize,
+    /// Number of sectors which are audited or proven per second
+    pub sectors_per_second: f64,
+    /// Mean time it takes to audit or to prove a single sector
+    pub sector_latency: Duration,
+    /// Mean time the farm spends in a slot: auditing all of its sectors, or
+    /// proving a solution
+    pub slot_time: Duration,
+}
+
+impl BenchmarkReport {
+    /// Report of the farm with `plotted_sectors` sectors of `sector_size`,
+    /// from the time of each sample
+    ///
+    /// # Panics
+    ///
+    /// If there are no samples or no plotted sectors
+    pub fn new(
+        kind: BenchmarkKind,
+        plotted_sectors: u64,
+        sector_size: ByteSize,
+        farming_thread_pool_size: usize,
+        times: &[Duration],
+    ) -> Self {
+        assert!(!times.is_empty() && plotted_sectors > 0, "Nothing was benchmarked");
+
+        let samples = times.len() as u64;
+        let slot_time = times.iter().sum::<Duration>() / samples as u32;
+        let sector_latency = match kind {
+            BenchmarkKind::Audit => slot_time / plotted_sectors as u32,
+            // Single sector is proven per slot
+            BenchmarkKind::Prove => slot_time,
+        };
+
+        Self {
+            kind,
+            plotted_sectors,
+            sector_size,
+            samples,
+            farming_thread_pool_size,
+            sectors_per_second: 1.0 / sector_latency.as_secs_f64(),
+            sector_latency,
+            slot_time,
+        }
+    }
+
+    /// Data which is audited per second, it is only meaningful for
+    /// [`BenchmarkKind::Audit`]
+    pub fn throughput(&self) -> ByteSize {
+        ByteSize::b((self.sectors_per_second * self.sector_size.as_u64() as f64) as u64)
+    }
+
+    /// Time which is left until the end of the slot, or `None` if the farm is
+    /// too slow and misses solutions
+    pub fn deadline_headroom(&self) -> Option<Duration> {
+        SLOT_DURATION.checked_sub(self.slot_time)
+    }
+}
+
+/// Plotted sectors of the farm, opened for the benchmark
+struct BenchmarkedFarm {
+    public_key: PublicKey,
+    sectors_metadata: Vec<SectorMetadataChecksummed>,
+    plot: File,
+}
+
+pub(crate) fn benchmark<T: Table>(
+    directory: &Path,
+    kind: BenchmarkKind,
+    sample_size: NonZeroUsize,
+    farming_thread_pool_size: Option<NonZeroUsize>,
+) -> anyhow::Result<BenchmarkReport> {
+    let info = SingleDiskFarmInfo::load_from(directory)
+        .with_context(|| format!("Failed to read farm info at `{directory:?}'"))?
+        .ok_or_else(|| anyhow!("Didn't found farm at `{directory:?}'"))?;
+    let sectors_metadata = layout::read_sectors_metadata(directory)?;
+    if sectors_metadata.is_empty() {
+        return Err(anyhow!("Farm at `{directory:?}' has no plotted sectors yet"));
+    }
+    let plot = File::open(directory.join(PLOT_FILE)).context("Failed to open plot")?;
+    let farm = BenchmarkedFarm { public_key: *info.public_key(), sectors_metadata, plot };
+
+    // Same default as the farmer
+    let farming_thread_pool_size =
+        farming_thread_pool_size.map(NonZeroUsize::get).unwrap_or_else(|| {
+            all_cpu_cores()
+                .first()
+                .expect("Not empty according to function description; qed")
+                .cpu_cores()
+                .len()
+        });
+    let thread_pool = rayon::ThreadPoolBuilder::new()
+        .thread_name(|index| format!("benchmark.{index}"))
+        .num_threads(farming_thread_pool_size)
+        .build()
+        .context("Failed to create farming thread pool")?;
+
+    info!(?directory, %kind, %sample_size, farming_thread_pool_size, "Benchmarking the farm");
+    let times = thread_pool.install(|| match kind {
+        BenchmarkKind::Audit => Ok(audit_times(&farm, sample_size)),
+        BenchmarkKind::Prove => prove_times::<T>(&farm, sample_size),
+    })?;
+
+    Ok(BenchmarkReport::new(
+        kind,
+        farm.sectors_metadata.len() as u64,
+        ByteSize::b(sector_size(info.pieces_in_sector()) as u64),
+        farming_thread_pool_size,
+        &times,
+    ))
+}
+
+/// Challenges aren't random, so that the benchmarks of the same farm are
+/// comparable
+fn synthetic_challenge(round: u64) -> Blake3Hash {
+    *blake3::hash(&round.to_le_bytes()).as_bytes()
+}
+
+/// Time of each audit of all the sectors of the farm
+fn audit_times(farm: &BenchmarkedFarm, sample_size: NonZeroUsize) -> Vec<Duration> {
+    (0..sample_size.get() as u64)
+        .map(|round| {
+            let start = Instant::now();
+            black_box(audit_plot_sync(
+                &farm.public_key,
+                &synthetic_challenge(round),
+                // Every sector is a solution candidate, while with the
+                // solution range of the chain only a few of them are, so the
+                // audit time is an upper bound
+                SolutionRange::MAX,
+                &farm.plot,
+                &farm.sectors_metadata,
+                None,
+            ));
+            start.elapsed()
+        })
+        .collect()
+}
+
+/// Time of proving a solution of each sector, sectors are picked by auditing
+/// the farm until there are enough of them. Sectors without a solution which
+/// can be proven are skipped.
+fn prove_times<T: Table>(
+    farm: &BenchmarkedFarm,
+    sample_size: NonZeroUsize,
+) -> anyhow::Result<Vec<Duration>> {
+    let kzg = Kzg::new(embedded_kzg_settings());
+    let erasure_coding = ErasureCoding::new(
+        NonZeroUsize::new(Record::NUM_S_BUCKETS.next_power_of_two().ilog2() as usize)
+            .expect("Number of buckets >= 1, therefore next power of 2 >= 2, therefore ilog2 >= 1"),
+    )
+    .map_err(|error| anyhow!("Failed to create erasure coding: {error}"))?;
+    let mut table_generator = T::generator();
+
+    let mut times = Vec::with_capacity(sample_size.get());
+    for round in 0..sample_size.get() as u64 * PROVING_ROUNDS_PER_SAMPLE {
+        let audit_results = audit_plot_sync(
+            &farm.public_key,
+            &synthetic_challenge(round),
+            SolutionRange::MAX,
+            &farm.plot,
+            &farm.sectors_metadata,
+            None,
+        );
+        if audit_results.is_empty() {
+            return Err(anyhow!("Audit found no solutions to prove"));
+        }
+
+        for audit_result in audit_results {
+            let start = Instant::now();
+            let sector_index = audit_result.sector_index;
+            let mut solutions = audit_result
+                .solution_candidates
+                .into_solutions(&farm.public_key, &kzg, &erasure_coding, |seed: &PosSeed| {
+                    table_generator.generate_parallel(seed)
+                })
+                .map_err(|error| anyhow!("Failed to prove sector {sector_index}: {error}"))?;
+            // Farm only needs the first solution which is proven
+            if solutions.find_map(Result::ok).is_none() {
+                continue;
+            }
+            times.push(start.elapsed());
+
+            if times.len() == sample_size.get() {
+                return Ok(times);
+            }
+        }
+    }
+
+    if times.is_empty() {
+        return Err(anyhow!("None of the solutions could be proven"));
+    }
+    Ok(times)
+}
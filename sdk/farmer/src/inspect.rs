@@ -0,0 +1,43 @@
+//! Offline inspection of the farm, see [`inspect`]
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context};
+use sdk_utils::ByteSize;
+use subspace_farmer::single_disk_farm::SingleDiskFarmInfo;
+
+use crate::layout::{self, PIECE_CACHE_ELEMENT_SIZE};
+use crate::FarmInfo;
+
+/// Farm as it is on the disk, returned by [`inspect`]
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct FarmInspection {
+    /// Path of the farm
+    pub directory: PathBuf,
+    /// Info of the farm, stored when the farm was created
+    pub info: FarmInfo,
+    /// Number of plotted sectors
+    pub plotted_sectors: u64,
+    /// Number of pieces which fit into the piece cache
+    pub piece_cache_capacity: u64,
+}
+
+/// Reads the farm at `directory` without opening it, so it can be inspected
+/// while it is farmed, or before a farmer is started with it.
+pub fn inspect(directory: impl AsRef<Path>) -> anyhow::Result<FarmInspection> {
+    let directory = directory.as_ref();
+    let info = SingleDiskFarmInfo::load_from(directory)
+        .with_context(|| format!("Failed to read farm info at `{directory:?}'"))?
+        .ok_or_else(|| anyhow!("Didn't found farm at `{directory:?}'"))?;
+    let plotted_sectors = layout::plotted_sector_count(directory)?;
+    let piece_cache_size =
+        layout::piece_cache_size(directory).context("Failed to read piece cache")?;
+
+    Ok(FarmInspection {
+        directory: directory.to_path_buf(),
+        info: FarmInfo::new(info, ByteSize::b(piece_cache_size)),
+        plotted_sectors: plotted_sectors.into(),
+        piece_cache_capacity: piece_cache_size / PIECE_CACHE_ELEMENT_SIZE as u64,
+    })
+}
@@ -0,0 +1,120 @@
+//! Layout of the farm files, for reading farms offline
+
+// TODO: The layout of the farm files is directly imported from the monorepo and
+// relies on internals of `SingleDiskFarm`. Remove it once the farm can be read
+// with public functions.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Seek, SeekFrom, Write};
+use std::path::Path;
+
+use anyhow::{anyhow, Context};
+use parity_scale_codec::{Decode, Encode, IoReader};
+use subspace_core_primitives::{Piece, PieceIndex, SectorIndex};
+use subspace_farmer_components::sector::{sector_size, SectorMetadataChecksummed};
+
+pub(crate) const METADATA_FILE: &str = "metadata.bin";
+pub(crate) const PLOT_FILE: &str = "plot.bin";
+pub(crate) const PIECE_CACHE_FILE: &str = "piece_cache.bin";
+pub(crate) const KNOWN_PEERS_FILE: &str = "known_addresses.bin";
+/// Space reserved for the header of the metadata file, sector metadata follows
+/// it
+pub(crate) const RESERVED_PLOT_METADATA: u64 = 1024 * 1024;
+/// Size of the checksums at the end of every sector and piece cache element
+pub(crate) const CHECKSUM_SIZE: usize = blake3::OUT_LEN;
+/// Piece cache elements are the index of the piece, the piece and the checksum
+/// of both
+pub(crate) const PIECE_CACHE_ELEMENT_SIZE: usize = PieceIndex::SIZE + Piece::SIZE + CHECKSUM_SIZE;
+
+/// Header of the metadata file
+#[derive(Debug, Encode, Decode)]
+pub(crate) struct PlotMetadataHeader {
+    pub(crate) version: u8,
+    pub(crate) plotted_sector_count: SectorIndex,
+}
+
+/// Number of plotted sectors of the farm
+pub(crate) fn plotted_sector_count(directory: &Path) -> anyhow::Result<SectorIndex> {
+    let mut metadata_file = match File::open(directory.join(METADATA_FILE)) {
+        Ok(file) => file,
+        // Farm which was never farmed has nothing plotted
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(0),
+        Err(error) => return Err(error).context("Failed to open sector metadata"),
+    };
+    let header = PlotMetadataHeader::decode(&mut IoReader(&mut metadata_file))
+        .map_err(|error| anyhow!("Failed to decode sector metadata header: {error}"))?;
+    Ok(header.plotted_sector_count)
+}
+
+/// Reads the metadata of all the plotted sectors of the farm
+pub(crate) fn read_sectors_metadata(
+    directory: &Path,
+) -> anyhow::Result<Vec<SectorMetadataChecksummed>> {
+    let mut metadata_file =
+        File::open(directory.join(METADATA_FILE)).context("Failed to open sector metadata")?;
+    let header = PlotMetadataHeader::decode(&mut IoReader(&mut metadata_file))
+        .map_err(|error| anyhow!("Failed to decode sector metadata header: {error}"))?;
+
+    metadata_file.seek(SeekFrom::Start(RESERVED_PLOT_METADATA))?;
+    (0..header.plotted_sector_count)
+        .map(|sector_index| {
+            SectorMetadataChecksummed::decode(&mut IoReader(&mut metadata_file)).map_err(|error| {
+                anyhow!("Failed to decode metadata of sector {sector_index}: {error}")
+            })
+        })
+        .collect()
+}
+
+/// Space used by the piece cache of the farm, farms might have no piece cache
+pub(crate) fn piece_cache_size(directory: &Path) -> io::Result<u64> {
+    match fs::metadata(directory.join(PIECE_CACHE_FILE)) {
+        Ok(metadata) => Ok(metadata.len()),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(0),
+        Err(error) => Err(error),
+    }
+}
+
+/// Drops the plotted sectors which are beyond `sector_count` from the metadata
+/// and the plot of the farm, returns the number of dropped sectors
+pub(crate) fn truncate_plot(
+    directory: &Path,
+    sector_count: SectorIndex,
+    pieces_in_sector: u16,
+) -> anyhow::Result<SectorIndex> {
+    let mut metadata_file =
+        match OpenOptions::new().read(true).write(true).open(directory.join(METADATA_FILE)) {
+            Ok(file) => file,
+            // Farm which was never farmed has nothing plotted
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(0),
+            Err(error) => return Err(error).context("Failed to open sector metadata"),
+        };
+    let mut header = PlotMetadataHeader::decode(&mut IoReader(&mut metadata_file))
+        .map_err(|error| anyhow!("Failed to decode sector metadata header: {error}"))?;
+    let dropped_sectors = header.plotted_sector_count.saturating_sub(sector_count);
+    if dropped_sectors > 0 {
+        header.plotted_sector_count = sector_count;
+        metadata_file.seek(SeekFrom::Start(0))?;
+        metadata_file
+            .write_all(&header.encode())
+            .context("Failed to update the number of plotted sectors")?;
+    }
+
+    let metadata_size = RESERVED_PLOT_METADATA
+        + u64::from(header.plotted_sector_count) * SectorMetadataChecksummed::encoded_size() as u64;
+    if metadata_file.metadata()?.len() > metadata_size {
+        metadata_file.set_len(metadata_size).context("Failed to truncate sector metadata")?;
+    }
+    metadata_file.sync_all()?;
+
+    let plot_file = OpenOptions::new()
+        .write(true)
+        .open(directory.join(PLOT_FILE))
+        .context("Failed to open plot")?;
+    let plot_size = u64::from(sector_count) * sector_size(pieces_in_sector) as u64;
+    if plot_file.metadata()?.len() > plot_size {
+        plot_file.set_len(plot_size).context("Failed to truncate plot")?;
+        plot_file.sync_all()?;
+    }
+
+    Ok(dropped_sectors)
+}
@@ -13,21 +13,25 @@
 use std::collections::HashMap;
 use std::io;
 use std::num::{NonZeroU8, NonZeroUsize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use anyhow::{anyhow, Context};
 pub use builder::{Builder, Config};
 use derivative::Derivative;
+use derive_more::Display;
 use futures::prelude::*;
 use futures::stream::FuturesUnordered;
+use sdk_dsn::FarmerMetrics;
 use sdk_traits::Node;
 use sdk_utils::{ByteSize, DestructorSet, PublicKey, TaskOutput};
 use serde::{Deserialize, Serialize};
 use subspace_core_primitives::crypto::kzg;
-use subspace_core_primitives::{PieceIndex, Record, SectorIndex};
+use subspace_core_primitives::{HistorySize, PieceIndex, Record, SectorIndex};
 use subspace_erasure_coding::ErasureCoding;
 use subspace_farmer::piece_cache::PieceCache as FarmerPieceCache;
+use subspace_farmer::single_disk_farm::piece_cache::DiskPieceCache;
+use subspace_farmer::single_disk_farm::piece_reader::PieceReader;
 use subspace_farmer::single_disk_farm::{
     SectorPlottingDetails, SectorUpdate, SingleDiskFarm, SingleDiskFarmError, SingleDiskFarmId,
     SingleDiskFarmInfo, SingleDiskFarmOptions, SingleDiskFarmSummary,
@@ -46,10 +50,28 @@ use subspace_networking::libp2p::kad::RecordKey;
 use subspace_networking::utils::multihash::ToMultihash;
 use subspace_networking::KnownPeersManager;
 use subspace_rpc_primitives::{FarmerAppInfo, SolutionResponse};
-use tokio::sync::{mpsc, oneshot, watch, Mutex, Semaphore};
+use tokio::sync::{broadcast, mpsc, oneshot, watch, Semaphore};
 use tracing::{debug, error, info, warn};
 use tracing_futures::Instrument;
 
+mod benchmark;
+mod inspect;
+mod layout;
+mod plotting;
+mod remote_node;
+pub mod rpc;
+mod scrub;
+mod sectors;
+#[cfg(test)]
+mod tests;
+
+pub use benchmark::{BenchmarkKind, BenchmarkReport, SLOT_DURATION};
+pub use inspect::{inspect, FarmInspection};
+pub use plotting::{PlottingController, PlottingStatus};
+pub use remote_node::RemoteNode;
+pub use scrub::{ScrubIssue, ScrubReport};
+pub use sectors::{SectorEvent, SectorEventKind, SectorExpiryReport, SectorUpdates};
+
 /// Description of the farm
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[non_exhaustive]
@@ -58,32 +80,230 @@ pub struct FarmDescription {
     pub directory: PathBuf,
     /// Space which you want to pledge
     pub space_pledged: ByteSize,
+    /// Size of the piece cache of the farm, the one of the farmer is used if
+    /// it isn't set
+    #[serde(default)]
+    pub piece_cache_size: Option<PieceCacheSize>,
 }
 
 impl FarmDescription {
     /// Construct Farm description
     pub fn new(directory: impl Into<PathBuf>, space_pledged: ByteSize) -> Self {
-        Self { directory: directory.into(), space_pledged }
+        Self { directory: directory.into(), space_pledged, piece_cache_size: None }
+    }
+
+    /// Sets the size of the piece cache of the farm
+    pub fn with_piece_cache_size(self, piece_cache_size: Option<PieceCacheSize>) -> Self {
+        Self { piece_cache_size, ..self }
     }
 
     /// Wipe all the data from the farm
     pub async fn wipe(self) -> io::Result<()> {
         tokio::fs::remove_dir_all(self.directory).await
     }
+
+    /// Plans resizing the farm to `space_pledged`, without changing the farm.
+    /// `piece_cache_size` is used unless the farm has its own.
+    pub fn plan_resize(
+        &self,
+        space_pledged: ByteSize,
+        piece_cache_size: PieceCacheSize,
+    ) -> anyhow::Result<FarmResize> {
+        let info = SingleDiskFarmInfo::load_from(&self.directory)
+            .with_context(|| format!("Failed to read farm info at `{:?}'", self.directory))?
+            .ok_or_else(|| anyhow!("Didn't found farm at `{:?}'", self.directory))?;
+        let current_space = ByteSize::b(info.allocated_space());
+        let pieces_in_sector = info.pieces_in_sector();
+        let piece_cache_size = self.piece_cache_size.unwrap_or(piece_cache_size);
+        let sector_count = |space: ByteSize| {
+            piece_cache_size
+                .percentage(space)
+                .map(|cache_percentage| {
+                    target_sector_count(space.as_u64(), cache_percentage, pieces_in_sector)
+                })
+                .ok_or_else(|| {
+                    anyhow!("Piece cache of {piece_cache_size} doesn't fit into farm of {space}")
+                })
+        };
+
+        let resize = FarmResize {
+            current_space,
+            new_space: space_pledged,
+            current_sectors: sector_count(current_space)?,
+            new_sectors: sector_count(space_pledged)?,
+        };
+        if resize.new_sectors == 0 {
+            return Err(anyhow!("Farm of {space_pledged} doesn't fit any sectors"));
+        }
+        Ok(resize)
+    }
+
+    /// Resizes the farm to `space_pledged`, it should not be farmed meanwhile.
+    ///
+    /// Growing adds sectors, which are plotted once the farm is farmed again,
+    /// as part of its initial plotting. Shrinking drops the plotted sectors
+    /// which don't fit anymore from the metadata and the plot of the farm.
+    pub fn resize(
+        self,
+        space_pledged: ByteSize,
+        piece_cache_size: PieceCacheSize,
+    ) -> anyhow::Result<(Self, FarmResize)> {
+        let resize = self.plan_resize(space_pledged, piece_cache_size)?;
+        let mut info = SingleDiskFarmInfo::load_from(&self.directory)?
+            .ok_or_else(|| anyhow!("Didn't found farm at `{:?}'", self.directory))?;
+        if resize.is_shrinking() {
+            let sector_count =
+                SectorIndex::try_from(resize.new_sectors).unwrap_or(SectorIndex::MAX);
+            let dropped_sectors =
+                layout::truncate_plot(&self.directory, sector_count, info.pieces_in_sector())
+                    .context("Failed to drop the sectors which don't fit")?;
+            info!(directory = ?self.directory, dropped_sectors, "Dropped plotted sectors");
+        }
+        let SingleDiskFarmInfo::V0 { allocated_space, .. } = &mut info;
+        *allocated_space = space_pledged.as_u64();
+        info.store_to(&self.directory).context("Failed to store farm info")?;
+
+        Ok((Self { space_pledged, ..self }, resize))
+    }
+}
+
+/// Size of the piece cache of a farm
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum PieceCacheSize {
+    /// Percentage of the space pledged to the farm
+    #[display(fmt = "{_0}%")]
+    Percentage(NonZeroU8),
+    /// Size, which is rounded up to a percentage of the space pledged to the
+    /// farm
+    Size(ByteSize),
+}
+
+impl PieceCacheSize {
+    /// Percentage of the space pledged to the farm used for the piece cache,
+    /// `None` if the piece cache doesn't leave any space for plotting
+    pub fn percentage(self, space_pledged: ByteSize) -> Option<NonZeroU8> {
+        let percentage = match self {
+            Self::Percentage(percentage) => percentage,
+            Self::Size(size) => {
+                let percentage = (u128::from(size.as_u64()) * 100)
+                    .div_ceil(u128::from(space_pledged.as_u64()).max(1));
+                NonZeroU8::new(u8::try_from(percentage).ok()?.max(1))?
+            }
+        };
+        (percentage.get() < 100).then_some(percentage)
+    }
+}
+
+impl Default for PieceCacheSize {
+    fn default() -> Self {
+        Self::Percentage(NonZeroU8::MIN)
+    }
+}
+
+impl From<NonZeroU8> for PieceCacheSize {
+    fn from(percentage: NonZeroU8) -> Self {
+        Self::Percentage(percentage)
+    }
+}
+
+impl From<ByteSize> for PieceCacheSize {
+    fn from(size: ByteSize) -> Self {
+        Self::Size(size)
+    }
+}
+
+impl std::str::FromStr for PieceCacheSize {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().strip_suffix('%') {
+            Some(percentage) => percentage
+                .trim()
+                .parse()
+                .map(Self::Percentage)
+                .map_err(|error| format!("Invalid piece cache percentage `{s}`: {error}")),
+            None => s
+                .parse()
+                .map(Self::Size)
+                .map_err(|error| format!("Invalid piece cache size `{s}`: {error}")),
+        }
+    }
+}
+
+impl TryFrom<String> for PieceCacheSize {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<PieceCacheSize> for String {
+    fn from(piece_cache_size: PieceCacheSize) -> Self {
+        piece_cache_size.to_string()
+    }
+}
+
+/// Resize of a farm, see [`FarmDescription::plan_resize`]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct FarmResize {
+    /// Space currently pledged to the farm
+    pub current_space: ByteSize,
+    /// Space pledged to the farm after the resize
+    pub new_space: ByteSize,
+    /// Number of sectors which currently fit into the farm
+    pub current_sectors: u64,
+    /// Number of sectors which fit into the farm after the resize
+    pub new_sectors: u64,
+}
+
+impl FarmResize {
+    /// Whether plotted sectors might be dropped by the resize
+    pub fn is_shrinking(&self) -> bool {
+        self.new_sectors < self.current_sectors
+    }
+
+    /// Maximum number of plotted sectors which are dropped by the resize
+    pub fn dropped_sectors(&self) -> u64 {
+        self.current_sectors.saturating_sub(self.new_sectors)
+    }
+}
+
+/// Number of sectors which fit into a farm
+fn target_sector_count(
+    allocated_space: u64,
+    cache_percentage: NonZeroU8,
+    pieces_in_sector: u16,
+) -> u64 {
+    // TODO: This calculation is directly imported from the monorepo and relies on
+    // internal calculation of farm. Remove it once we have public function.
+    let fixed_space_usage = 2 * 1024 * 1024
+        + Identity::file_size() as u64
+        + KnownPeersManager::file_size(KNOWN_PEERS_CACHE_SIZE) as u64;
+    // Calculate how many sectors can fit
+    let potentially_plottable_space = allocated_space.saturating_sub(fixed_space_usage) / 100
+        * (100 - u64::from(cache_percentage.get()));
+    // Do the rounding to make sure we have exactly as much space as fits whole
+    // number of sectors
+    potentially_plottable_space
+        / (sector_size(pieces_in_sector) + SectorMetadataChecksummed::encoded_size()) as u64
 }
 
 mod builder {
-    use std::num::{NonZeroU8, NonZeroUsize};
+    use std::net::SocketAddr;
+    use std::num::NonZeroUsize;
 
     use derivative::Derivative;
     use derive_builder::Builder;
     use derive_more::{Deref, DerefMut, Display, From};
     use sdk_traits::Node;
-    use sdk_utils::{ByteSize, PublicKey};
+    use sdk_utils::PublicKey;
     use serde::{Deserialize, Serialize};
 
     use super::BuildError;
-    use crate::{FarmDescription, Farmer};
+    use crate::{FarmDescription, Farmer, PieceCacheSize};
 
     #[derive(
         Debug,
@@ -105,25 +325,6 @@ mod builder {
         pub(crate)  NonZeroUsize,
     );
 
-    #[derive(
-        Debug,
-        Clone,
-        Derivative,
-        Deserialize,
-        Serialize,
-        PartialEq,
-        Eq,
-        From,
-        Deref,
-        DerefMut,
-        Display,
-    )]
-    #[derivative(Default)]
-    #[serde(transparent)]
-    pub struct PieceCacheSize(
-        #[derivative(Default(value = "ByteSize::mib(10)"))] pub(crate) ByteSize,
-    );
-
     #[derive(
         Debug,
         Clone,
@@ -193,6 +394,10 @@ mod builder {
         /// Threads will be pinned to corresponding CPU cores at creation.
         #[builder(default)]
         pub replotting_thread_pool_size: Option<NonZeroUsize>,
+        /// Address to serve the `farmer_*` RPC methods on (see [`crate::rpc`]),
+        /// not served if `None`
+        #[builder(default)]
+        pub rpc_listen_address: Option<SocketAddr>,
     }
 
     impl Builder {
@@ -207,13 +412,17 @@ mod builder {
             reward_address: PublicKey,
             node: &N,
             farms: &[FarmDescription],
-            cache_percentage: NonZeroU8,
+            piece_cache_size: impl Into<PieceCacheSize>,
         ) -> Result<Farmer<N::Table>, BuildError> {
-            self.configuration().build(reward_address, node, farms, cache_percentage).await
+            self.configuration().build(reward_address, node, farms, piece_cache_size).await
         }
     }
 }
 
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
 /// Error when farm creation fails
 #[derive(Debug, thiserror::Error)]
 pub enum SingleDiskFarmCreationError {
@@ -245,6 +454,44 @@ pub enum BuildError {
     /// Failed to build thread pool
     #[error("Failed to build thread pool: {0}")]
     ThreadPoolError(#[from] rayon::ThreadPoolBuildError),
+    /// Farm was created for another chain than the one of the node
+    #[error("Farm at `{directory:?}' was created for another chain, its genesis hash is 0x{} while the node's is 0x{}", hex(.farm_genesis_hash), hex(.node_genesis_hash))]
+    ChainMismatch {
+        /// Path of the farm
+        directory: PathBuf,
+        /// Genesis hash of the chain used for farm creation
+        farm_genesis_hash: [u8; 32],
+        /// Genesis hash of the chain of the node
+        node_genesis_hash: [u8; 32],
+    },
+    /// Piece cache doesn't leave any space for plotting in the farm
+    #[error(
+        "Piece cache of {piece_cache_size} doesn't fit into the farm at `{directory:?}' of \
+         {space_pledged}"
+    )]
+    PieceCacheTooLarge {
+        /// Path of the farm
+        directory: PathBuf,
+        /// Size of the piece cache of the farm
+        piece_cache_size: PieceCacheSize,
+        /// Space pledged to the farm
+        space_pledged: ByteSize,
+    },
+    /// Identity of the farm doesn't match the public key the farm was created
+    /// with, i.e. it was copied from another farm. Note that the identity is
+    /// the farm's own key, and is unrelated to the reward address.
+    #[error(
+        "Identity of the farm at `{directory:?}' is {identity_public_key}, while the farm was \
+         created with {farm_public_key}"
+    )]
+    IdentityMismatch {
+        /// Path of the farm
+        directory: PathBuf,
+        /// Public key of identity used for farm creation
+        farm_public_key: PublicKey,
+        /// Public key of the identity which is in the farm
+        identity_public_key: PublicKey,
+    },
     /// Other error
     #[error("{0}")]
     Other(#[from] anyhow::Error),
@@ -296,69 +543,84 @@ impl<T: subspace_proof_of_space::Table> sdk_traits::Farmer for Farmer<T> {
 const SEGMENT_COMMITMENTS_CACHE_SIZE: NonZeroUsize =
     NonZeroUsize::new(1_000_000).expect("Not zero; qed");
 
-async fn create_readers_and_pieces(
-    single_disk_farms: &[SingleDiskFarm],
-) -> Result<ReadersAndPieces, BuildError> {
-    // Store piece readers so we can reference them later
-    let readers = single_disk_farms.iter().map(SingleDiskFarm::piece_reader).collect();
-    let mut readers_and_pieces = ReadersAndPieces::new(readers);
+/// Number of sector updates a subscriber can lag behind before missing them
+const SECTOR_EVENTS_CAPACITY: usize = 1024;
+
+/// Plotted sectors of a farm, kept for indexing its pieces again when the
+/// farms of the farmer change
+#[derive(Debug, Default)]
+struct FarmPieces {
+    /// Any of the plotted sectors, the other ones are re-created from it, as
+    /// [`ReadersAndPieces`] only looks at their index and pieces
+    template: Option<PlottedSector>,
+    sectors: HashMap<SectorIndex, Vec<PieceIndex>>,
+}
 
-    tracing::debug!("Collecting already plotted pieces");
+impl FarmPieces {
+    fn insert(&mut self, plotted_sector: &PlottedSector) {
+        self.sectors.insert(plotted_sector.sector_index, plotted_sector.piece_indexes.clone());
+        if self.template.is_none() {
+            self.template = Some(plotted_sector.clone());
+        }
+    }
 
-    let mut plotted_sectors_iters = futures::future::join_all(
-        single_disk_farms.iter().map(|single_disk_farm| single_disk_farm.plotted_sectors()),
-    )
-    .await;
+    fn add_to(&self, disk_farm_index: u8, readers_and_pieces: &mut ReadersAndPieces) {
+        let Some(mut plotted_sector) = self.template.clone() else {
+            return;
+        };
+        for (&sector_index, piece_indexes) in &self.sectors {
+            plotted_sector.sector_index = sector_index;
+            plotted_sector.piece_indexes.clone_from(piece_indexes);
+            readers_and_pieces.add_sector(disk_farm_index, &plotted_sector);
+        }
+    }
+}
 
-    plotted_sectors_iters.drain(..).enumerate().try_for_each(
-        |(disk_farm_index, plotted_sectors_iter)| {
-            let disk_farm_index = disk_farm_index.try_into().map_err(|_error| {
-                anyhow!(
-                    "More than 256 farms are not supported, consider running multiple farmer \
-                     instances"
-                )
-            })?;
+/// Farm as seen by the DSN, which references it by its index in
+/// [`ReadersAndPieces`]
+struct FarmSlot {
+    piece_reader: PieceReader,
+    /// `None` once the farm is removed, the slot is then reused by the next
+    /// added farm
+    farm: Option<IndexedFarm>,
+}
 
-            (0 as SectorIndex..).zip(plotted_sectors_iter).for_each(
-                |(sector_index, plotted_sector_result)| match plotted_sector_result {
-                    Ok(plotted_sector) => {
-                        readers_and_pieces.add_sector(disk_farm_index, &plotted_sector);
-                    }
-                    Err(error) => {
-                        error!(
-                            %error,
-                            %disk_farm_index,
-                            %sector_index,
-                            "Failed reading plotted sector on startup, skipping"
-                        );
-                    }
-                },
-            );
+struct IndexedFarm {
+    directory: PathBuf,
+    piece_cache: DiskPieceCache,
+    pieces: Arc<parking_lot::Mutex<FarmPieces>>,
+}
 
-            Ok::<_, anyhow::Error>(())
-        },
-    )?;
+/// Indexes the pieces of all the farms
+fn index_farms(farm_slots: &[FarmSlot]) -> ReadersAndPieces {
+    let readers = farm_slots.iter().map(|slot| slot.piece_reader.clone()).collect();
+    let mut readers_and_pieces = ReadersAndPieces::new(readers);
 
-    tracing::debug!("Finished collecting already plotted pieces");
+    for (disk_farm_index, slot) in farm_slots.iter().enumerate() {
+        let disk_farm_index =
+            u8::try_from(disk_farm_index).expect("Farm slots are limited to 256; qed");
+        if let Some(farm) = &slot.farm {
+            farm.pieces.lock().add_to(disk_farm_index, &mut readers_and_pieces);
+        }
+    }
 
-    Ok(readers_and_pieces)
+    readers_and_pieces
 }
 
 fn handler_on_sector_plotted(
     plotted_sector: &PlottedSector,
     maybe_old_plotted_sector: &Option<PlottedSector>,
-    disk_farm_index: usize,
-    readers_and_pieces: Arc<parking_lot::Mutex<Option<ReadersAndPieces>>>,
+    disk_farm_index: u8,
+    readers_and_pieces: &parking_lot::Mutex<Option<ReadersAndPieces>>,
+    farm_pieces: &parking_lot::Mutex<FarmPieces>,
 ) {
-    let disk_farm_index = disk_farm_index
-        .try_into()
-        .expect("More than 256 farms are not supported, this is checked above already; qed");
-
-    {
-        let mut readers_and_pieces = readers_and_pieces.lock();
-        let readers_and_pieces =
-            readers_and_pieces.as_mut().expect("Initial value was populated before; qed");
+    // Farm pieces are updated under the lock too, so that indexing the farms
+    // doesn't miss or repeat the sector
+    let mut readers_and_pieces = readers_and_pieces.lock();
+    farm_pieces.lock().insert(plotted_sector);
 
+    // Farms are indexed once all of them are created
+    if let Some(readers_and_pieces) = readers_and_pieces.as_mut() {
         if let Some(old_plotted_sector) = maybe_old_plotted_sector {
             readers_and_pieces.delete_sector(disk_farm_index, old_plotted_sector);
         }
@@ -367,17 +629,19 @@ fn handler_on_sector_plotted(
 }
 
 impl Config {
-    /// Open and start farmer
+    /// Open and start farmer, `piece_cache_size` is used by the farms which
+    /// don't have their own
     pub async fn build<N: Node, T: subspace_proof_of_space::Table>(
         self,
         reward_address: PublicKey,
         node: &N,
         farms: &[FarmDescription],
-        cache_percentage: NonZeroU8,
+        piece_cache_size: impl Into<PieceCacheSize>,
     ) -> Result<Farmer<T>, BuildError> {
         if farms.is_empty() {
             return Err(BuildError::NoFarmsSupplied);
         }
+        let piece_cache_size = piece_cache_size.into();
 
         let mut destructors = DestructorSet::new("farmer-destructors");
 
@@ -390,9 +654,11 @@ impl Config {
             replotting_thread_pool_size,
             sector_downloading_concurrency,
             sector_encoding_concurrency,
+            rpc_listen_address,
         } = self;
 
-        let mut single_disk_farms = Vec::with_capacity(farms.len());
+        let mut farm_runners = Vec::with_capacity(farms.len());
+        let mut farm_slots = Vec::with_capacity(farms.len());
         let mut farm_info = HashMap::with_capacity(farms.len());
 
         let readers_and_pieces = Arc::clone(&node.dsn().farmer_readers_and_pieces);
@@ -486,16 +752,26 @@ impl Config {
             replotting_thread_pool_core_indices
         };
 
-        let downloading_semaphore = Arc::new(Semaphore::new(
-            sector_downloading_concurrency
-                .map(|sector_downloading_concurrency| sector_downloading_concurrency.get())
-                .unwrap_or(plotting_thread_pool_core_indices.len() + 1),
-        ));
+        let plotting_thread_pools = NonZeroUsize::new(plotting_thread_pool_core_indices.len())
+            .expect("There is always at least one thread pool; qed");
+        let sector_downloading_concurrency =
+            sector_downloading_concurrency.unwrap_or(plotting_thread_pools.saturating_add(1));
+        let downloading_semaphore = Arc::new(Semaphore::new(sector_downloading_concurrency.get()));
 
         let all_cpu_cores = all_cpu_cores();
         let plotting_thread_pool_manager = create_plotting_thread_pool_manager(
             plotting_thread_pool_core_indices.into_iter().zip(replotting_thread_pool_core_indices),
         )?;
+        let plotting_controller = PlottingController::new(
+            Arc::clone(&downloading_semaphore),
+            plotting_thread_pool_manager.clone(),
+            sector_downloading_concurrency,
+            plotting_thread_pools,
+        );
+        destructors.add_sync_destructor({
+            let plotting_controller = plotting_controller.clone();
+            move || plotting_controller.close()
+        })?;
         let farming_thread_pool_size = farming_thread_pool_size
             .map(|farming_thread_pool_size| farming_thread_pool_size.get())
             .unwrap_or_else(|| {
@@ -530,30 +806,46 @@ impl Config {
             }
         }
 
+        let farm_factory: FarmFactory<T> = {
+            let node_client = node.rpc().clone();
+            let farmer_metrics = node.dsn().farmer_metrics.clone();
+            let readers_and_pieces = Arc::clone(&readers_and_pieces);
+            let piece_getter = Arc::clone(&farmer_piece_getter);
+
+            Box::new(move |disk_farm_idx, description, plotting_delay| {
+                Farm::new(FarmOptions {
+                    disk_farm_idx,
+                    piece_cache_size,
+                    reward_address,
+                    node_client: node_client.clone(),
+                    farmer_metrics: farmer_metrics.clone(),
+                    readers_and_pieces: Arc::clone(&readers_and_pieces),
+                    max_pieces_in_sector,
+                    piece_getter: Arc::clone(&piece_getter),
+                    description,
+                    kzg: kzg.clone(),
+                    erasure_coding: erasure_coding.clone(),
+                    farming_thread_pool_size,
+                    plotting_delay,
+                    downloading_semaphore: Arc::clone(&downloading_semaphore),
+                    plotting_thread_pool_manager: plotting_thread_pool_manager.clone(),
+                })
+                .boxed()
+            })
+        };
+
         for (disk_farm_idx, description) in farms.iter().enumerate() {
             let (plotting_delay_sender, plotting_delay_receiver) =
                 futures::channel::oneshot::channel();
             plotting_delay_senders.push(plotting_delay_sender);
 
-            let (farm, single_disk_farm) = Farm::new(FarmOptions {
-                disk_farm_idx,
-                cache_percentage,
-                reward_address,
-                node,
-                max_pieces_in_sector,
-                piece_getter: Arc::clone(&farmer_piece_getter),
-                description,
-                kzg: kzg.clone(),
-                erasure_coding: erasure_coding.clone(),
-                farming_thread_pool_size,
-                plotting_delay: Some(plotting_delay_receiver),
-                downloading_semaphore: Arc::clone(&downloading_semaphore),
-                plotting_thread_pool_manager: plotting_thread_pool_manager.clone(),
-            })
-            .await?;
+            let (farm, farm_runner) =
+                farm_factory(disk_farm_idx, description.clone(), Some(plotting_delay_receiver))
+                    .await?;
 
+            farm_slots.push(farm_runner.slot(farm.directory.clone()));
             farm_info.insert(farm.directory.clone(), farm);
-            single_disk_farms.push(single_disk_farm);
+            farm_runners.push(farm_runner);
         }
 
         *node.dsn().farmer_piece_cache.write() = Some(farmer_piece_cache.clone());
@@ -566,13 +858,12 @@ impl Config {
 
         let cache_acknowledgement_receiver = farmer_piece_cache
             .replace_backing_caches(
-                single_disk_farms
+                farm_slots
                     .iter()
-                    .map(|single_disk_farm| single_disk_farm.piece_cache())
+                    .filter_map(|slot| Some(slot.farm.as_ref()?.piece_cache.clone()))
                     .collect(),
             )
             .await;
-        drop(farmer_piece_cache);
 
         let (plotting_delay_task_drop_sender, plotting_delay_task_drop_receiver) =
             oneshot::channel::<()>();
@@ -606,8 +897,7 @@ impl Config {
             }
         })?;
 
-        let readers_and_pieces_instance = create_readers_and_pieces(&single_disk_farms).await?;
-        readers_and_pieces.lock().replace(readers_and_pieces_instance);
+        readers_and_pieces.lock().replace(index_farms(&farm_slots));
         destructors.add_sync_destructor({
             let farmer_reader_and_pieces = node.dsn().farmer_readers_and_pieces.clone();
             move || {
@@ -615,39 +905,10 @@ impl Config {
             }
         })?;
 
-        let mut sector_plotting_handler_ids = vec![];
-        for (disk_farm_index, single_disk_farm) in single_disk_farms.iter().enumerate() {
-            let readers_and_pieces = Arc::clone(&readers_and_pieces);
-            let span = tracing::info_span!("farm", %disk_farm_index);
-
-            // Collect newly plotted pieces
-            // TODO: Once we have replotting, this will have to be updated
-            sector_plotting_handler_ids.push(single_disk_farm.on_sector_update(Arc::new(
-                move |(_plotted_sector, sector_update)| {
-                    let _span_guard = span.enter();
-
-                    let (plotted_sector, maybe_old_plotted_sector) = match sector_update {
-                        SectorUpdate::Plotting(SectorPlottingDetails::Finished {
-                            plotted_sector,
-                            old_plotted_sector,
-                            ..
-                        }) => (plotted_sector, old_plotted_sector),
-                        _ => return,
-                    };
-
-                    handler_on_sector_plotted(
-                        plotted_sector,
-                        maybe_old_plotted_sector,
-                        disk_farm_index,
-                        readers_and_pieces.clone(),
-                    )
-                },
-            )));
-        }
-
         let mut single_disk_farms_stream =
-            single_disk_farms.into_iter().map(SingleDiskFarm::run).collect::<FuturesUnordered<_>>();
+            farm_runners.into_iter().map(FarmRunner::run).collect::<FuturesUnordered<_>>();
 
+        let (new_farms_sender, mut new_farms_receiver) = mpsc::unbounded_channel::<FarmRunFuture>();
         let (farm_driver_drop_sender, mut farm_driver_drop_receiver) = oneshot::channel::<()>();
         let (farm_driver_result_sender, farm_driver_result_receiver) =
             mpsc::channel::<_>(u8::MAX as usize + 1);
@@ -657,34 +918,45 @@ impl Config {
                 let handle = tokio::runtime::Handle::current();
 
                 move || {
-                    use future::Either::*;
+                    enum Event {
+                        FarmExited(Option<anyhow::Result<SingleDiskFarmId>>),
+                        FarmAdded(FarmRunFuture),
+                        Dropped,
+                    }
 
                     loop {
-                        let result = handle.block_on(future::select(
-                            single_disk_farms_stream.next(),
-                            &mut farm_driver_drop_receiver,
-                        ));
-
-                        match result {
-                            Left((maybe_result, _)) => {
-                                let send_result = match maybe_result {
-                                    None => farm_driver_result_sender
-                                        .try_send(Ok(TaskOutput::Value(None))),
-                                    Some(result) => match result {
-                                        Ok(single_disk_farm_id) => farm_driver_result_sender
-                                            .try_send(Ok(TaskOutput::Value(Some(
-                                                single_disk_farm_id,
-                                            )))),
-                                        Err(e) => farm_driver_result_sender.try_send(Err(e)),
-                                    },
-                                };
+                        // Farms which are added later are received over the channel, removed farms
+                        // exit with `None`
+                        let event = handle.block_on(async {
+                            tokio::select! {
+                                Some(maybe_result) = single_disk_farms_stream.next(),
+                                    if !single_disk_farms_stream.is_empty() =>
+                                    Event::FarmExited(maybe_result),
+                                Some(farm) = new_farms_receiver.recv() => Event::FarmAdded(farm),
+                                _ = &mut farm_driver_drop_receiver => Event::Dropped,
+                                else => Event::Dropped,
+                            }
+                        });
 
-                                // Receiver is closed which would mean we are shutting down
-                                if send_result.is_err() {
-                                    break;
+                        let send_result = match event {
+                            Event::FarmAdded(farm) => {
+                                single_disk_farms_stream.push(farm);
+                                continue;
+                            }
+                            Event::FarmExited(None) => continue,
+                            Event::FarmExited(Some(result)) => {
+                                let send_result = match result {
+                                    Ok(single_disk_farm_id) => farm_driver_result_sender
+                                        .try_send(Ok(TaskOutput::Value(Some(single_disk_farm_id)))),
+                                    Err(e) => farm_driver_result_sender.try_send(Err(e)),
+                                };
+                                if single_disk_farms_stream.is_empty() {
+                                    let _ = farm_driver_result_sender
+                                        .try_send(Ok(TaskOutput::Value(None)));
                                 }
+                                send_result
                             }
-                            Right((_, _)) => {
+                            Event::Dropped => {
                                 warn!("Received drop signal for farm driver, exiting...");
                                 let _ =
                                     farm_driver_result_sender.try_send(Ok(TaskOutput::Cancelled(
@@ -693,6 +965,11 @@ impl Config {
                                 break;
                             }
                         };
+
+                        // Receiver is closed which would mean we are shutting down
+                        if send_result.is_err() {
+                            break;
+                        }
                     }
                 }
             });
@@ -704,27 +981,55 @@ impl Config {
             }
         })?;
 
-        for handler_id in sector_plotting_handler_ids.drain(..) {
-            destructors.add_items_to_drop(handler_id)?;
-        }
-
         tracing::debug!("Started farmer");
 
-        Ok(Farmer {
+        let mut farmer = Farmer {
             reward_address,
             farm_info,
+            farm_slots,
+            farm_factory,
+            new_farms_sender,
+            farmer_piece_cache,
+            readers_and_pieces,
             result_receiver: Some(farm_driver_result_receiver),
+            piece_cache_size,
+            plotting_controller,
             node_name,
             app_info: subspace_farmer::NodeClient::farmer_app_info(node.rpc())
                 .await
                 .expect("Node is always reachable"),
             _destructors: destructors,
-        })
+        };
+
+        if let Some(address) = rpc_listen_address {
+            let module = farmer.rpc_module().await?;
+            let server_handle = rpc::start_server(address, module).await?;
+            farmer._destructors.add_sync_destructor(move || {
+                let _ = server_handle.stop();
+            })?;
+        }
+
+        Ok(farmer)
     }
 }
 
 type ResultReceiver = mpsc::Receiver<anyhow::Result<TaskOutput<Option<SingleDiskFarmId>, String>>>;
 
+/// Farm run by the farm driver, resolves to `None` once the farm is removed
+type FarmRunFuture =
+    std::pin::Pin<Box<dyn Future<Output = Option<anyhow::Result<SingleDiskFarmId>>> + Send>>;
+
+/// Creates farms with the options of the farmer, also after it is built
+type FarmFactory<T> = Box<
+    dyn Fn(
+            usize,
+            FarmDescription,
+            Option<futures::channel::oneshot::Receiver<()>>,
+        ) -> future::BoxFuture<'static, Result<(Farm<T>, FarmRunner), BuildError>>
+        + Send
+        + Sync,
+>;
+
 /// Farmer structure
 #[derive(Derivative)]
 #[derivative(Debug)]
@@ -732,7 +1037,19 @@ type ResultReceiver = mpsc::Receiver<anyhow::Result<TaskOutput<Option<SingleDisk
 pub struct Farmer<T: subspace_proof_of_space::Table> {
     reward_address: PublicKey,
     farm_info: HashMap<PathBuf, Farm<T>>,
+    #[derivative(Debug = "ignore")]
+    farm_slots: Vec<FarmSlot>,
+    #[derivative(Debug = "ignore")]
+    farm_factory: FarmFactory<T>,
+    #[derivative(Debug = "ignore")]
+    new_farms_sender: mpsc::UnboundedSender<FarmRunFuture>,
+    #[derivative(Debug = "ignore")]
+    farmer_piece_cache: FarmerPieceCache,
+    #[derivative(Debug = "ignore")]
+    readers_and_pieces: Arc<parking_lot::Mutex<Option<ReadersAndPieces>>>,
     result_receiver: Option<ResultReceiver>,
+    piece_cache_size: PieceCacheSize,
+    plotting_controller: PlottingController,
     node_name: String,
     app_info: FarmerAppInfo,
     _destructors: DestructorSet,
@@ -753,10 +1070,12 @@ pub struct FarmInfo {
     pub allocated_space: ByteSize,
     /// How many pieces are in sector
     pub pieces_in_sector: u16,
+    /// Space used by the piece cache of the farm
+    pub piece_cache_size: ByteSize,
 }
 
-impl From<SingleDiskFarmInfo> for FarmInfo {
-    fn from(info: SingleDiskFarmInfo) -> Self {
+impl FarmInfo {
+    fn new(info: SingleDiskFarmInfo, piece_cache_size: ByteSize) -> Self {
         let SingleDiskFarmInfo::V0 {
             id,
             genesis_hash,
@@ -770,6 +1089,7 @@ impl From<SingleDiskFarmInfo> for FarmInfo {
             public_key: PublicKey(public_key),
             allocated_space: ByteSize::b(allocated_space),
             pieces_in_sector,
+            piece_cache_size,
         }
     }
 }
@@ -797,59 +1117,76 @@ pub struct InitialPlottingProgress {
     pub starting_sector: u64,
     /// Current number of sectors
     pub current_sector: u64,
+    /// Number of sectors which are being plotted, and are not counted in
+    /// `current_sector` yet. It doesn't exceed the number of sectors which
+    /// are left to plot.
+    pub sectors_in_flight: u64,
     /// Total number of sectors on disk
     pub total_sectors: u64,
 }
 
-/// Progress data received from sender, used to monitor plotting progress
-pub type ProgressData = Option<(u16, SectorUpdate)>;
+impl InitialPlottingProgress {
+    /// Progress of a farm with `plotted_sectors` sectors plotted before it
+    /// was started
+    fn new(plotted_sectors: u64, total_sectors: u64) -> Self {
+        Self {
+            starting_sector: plotted_sectors,
+            current_sector: plotted_sectors.min(total_sectors),
+            sectors_in_flight: 0,
+            total_sectors,
+        }
+    }
+
+    /// Whether all the sectors of the farm are plotted
+    pub fn is_finished(&self) -> bool {
+        self.current_sector >= self.total_sectors
+    }
+
+    /// Applies an update of a sector, returns whether the progress changed.
+    /// Replotted sectors are not part of the initial plotting.
+    ///
+    /// Failed plotting of a sector stops the farm, so the sector isn't
+    /// finished and stays in flight until then. Sectors in flight are capped
+    /// at the sectors left to plot, so that they are not overcounted if the
+    /// sector is started again.
+    fn update(&mut self, kind: SectorEventKind) -> bool {
+        let previous = *self;
+        match kind {
+            SectorEventKind::PlottingStarted => self.sectors_in_flight += 1,
+            SectorEventKind::Finished { replotted: false, .. } => {
+                self.sectors_in_flight = self.sectors_in_flight.saturating_sub(1);
+                self.current_sector = (self.current_sector + 1).min(self.total_sectors);
+            }
+            _ => return false,
+        }
+        self.sectors_in_flight =
+            self.sectors_in_flight.min(self.total_sectors - self.current_sector);
+        *self != previous
+    }
+}
+
+/// Current history size of the chain, fetched from the node of the farm
+type HistorySizeGetter =
+    Arc<dyn Fn() -> future::BoxFuture<'static, anyhow::Result<HistorySize>> + Send + Sync>;
 
 /// Farm structure
-#[derive(Debug)]
+#[derive(Derivative)]
+#[derivative(Debug)]
 pub struct Farm<T: subspace_proof_of_space::Table> {
     directory: PathBuf,
-    progress: watch::Receiver<ProgressData>,
     solutions: watch::Receiver<Option<SolutionResponse>>,
-    initial_plotting_progress: Arc<Mutex<InitialPlottingProgress>>,
+    sector_events: broadcast::Sender<SectorEvent>,
+    sector_expirations: Arc<parking_lot::Mutex<sectors::SectorExpirations>>,
+    #[derivative(Debug = "ignore")]
+    history_size: HistorySizeGetter,
+    initial_plotting_progress: watch::Receiver<InitialPlottingProgress>,
     allocated_space: u64,
+    piece_cache_size: PieceCacheSize,
+    stopped_receiver: oneshot::Receiver<()>,
     _destructors: DestructorSet,
     _table: std::marker::PhantomData<T>,
 }
 
-#[pin_project::pin_project]
-struct InitialPlottingProgressStreamInner<S> {
-    last_initial_plotting_progress: InitialPlottingProgress,
-    #[pin]
-    stream: S,
-}
-
-impl<S: Stream> Stream for InitialPlottingProgressStreamInner<S>
-where
-    S: Stream<Item = InitialPlottingProgress>,
-{
-    type Item = InitialPlottingProgress;
-
-    fn poll_next(
-        self: std::pin::Pin<&mut Self>,
-        cx: &mut std::task::Context<'_>,
-    ) -> std::task::Poll<Option<Self::Item>> {
-        let this = self.project();
-        match this.stream.poll_next(cx) {
-            result @ std::task::Poll::Ready(Some(progress)) => {
-                *this.last_initial_plotting_progress = progress;
-                result
-            }
-            result => result,
-        }
-    }
-
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        let left = self.last_initial_plotting_progress.total_sectors
-            - self.last_initial_plotting_progress.current_sector;
-        (left as usize, Some(left as usize))
-    }
-}
-
 /// Initial plotting progress stream
 #[pin_project::pin_project]
 pub struct InitialPlottingProgressStream {
@@ -873,13 +1210,15 @@ impl Stream for InitialPlottingProgressStream {
     }
 }
 
-struct FarmOptions<'a, PG, N: sdk_traits::Node> {
+struct FarmOptions<PG, NC> {
     pub disk_farm_idx: usize,
-    pub cache_percentage: NonZeroU8,
+    pub piece_cache_size: PieceCacheSize,
     pub reward_address: PublicKey,
-    pub node: &'a N,
+    pub node_client: NC,
+    pub farmer_metrics: Option<FarmerMetrics>,
+    pub readers_and_pieces: Arc<parking_lot::Mutex<Option<ReadersAndPieces>>>,
     pub piece_getter: PG,
-    pub description: &'a FarmDescription,
+    pub description: FarmDescription,
     pub kzg: kzg::Kzg,
     pub erasure_coding: ErasureCoding,
     pub max_pieces_in_sector: u16,
@@ -889,13 +1228,85 @@ struct FarmOptions<'a, PG, N: sdk_traits::Node> {
     pub plotting_thread_pool_manager: PlottingThreadPoolManager,
 }
 
+/// Farm which is created, but not run by the farm driver yet
+struct FarmRunner {
+    single_disk_farm: SingleDiskFarm,
+    pieces: Arc<parking_lot::Mutex<FarmPieces>>,
+    stop_receiver: oneshot::Receiver<()>,
+    stopped_sender: oneshot::Sender<()>,
+}
+
+/// Checks that the farm, if it exists already, was created for the chain of
+/// the node, and with the identity it has
+fn check_existing_farm(directory: &Path, node_genesis_hash: [u8; 32]) -> Result<(), BuildError> {
+    let Some(SingleDiskFarmInfo::V0 { genesis_hash, public_key, .. }) =
+        SingleDiskFarmInfo::load_from(directory)
+            .with_context(|| format!("Failed to read farm info at `{directory:?}'"))?
+    else {
+        return Ok(());
+    };
+    if genesis_hash != node_genesis_hash {
+        return Err(BuildError::ChainMismatch {
+            directory: directory.to_path_buf(),
+            farm_genesis_hash: genesis_hash,
+            node_genesis_hash,
+        });
+    }
+
+    let identity = Identity::open(directory)
+        .with_context(|| format!("Failed to open identity of the farm at `{directory:?}'"))?;
+    if let Some(identity) = identity {
+        let identity_public_key =
+            subspace_core_primitives::PublicKey::from(identity.public_key().to_bytes());
+        if identity_public_key != public_key {
+            return Err(BuildError::IdentityMismatch {
+                directory: directory.to_path_buf(),
+                farm_public_key: PublicKey(public_key),
+                identity_public_key: PublicKey(identity_public_key),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+impl FarmRunner {
+    fn slot(&self, directory: PathBuf) -> FarmSlot {
+        FarmSlot {
+            piece_reader: self.single_disk_farm.piece_reader(),
+            farm: Some(IndexedFarm {
+                directory,
+                piece_cache: self.single_disk_farm.piece_cache(),
+                pieces: Arc::clone(&self.pieces),
+            }),
+        }
+    }
+
+    fn run(self) -> FarmRunFuture {
+        let Self { single_disk_farm, stop_receiver, stopped_sender, .. } = self;
+        Box::pin(async move {
+            match future::select(Box::pin(single_disk_farm.run()), stop_receiver).await {
+                future::Either::Left((result, _)) => Some(result),
+                // Farm is dropped, either on removal or on shutdown
+                future::Either::Right((_, single_disk_farm)) => {
+                    drop(single_disk_farm);
+                    let _ = stopped_sender.send(());
+                    None
+                }
+            }
+        })
+    }
+}
+
 impl<T: subspace_proof_of_space::Table> Farm<T> {
     async fn new(
         FarmOptions {
             disk_farm_idx,
-            cache_percentage,
+            piece_cache_size,
             reward_address,
-            node,
+            node_client,
+            farmer_metrics,
+            readers_and_pieces,
             piece_getter,
             description,
             kzg,
@@ -906,16 +1317,42 @@ impl<T: subspace_proof_of_space::Table> Farm<T> {
             downloading_semaphore,
             plotting_thread_pool_manager,
         }: FarmOptions<
-            '_,
             impl subspace_farmer_components::PieceGetter + Clone + Send + Sync + 'static,
-            impl sdk_traits::Node,
+            impl subspace_farmer::NodeClient,
         >,
-    ) -> Result<(Self, SingleDiskFarm), BuildError> {
+    ) -> Result<(Self, FarmRunner), BuildError> {
+        let disk_farm_index = u8::try_from(disk_farm_idx).map_err(|_error| {
+            anyhow!(
+                "More than 256 farms are not supported, consider running multiple farmer instances"
+            )
+        })?;
         let directory = description.directory.clone();
         let allocated_space = description.space_pledged.as_u64();
-        let farmer_app_info = subspace_farmer::NodeClient::farmer_app_info(node.rpc())
+        let piece_cache_size = description.piece_cache_size.unwrap_or(piece_cache_size);
+        let cache_percentage = piece_cache_size.percentage(description.space_pledged).ok_or(
+            BuildError::PieceCacheTooLarge {
+                directory: directory.clone(),
+                piece_cache_size,
+                space_pledged: description.space_pledged,
+            },
+        )?;
+        let farmer_app_info = subspace_farmer::NodeClient::farmer_app_info(&node_client)
             .await
             .expect("Node is always reachable");
+        check_existing_farm(&directory, farmer_app_info.genesis_hash)?;
+        let history_size: HistorySizeGetter = {
+            let node_client = node_client.clone();
+            Arc::new(move || {
+                let node_client = node_client.clone();
+                async move {
+                    subspace_farmer::NodeClient::farmer_app_info(&node_client)
+                        .await
+                        .map(|farmer_app_info| farmer_app_info.protocol_info.history_size)
+                        .map_err(|error| anyhow!("Failed to get farmer app info: {error}"))
+                }
+                .boxed()
+            })
+        };
 
         let description = SingleDiskFarmOptions {
             allocated_space,
@@ -923,7 +1360,7 @@ impl<T: subspace_proof_of_space::Table> Farm<T> {
             farmer_app_info,
             max_pieces_in_sector,
             reward_address: *reward_address,
-            node_client: node.rpc().clone(),
+            node_client,
             kzg,
             erasure_coding,
             piece_getter,
@@ -953,59 +1390,142 @@ impl<T: subspace_proof_of_space::Table> Farm<T> {
         };
         let mut destructors = DestructorSet::new_without_async("farm-destructors");
 
-        let progress = {
-            let (sender, receiver) = watch::channel::<Option<_>>(None);
+        let metrics = farmer_metrics.map(|metrics| {
+            let labels = FarmerMetrics::farm_labels(&directory);
+            metrics
+                .allocated_space
+                .get_or_create(&labels)
+                .set(i64::try_from(allocated_space).unwrap_or(i64::MAX));
+            (metrics, labels)
+        });
+
+        if let Some((metrics, labels)) = metrics.clone() {
             destructors.add_items_to_drop(single_disk_farm.on_sector_update(Arc::new(
-                move |sector| {
-                    let _ = sender.send(Some(sector.clone()));
+                move |(_, sector_update)| {
+                    if let SectorUpdate::Plotting(SectorPlottingDetails::Finished {
+                        old_plotted_sector,
+                        ..
+                    }) = sector_update
+                    {
+                        let sectors = if old_plotted_sector.is_some() {
+                            &metrics.sectors_replotted
+                        } else {
+                            &metrics.sectors_plotted
+                        };
+                        sectors.get_or_create(&labels).inc();
+                    }
                 },
             )))?;
-            receiver
-        };
+        }
         let solutions = {
             let (sender, receiver) = watch::channel::<Option<_>>(None);
             destructors.add_items_to_drop(single_disk_farm.on_solution(Arc::new(
                 move |solution| {
+                    if let Some((metrics, labels)) = &metrics {
+                        metrics.solutions.get_or_create(labels).inc();
+                    }
                     let _ = sender.send(Some(solution.clone()));
                 },
             )))?;
             receiver
         };
 
-        // TODO: This calculation is directly imported from the monorepo and relies on
-        // internal calculation of farm. Remove it once we have public function.
-        let fixed_space_usage = 2 * 1024 * 1024
-            + Identity::file_size() as u64
-            + KnownPeersManager::file_size(KNOWN_PEERS_CACHE_SIZE) as u64;
-        // Calculate how many sectors can fit
-        let target_sector_count = {
-            let potentially_plottable_space = allocated_space.saturating_sub(fixed_space_usage)
-                / 100
-                * (100 - u64::from(cache_percentage.get()));
-            // Do the rounding to make sure we have exactly as much space as fits whole
-            // number of sectors
-            potentially_plottable_space
-                / (sector_size(max_pieces_in_sector) + SectorMetadataChecksummed::encoded_size())
-                    as u64
+        let target_sector_count =
+            target_sector_count(allocated_space, cache_percentage, max_pieces_in_sector);
+
+        // Progress is shared by all the subscribers, and it starts from the
+        // sectors which were plotted before the farm was started
+        let (initial_plotting_progress_sender, initial_plotting_progress) =
+            watch::channel(InitialPlottingProgress::new(
+                u64::try_from(single_disk_farm.plotted_sectors_count().await)
+                    .expect("Sector count is less than u64::MAX"),
+                target_sector_count,
+            ));
+        let sector_expirations =
+            Arc::new(parking_lot::Mutex::new(sectors::SectorExpirations::default()));
+        let (sector_events, _) = broadcast::channel(SECTOR_EVENTS_CAPACITY);
+        destructors.add_items_to_drop(single_disk_farm.on_sector_update(Arc::new({
+            let sector_expirations = Arc::clone(&sector_expirations);
+            let sector_events = sector_events.clone();
+            move |(sector_index, sector_update)| {
+                if let Some(event) = SectorEvent::new(*sector_index, sector_update) {
+                    initial_plotting_progress_sender
+                        .send_if_modified(|progress| progress.update(event.kind));
+                    let mut sector_expirations = sector_expirations.lock();
+                    sector_expirations.update(event);
+                    // Sent under the lock, so that the expirations which
+                    // lagging subscribers resync with are in line with the
+                    // updates which follow them. Doesn't matter if there are
+                    // no subscribers.
+                    let _ = sector_events.send(event);
+                }
+            }
+        })))?;
+
+        let pieces = {
+            let plotted_sectors = single_disk_farm.plotted_sectors().await;
+            let mut pieces = FarmPieces::default();
+            (0 as SectorIndex..).zip(plotted_sectors).for_each(
+                |(sector_index, plotted_sector_result)| match plotted_sector_result {
+                    Ok(plotted_sector) => pieces.insert(&plotted_sector),
+                    Err(error) => {
+                        error!(
+                            %error,
+                            %disk_farm_index,
+                            %sector_index,
+                            "Failed reading plotted sector on startup, skipping"
+                        );
+                    }
+                },
+            );
+            Arc::new(parking_lot::Mutex::new(pieces))
         };
 
+        // Collect newly plotted pieces
+        destructors.add_items_to_drop(single_disk_farm.on_sector_update(Arc::new({
+            let pieces = Arc::clone(&pieces);
+            let span = tracing::info_span!("farm", %disk_farm_index);
+
+            move |(_plotted_sector, sector_update)| {
+                let _span_guard = span.enter();
+
+                if let SectorUpdate::Plotting(SectorPlottingDetails::Finished {
+                    plotted_sector,
+                    old_plotted_sector,
+                    ..
+                }) = sector_update
+                {
+                    handler_on_sector_plotted(
+                        plotted_sector,
+                        old_plotted_sector,
+                        disk_farm_index,
+                        &readers_and_pieces,
+                        &pieces,
+                    );
+                }
+            }
+        })))?;
+
+        // Farm driver stops the farm once it is dropped
+        let (stop_sender, stop_receiver) = oneshot::channel::<()>();
+        destructors.add_items_to_drop(stop_sender)?;
+        let (stopped_sender, stopped_receiver) = oneshot::channel::<()>();
+
         Ok((
             Self {
                 directory: directory.clone(),
                 allocated_space,
-                progress,
+                piece_cache_size,
                 solutions,
-                initial_plotting_progress: Arc::new(Mutex::new(InitialPlottingProgress {
-                    starting_sector: u64::try_from(single_disk_farm.plotted_sectors_count().await)
-                        .expect("Sector count is less than u64::MAX"),
-                    current_sector: u64::try_from(single_disk_farm.plotted_sectors_count().await)
-                        .expect("Sector count is less than u64::MAX"),
-                    total_sectors: target_sector_count,
-                })),
+                sector_events,
+                sector_expirations,
+                history_size,
+                initial_plotting_progress,
+                stopped_receiver,
                 _destructors: destructors,
                 _table: Default::default(),
             },
-            single_disk_farm,
+            FarmRunner { single_disk_farm, pieces, stop_receiver, stopped_sender },
         ))
     }
 
@@ -1019,44 +1539,127 @@ impl<T: subspace_proof_of_space::Table> Farm<T> {
         ByteSize::b(self.allocated_space)
     }
 
+    /// Size of the piece cache of the farm, as it was configured
+    pub fn piece_cache_size(&self) -> PieceCacheSize {
+        self.piece_cache_size
+    }
+
     /// Will return a stream of initial plotting progress which will end once we
     /// finish plotting
     pub async fn subscribe_initial_plotting_progress(&self) -> InitialPlottingProgressStream {
-        let initial = *self.initial_plotting_progress.lock().await;
-        if initial.current_sector == initial.total_sectors {
+        self.subscriptions().subscribe_initial_plotting_progress().await
+    }
+
+    /// New solution subscription
+    pub async fn subscribe_new_solutions(
+        &self,
+    ) -> impl Stream<Item = SolutionResponse> + Send + Sync + Unpin {
+        self.subscriptions().subscribe_new_solutions()
+    }
+
+    /// Updates of the sectors of the farm, both while the farm is plotted for
+    /// the first time and while its expired sectors are replotted. The stream
+    /// ends once the farm is closed.
+    ///
+    /// If the subscriber lags too far behind, the skipped updates are replaced
+    /// with the current expiration of the sectors, see
+    /// [`SectorUpdates::Resynced`].
+    pub async fn subscribe_sector_updates(
+        &self,
+    ) -> impl Stream<Item = SectorUpdates> + Send + Unpin {
+        let sector_expirations = Arc::clone(&self.sector_expirations);
+        Box::pin(stream::unfold(self.sector_events.subscribe(), move |mut receiver| {
+            let sector_expirations = Arc::clone(&sector_expirations);
+            async move {
+                match receiver.recv().await {
+                    Ok(event) => Some((SectorUpdates::Event(event), receiver)),
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!(skipped, "Sector updates subscriber lagged behind, resyncing");
+                        // Updates are sent under the lock, so none of them
+                        // are missed or applied twice
+                        let sector_expirations = sector_expirations.lock();
+                        let receiver = receiver.resubscribe();
+                        Some((SectorUpdates::Resynced(sector_expirations.events()), receiver))
+                    }
+                    Err(broadcast::error::RecvError::Closed) => None,
+                }
+            }
+        }))
+    }
+
+    /// Number of sectors which are expired, or which expire within
+    /// `within_segments` segments of the chain history. Sectors whose
+    /// expiration isn't determined yet are not counted.
+    pub async fn sector_expiry_report(
+        &self,
+        within_segments: u64,
+    ) -> anyhow::Result<SectorExpiryReport> {
+        let history_size = (self.history_size)().await?;
+        Ok(self.sector_expirations.lock().report(history_size.segment_index(), within_segments))
+    }
+
+    /// Measures the audit or the proving of the plotted sectors of the farm
+    /// at `directory`, with synthetic challenges. They are run on a thread
+    /// pool of `farming_thread_pool_size` threads, which defaults to the same
+    /// size as [`Config::farming_thread_pool_size`].
+    ///
+    /// The farm doesn't have to be opened, and it should not be farmed
+    /// meanwhile, as farming skews the results.
+    pub async fn benchmark(
+        directory: impl AsRef<Path>,
+        kind: BenchmarkKind,
+        sample_size: NonZeroUsize,
+        farming_thread_pool_size: Option<NonZeroUsize>,
+    ) -> anyhow::Result<BenchmarkReport> {
+        let directory = directory.as_ref().to_path_buf();
+        sdk_utils::task_spawn_blocking("sdk-farmer-benchmark", move || {
+            benchmark::benchmark::<T>(&directory, kind, sample_size, farming_thread_pool_size)
+        })
+        .await
+        .context("Benchmarking task panicked")?
+    }
+
+    /// Subscriptions of the farm, which can outlive it
+    pub(crate) fn subscriptions(&self) -> FarmSubscriptions {
+        FarmSubscriptions {
+            solutions: self.solutions.clone(),
+            initial_plotting_progress: self.initial_plotting_progress.clone(),
+        }
+    }
+}
+
+/// Receivers of a farm, used for subscribing to it without borrowing the farm
+#[derive(Debug, Clone)]
+pub(crate) struct FarmSubscriptions {
+    solutions: watch::Receiver<Option<SolutionResponse>>,
+    initial_plotting_progress: watch::Receiver<InitialPlottingProgress>,
+}
+
+impl FarmSubscriptions {
+    /// Current initial plotting progress
+    pub(crate) async fn initial_plotting_progress(&self) -> InitialPlottingProgress {
+        *self.initial_plotting_progress.borrow()
+    }
+
+    pub(crate) async fn subscribe_initial_plotting_progress(
+        &self,
+    ) -> InitialPlottingProgressStream {
+        if self.initial_plotting_progress.borrow().is_finished() {
             return InitialPlottingProgressStream {
                 boxed_stream: Box::pin(futures::stream::iter(None)),
             };
         }
 
-        let stream = tokio_stream::wrappers::WatchStream::new(self.progress.clone())
-            .filter_map({
-                let initial_plotting_progress = Arc::clone(&self.initial_plotting_progress);
-                move |_| {
-                    let initial_plotting_progress = Arc::clone(&initial_plotting_progress);
-                    async move {
-                        let mut guard = initial_plotting_progress.lock().await;
-                        let plotting_progress = *guard;
-                        guard.current_sector += 1;
-                        Some(plotting_progress)
-                    }
-                }
-            })
-            .take_while(|InitialPlottingProgress { current_sector, total_sectors, .. }| {
-                futures::future::ready(current_sector < total_sectors)
-            });
-        let last_initial_plotting_progress = *self.initial_plotting_progress.lock().await;
+        // Watch only keeps the latest progress, so a slow subscriber skips
+        // some of it, but never miscounts the sectors
+        let stream =
+            tokio_stream::wrappers::WatchStream::new(self.initial_plotting_progress.clone())
+                .take_while(|progress| futures::future::ready(!progress.is_finished()));
 
-        InitialPlottingProgressStream {
-            boxed_stream: Box::pin(Box::pin(InitialPlottingProgressStreamInner {
-                stream,
-                last_initial_plotting_progress,
-            })),
-        }
+        InitialPlottingProgressStream { boxed_stream: Box::pin(stream) }
     }
 
-    /// New solution subscription
-    pub async fn subscribe_new_solutions(
+    pub(crate) fn subscribe_new_solutions(
         &self,
     ) -> impl Stream<Item = SolutionResponse> + Send + Sync + Unpin {
         tokio_stream::wrappers::WatchStream::new(self.solutions.clone())
@@ -1074,12 +1677,24 @@ impl<T: subspace_proof_of_space::Table> Farmer<T> {
     pub async fn get_info(&self) -> anyhow::Result<Info> {
         let farms_info = tokio::task::spawn_blocking({
             let dirs = self.farm_info.keys().cloned().collect::<Vec<_>>();
-            || dirs.into_iter().map(SingleDiskFarm::collect_summary).collect::<Vec<_>>()
+            || {
+                dirs.into_iter()
+                    .map(|directory| {
+                        let piece_cache_size = layout::piece_cache_size(&directory);
+                        (SingleDiskFarm::collect_summary(directory), piece_cache_size)
+                    })
+                    .collect::<Vec<_>>()
+            }
         })
         .await?
         .into_iter()
-        .map(|summary| match summary {
-            SingleDiskFarmSummary::Found { info, directory } => Ok((directory, info.into())),
+        .map(|(summary, piece_cache_size)| match summary {
+            SingleDiskFarmSummary::Found { info, directory } => {
+                let piece_cache_size = piece_cache_size.with_context(|| {
+                    format!("Failed to get piece cache size at `{directory:?}'")
+                })?;
+                Ok((directory, FarmInfo::new(info, ByteSize::b(piece_cache_size))))
+            }
             SingleDiskFarmSummary::NotFound { directory } =>
                 Err(anyhow::anyhow!("Didn't found farm at `{directory:?}'")),
             SingleDiskFarmSummary::Error { directory, error } =>
@@ -1102,6 +1717,170 @@ impl<T: subspace_proof_of_space::Table> Farmer<T> {
         self.farm_info.values()
     }
 
+    /// Number of plotted pieces of the farm at `directory` which are served
+    /// to the network, `None` if the farm isn't served
+    pub fn served_piece_count(&self, directory: &Path) -> Option<usize> {
+        self.farm_slots.iter().find_map(|slot| {
+            let farm = slot.farm.as_ref().filter(|farm| farm.directory == directory)?;
+            let pieces = farm.pieces.lock();
+            Some(pieces.sectors.values().map(Vec::len).sum())
+        })
+    }
+
+    /// Controls the plotting of all the farms, also of the ones which are
+    /// added later
+    pub fn plotting_controller(&self) -> PlottingController {
+        self.plotting_controller.clone()
+    }
+
+    /// Adds a farm to the running farmer, with the same options as the other
+    /// farms. Its plotting starts once the piece cache is synced with it.
+    pub async fn add_farm(&mut self, description: FarmDescription) -> Result<(), BuildError> {
+        if self.farm_info.contains_key(&description.directory) {
+            return Err(anyhow!("Farm at `{:?}' is already added", description.directory).into());
+        }
+
+        let disk_farm_idx = self
+            .farm_slots
+            .iter()
+            .position(|slot| slot.farm.is_none())
+            .unwrap_or(self.farm_slots.len());
+        let (plotting_delay_sender, plotting_delay_receiver) = futures::channel::oneshot::channel();
+        let (farm, farm_runner) =
+            (self.farm_factory)(disk_farm_idx, description, Some(plotting_delay_receiver)).await?;
+
+        let slot = farm_runner.slot(farm.directory.clone());
+        match self.farm_slots.get_mut(disk_farm_idx) {
+            Some(free_slot) => *free_slot = slot,
+            None => self.farm_slots.push(slot),
+        }
+        let cache_acknowledgement_receiver = self.reindex_farms().await;
+        sdk_utils::task_spawn(
+            format!("sdk-farmer-{}-plotting-delay-task-{disk_farm_idx}", self.node_name),
+            async move {
+                if cache_acknowledgement_receiver.await.is_ok() {
+                    // Doesn't matter if receiver is gone
+                    let _ = plotting_delay_sender.send(());
+                }
+            },
+        );
+
+        self.new_farms_sender
+            .send(farm_runner.run())
+            .map_err(|_| anyhow!("Farm driver has exited already"))?;
+        info!(directory = ?farm.directory, "Added farm");
+        self.farm_info.insert(farm.directory.clone(), farm);
+
+        Ok(())
+    }
+
+    /// Stops farming and plotting of the farm, its data is kept on the disk.
+    /// The last farm can't be removed.
+    pub async fn remove_farm(&mut self, directory: &Path) -> anyhow::Result<()> {
+        if !self.farm_info.contains_key(directory) {
+            return Err(anyhow!("Didn't found farm at `{directory:?}'"));
+        }
+        if self.farm_info.len() == 1 {
+            return Err(anyhow!("Farmer should have at least one farm"));
+        }
+
+        // Farm driver stops the farm once it is dropped
+        drop(self.take_farm(directory).await?);
+        info!(?directory, "Removed farm");
+
+        Ok(())
+    }
+
+    /// Resizes the farm to `space_pledged`, see [`FarmDescription::resize`].
+    ///
+    /// The farm is stopped meanwhile, and farmed again with the new size. Use
+    /// [`FarmDescription::plan_resize`] beforehand to confirm shrinking.
+    pub async fn resize_farm(
+        &mut self,
+        directory: &Path,
+        space_pledged: ByteSize,
+    ) -> anyhow::Result<FarmResize> {
+        let farm = self
+            .farm_info
+            .get(directory)
+            .ok_or_else(|| anyhow!("Didn't found farm at `{directory:?}'"))?;
+        let description = FarmDescription::new(directory, space_pledged)
+            .with_piece_cache_size(Some(farm.piece_cache_size));
+        description.plan_resize(space_pledged, self.piece_cache_size)?;
+
+        let original = FarmDescription::new(directory, ByteSize::b(farm.allocated_space))
+            .with_piece_cache_size(Some(farm.piece_cache_size));
+
+        let Farm { stopped_receiver, .. } = self.take_farm(directory).await?;
+        // Farm is resized only once the farm driver is done with it
+        let _ = stopped_receiver.await;
+        let (description, resize) = match description.resize(space_pledged, self.piece_cache_size) {
+            Ok(resized) => resized,
+            Err(error) => {
+                self.restore_farm(original).await;
+                return Err(error);
+            }
+        };
+        if let Err(error) = self.add_farm(description).await {
+            self.restore_farm(original).await;
+            return Err(error.into());
+        }
+        info!(?directory, %space_pledged, "Resized farm");
+
+        Ok(resize)
+    }
+
+    /// Farms the farm again with its original size, after its resize failed
+    async fn restore_farm(&mut self, original: FarmDescription) {
+        let directory = original.directory.clone();
+        let space_pledged = original.space_pledged;
+        // Farm might be resized already, if only adding it back failed
+        let restored = match original.resize(space_pledged, self.piece_cache_size) {
+            Ok((original, _)) => self.add_farm(original).await.map_err(Into::into),
+            Err(error) => Err(error),
+        };
+        if let Err(error) = restored {
+            error!(%error, ?directory, "Failed to restore the farm after a failed resize");
+        }
+    }
+
+    /// Takes the farm out of the farmer, it is stopped once it is dropped
+    async fn take_farm(&mut self, directory: &Path) -> anyhow::Result<Farm<T>> {
+        let farm = self
+            .farm_info
+            .remove(directory)
+            .ok_or_else(|| anyhow!("Didn't found farm at `{directory:?}'"))?;
+
+        // Pieces of the farm are not served anymore, before the farm is stopped
+        for slot in &mut self.farm_slots {
+            if slot.farm.as_ref().is_some_and(|farm| farm.directory == directory) {
+                slot.farm = None;
+            }
+        }
+        while self.farm_slots.last().is_some_and(|slot| slot.farm.is_none()) {
+            self.farm_slots.pop();
+        }
+        let _ = self.reindex_farms().await;
+
+        Ok(farm)
+    }
+
+    /// Indexes the pieces of the farms again, and replaces the backing caches
+    /// of the piece cache with the caches of the farms
+    ///
+    /// Returns the receiver for the acknowledgement of the piece cache sync
+    async fn reindex_farms(&self) -> oneshot::Receiver<()> {
+        self.readers_and_pieces.lock().replace(index_farms(&self.farm_slots));
+        self.farmer_piece_cache
+            .replace_backing_caches(
+                self.farm_slots
+                    .iter()
+                    .filter_map(|slot| Some(slot.farm.as_ref()?.piece_cache.clone()))
+                    .collect(),
+            )
+            .await
+    }
+
     /// Stops farming, closes farms, and sends signal to the node
     pub async fn close(mut self) -> anyhow::Result<()> {
         self._destructors.async_drop().await?;
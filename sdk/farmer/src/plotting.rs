@@ -0,0 +1,179 @@
+//! Runtime control of the plotting, see [`PlottingController`]
+
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+
+use derivative::Derivative;
+use futures::prelude::*;
+use subspace_farmer::thread_pool_manager::PlottingThreadPoolManager;
+use tokio::sync::{oneshot, Semaphore};
+use tracing::info;
+
+/// Plotting state of the farmer, returned by [`PlottingController::status`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct PlottingStatus {
+    /// Whether the plotting and the replotting are paused
+    pub paused: bool,
+    /// Number of sectors which are downloaded concurrently
+    pub sector_downloading_concurrency: NonZeroUsize,
+    /// Number of sectors which are encoded concurrently
+    pub sector_encoding_concurrency: NonZeroUsize,
+    /// Maximum number of sectors which can be encoded concurrently, which is
+    /// the number of plotting thread pools
+    pub max_sector_encoding_concurrency: NonZeroUsize,
+}
+
+/// Pauses, resumes and throttles the plotting and the replotting of all the
+/// farms of the farmer while it is running, see
+/// [`crate::Farmer::plotting_controller`].
+///
+/// Sectors which are already being downloaded or encoded are finished before
+/// a pause or a lower concurrency takes effect.
+#[derive(Debug, Clone)]
+pub struct PlottingController {
+    inner: Arc<parking_lot::Mutex<Inner>>,
+}
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct Inner {
+    downloading_semaphore: Arc<Semaphore>,
+    #[derivative(Debug = "ignore")]
+    plotting_thread_pool_manager: PlottingThreadPoolManager,
+    /// Number of permits of the downloading semaphore, it is only ever grown
+    downloading_permits: NonZeroUsize,
+    status: PlottingStatus,
+    /// Holds the permits and the thread pools which are taken away from the
+    /// plotting, they are given back once it is dropped
+    throttle: Option<oneshot::Sender<()>>,
+    closed: bool,
+}
+
+impl PlottingController {
+    pub(crate) fn new(
+        downloading_semaphore: Arc<Semaphore>,
+        plotting_thread_pool_manager: PlottingThreadPoolManager,
+        sector_downloading_concurrency: NonZeroUsize,
+        max_sector_encoding_concurrency: NonZeroUsize,
+    ) -> Self {
+        let inner = Inner {
+            downloading_semaphore,
+            plotting_thread_pool_manager,
+            downloading_permits: sector_downloading_concurrency,
+            status: PlottingStatus {
+                paused: false,
+                sector_downloading_concurrency,
+                sector_encoding_concurrency: max_sector_encoding_concurrency,
+                max_sector_encoding_concurrency,
+            },
+            throttle: None,
+            closed: false,
+        };
+        Self { inner: Arc::new(parking_lot::Mutex::new(inner)) }
+    }
+
+    /// Current plotting state
+    pub fn status(&self) -> PlottingStatus {
+        self.inner.lock().status
+    }
+
+    /// Pauses the plotting and the replotting until [`Self::resume`] is
+    /// called
+    pub fn pause(&self) {
+        self.update(|status| status.paused = true);
+    }
+
+    /// Resumes the plotting and the replotting paused with [`Self::pause`]
+    pub fn resume(&self) {
+        self.update(|status| status.paused = false);
+    }
+
+    /// Changes the number of sectors which are downloaded concurrently
+    pub fn set_sector_downloading_concurrency(&self, concurrency: NonZeroUsize) {
+        self.update(|status| status.sector_downloading_concurrency = concurrency);
+    }
+
+    /// Changes the number of sectors which are encoded concurrently, it is
+    /// capped at [`PlottingStatus::max_sector_encoding_concurrency`]
+    pub fn set_sector_encoding_concurrency(&self, concurrency: NonZeroUsize) {
+        self.update(|status| {
+            status.sector_encoding_concurrency =
+                concurrency.min(status.max_sector_encoding_concurrency)
+        });
+    }
+
+    /// Gives everything back to the plotting, and ignores the later changes,
+    /// so that the farms can be closed
+    pub(crate) fn close(&self) {
+        let mut inner = self.inner.lock();
+        inner.closed = true;
+        inner.throttle.take();
+    }
+
+    fn update(&self, change: impl FnOnce(&mut PlottingStatus)) {
+        let mut inner = self.inner.lock();
+        let previous_status = inner.status;
+        change(&mut inner.status);
+        if inner.closed || inner.status == previous_status {
+            return;
+        }
+
+        let Inner {
+            downloading_semaphore,
+            plotting_thread_pool_manager,
+            downloading_permits,
+            status,
+            throttle,
+            ..
+        } = &mut *inner;
+        if status.sector_downloading_concurrency > *downloading_permits {
+            downloading_semaphore.add_permits(
+                status.sector_downloading_concurrency.get() - downloading_permits.get(),
+            );
+            *downloading_permits = status.sector_downloading_concurrency;
+        }
+
+        let (held_permits, held_thread_pools) = if status.paused {
+            (downloading_permits.get(), status.max_sector_encoding_concurrency.get())
+        } else {
+            (
+                downloading_permits.get() - status.sector_downloading_concurrency.get(),
+                status.max_sector_encoding_concurrency.get()
+                    - status.sector_encoding_concurrency.get(),
+            )
+        };
+
+        // Previous throttle gives everything back once it is dropped
+        let (throttle_sender, throttle_receiver) = oneshot::channel();
+        *throttle = Some(throttle_sender);
+        if held_permits > 0 || held_thread_pools > 0 {
+            let downloading_semaphore = Arc::clone(downloading_semaphore);
+            let plotting_thread_pool_manager = plotting_thread_pool_manager.clone();
+            let hold = async move {
+                let permits = match held_permits {
+                    0 => None,
+                    held_permits =>
+                        downloading_semaphore.acquire_many_owned(held_permits as u32).await.ok(),
+                };
+                let mut thread_pools = Vec::with_capacity(held_thread_pools);
+                for _ in 0..held_thread_pools {
+                    thread_pools.push(plotting_thread_pool_manager.get_thread_pools().await);
+                }
+                future::pending::<()>().await;
+                drop((permits, thread_pools));
+            };
+            sdk_utils::task_spawn(
+                "sdk-farmer-plotting-throttle",
+                future::select(Box::pin(hold), throttle_receiver),
+            );
+        }
+
+        info!(
+            paused = status.paused,
+            sector_downloading_concurrency = %status.sector_downloading_concurrency,
+            sector_encoding_concurrency = %status.sector_encoding_concurrency,
+            "Plotting is updated"
+        );
+    }
+}
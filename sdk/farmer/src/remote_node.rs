@@ -0,0 +1,163 @@
+//! Node reached over RPC, so that many farmers can share a single node
+
+use std::marker::PhantomData;
+use std::path::Path;
+use std::sync::{Arc, Weak};
+
+use anyhow::{anyhow, Context};
+use derivative::Derivative;
+use futures::prelude::*;
+use sdk_dsn::{Dsn, DsnShared, StandaloneDsnOptions};
+use sdk_utils::DestructorSet;
+use subspace_farmer::piece_cache::PieceCache as FarmerPieceCache;
+use subspace_farmer::utils::readers_and_pieces::ReadersAndPieces;
+use subspace_farmer::{NodeClient, NodeRpcClient};
+use subspace_networking::libp2p::identity::Keypair;
+use subspace_networking::{PieceByIndexRequest, PieceByIndexResponse};
+use tokio::sync::oneshot;
+use tracing_futures::Instrument;
+
+use crate::Farmer;
+
+/// File in the DSN directory with the networking keypair of the farmer
+const KEYPAIR_FILE: &str = "network_keypair";
+
+/// Node reached over WebSocket JSON-RPC, along with the DSN of the farmer
+///
+/// Can be passed to [`crate::Config::build`] instead of an in-process node
+#[derive(Derivative)]
+#[derivative(Debug)]
+#[must_use = "Node should be closed"]
+pub struct RemoteNode<T: subspace_proof_of_space::Table> {
+    name: String,
+    #[derivative(Debug = "ignore")]
+    rpc: NodeRpcClient,
+    dsn: DsnShared,
+    #[derivative(Debug = "ignore")]
+    _destructors: DestructorSet,
+    #[derivative(Debug = "ignore")]
+    _table: PhantomData<T>,
+}
+
+impl<T: subspace_proof_of_space::Table> RemoteNode<T> {
+    /// Connects to the node RPC at `url` (i.e. `ws://127.0.0.1:9944`) and
+    /// starts the DSN of the farmer, which keeps its state in `directory`
+    pub async fn connect(
+        url: &str,
+        name: impl Into<String>,
+        dsn: Dsn,
+        directory: impl AsRef<Path>,
+    ) -> anyhow::Result<Self> {
+        let name = name.into();
+        let directory = directory.as_ref();
+
+        let rpc = NodeRpcClient::new(url)
+            .await
+            .map_err(|error| anyhow!("Failed to connect to the node at `{url}`: {error}"))?;
+        let farmer_app_info = rpc
+            .farmer_app_info()
+            .await
+            .map_err(|error| anyhow!("Failed to get farmer app info from the node: {error}"))?;
+
+        tokio::fs::create_dir_all(directory).await.context("Failed to create the DSN directory")?;
+        let keypair = load_or_generate_keypair(&directory.join(KEYPAIR_FILE)).await?;
+
+        let (dsn, runner, _) = dsn.build_standalone_dsn(StandaloneDsnOptions {
+            genesis_hash: farmer_app_info.genesis_hash,
+            base_path: directory.to_path_buf(),
+            keypair,
+            bootstrap_addresses: farmer_app_info.dsn_bootstrap_nodes,
+            get_piece_by_index: get_piece_by_index::<T>,
+            is_metrics_enabled: false,
+        })?;
+
+        tracing::debug!("Subspace networking initialized: Node ID is {}", dsn.node.id());
+
+        let node_runner_future = subspace_farmer::utils::run_future_in_dedicated_thread(
+            move || async move {
+                runner.run().await;
+                tracing::error!("Exited from node runner future");
+            },
+            format!("sdk-networking-{name}"),
+        )
+        .context("Failed to run node runner future")?;
+
+        let mut destructors = DestructorSet::new("remote-node-destructors");
+        let (runner_drop_sender, runner_drop_receiver) = oneshot::channel::<()>();
+        let runner_join_handle =
+            sdk_utils::task_spawn(format!("sdk-farmer-{name}-networking"), async move {
+                futures::select! {
+                    _ = runner_drop_receiver.fuse() => {},
+                    _ = node_runner_future.fuse() => {},
+                }
+            });
+        destructors.add_async_destructor({
+            async move {
+                let _ = runner_drop_sender.send(());
+                runner_join_handle.await.expect("joining should not fail; qed");
+            }
+        })?;
+
+        Ok(Self { name, rpc, dsn, _destructors: destructors, _table: PhantomData })
+    }
+
+    /// Disconnects from the DSN and the node
+    pub async fn close(self) -> anyhow::Result<()> {
+        self._destructors.async_drop().await
+    }
+}
+
+impl<T: subspace_proof_of_space::Table> sdk_traits::Node for RemoteNode<T> {
+    type Rpc = NodeRpcClient;
+    type Table = T;
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn dsn(&self) -> &DsnShared {
+        &self.dsn
+    }
+
+    fn rpc(&self) -> &Self::Rpc {
+        &self.rpc
+    }
+}
+
+/// Keeps the peer id of the farmer the same across restarts
+async fn load_or_generate_keypair(path: &Path) -> anyhow::Result<Keypair> {
+    match tokio::fs::read(path).await {
+        Ok(encoded) => Keypair::from_protobuf_encoding(&encoded)
+            .context("Failed to decode the networking keypair"),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+            let keypair = Keypair::generate_ed25519();
+            let encoded = keypair
+                .to_protobuf_encoding()
+                .context("Failed to encode the networking keypair")?;
+            tokio::fs::write(path, encoded)
+                .await
+                .context("Failed to store the networking keypair")?;
+            Ok(keypair)
+        }
+        Err(error) => Err(error).context("Failed to read the networking keypair"),
+    }
+}
+
+fn get_piece_by_index<T: subspace_proof_of_space::Table>(
+    &PieceByIndexRequest { piece_index }: &PieceByIndexRequest,
+    weak_readers_and_pieces: Weak<parking_lot::Mutex<Option<ReadersAndPieces>>>,
+    farmer_piece_cache: Arc<parking_lot::RwLock<Option<FarmerPieceCache>>>,
+) -> impl Future<Output = Option<PieceByIndexResponse>> {
+    async move {
+        // Have to clone due to RAII guard is not `Send`
+        let farmer_piece_cache = farmer_piece_cache.read().clone()?;
+        let piece = <Farmer<T> as sdk_traits::Farmer>::get_piece_by_index(
+            piece_index,
+            &farmer_piece_cache,
+            &weak_readers_and_pieces,
+        )
+        .await;
+        Some(PieceByIndexResponse { piece })
+    }
+    .in_current_span()
+}
@@ -0,0 +1,223 @@
+//! `farmer_*` RPC namespace, so that external tools and UIs can watch the
+//! farmer
+//!
+//! Only read-only methods are exposed:
+//! - `farmer_info` returns [`RpcFarmerInfo`]
+//! - `farmer_subscribePlottingProgress` notifies [`RpcPlottingProgress`] until
+//!   the initial plotting of every farm is finished
+//! - `farmer_subscribeSolutions` notifies [`RpcSolution`] for every solution
+//!   found by the farms
+
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use futures::prelude::*;
+use jsonrpsee::server::{ServerBuilder, ServerHandle};
+use jsonrpsee::RpcModule;
+use sdk_utils::PublicKey;
+use serde::{Deserialize, Serialize};
+use subspace_core_primitives::SectorIndex;
+use subspace_farmer::single_disk_farm::SingleDiskFarmId;
+
+use crate::{FarmInfo, FarmSubscriptions, Farmer, Info};
+
+/// Farmer info returned by `farmer_info`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcFarmerInfo {
+    /// Version of the farmer
+    pub version: String,
+    /// Reward address of the farmer
+    pub reward_address: PublicKey,
+    /// Sector size in bytes
+    pub sector_size: u64,
+    /// Farms, indexed the same way as in the notifications
+    pub farms: Vec<RpcFarmInfo>,
+}
+
+/// Farm info returned by `farmer_info`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcFarmInfo {
+    /// Path of the farm
+    pub directory: PathBuf,
+    /// ID of the farm
+    pub id: SingleDiskFarmId,
+    /// Public key of identity used for farm creation
+    pub public_key: PublicKey,
+    /// How much space in bytes is allocated for this farm
+    pub allocated_space: u64,
+    /// How many pieces are in sector
+    pub pieces_in_sector: u16,
+    /// Space in bytes used by the piece cache of the farm
+    pub piece_cache_size: u64,
+    /// Number of sectors plotted during the initial plotting
+    pub plotted_sectors: u64,
+    /// Total number of sectors of the farm
+    pub total_sectors: u64,
+}
+
+/// Notification of `farmer_subscribePlottingProgress`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcPlottingProgress {
+    /// Index of the farm in [`RpcFarmerInfo::farms`]
+    pub farm: usize,
+    /// Path of the farm
+    pub directory: PathBuf,
+    /// Number of sectors plotted during the initial plotting
+    pub plotted_sectors: u64,
+    /// Total number of sectors of the farm
+    pub total_sectors: u64,
+}
+
+/// Notification of `farmer_subscribeSolutions`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcSolution {
+    /// Index of the farm in [`RpcFarmerInfo::farms`]
+    pub farm: usize,
+    /// Path of the farm
+    pub directory: PathBuf,
+    /// Slot of the solution
+    pub slot_number: u64,
+    /// Sector of the solution
+    pub sector_index: SectorIndex,
+}
+
+struct RpcFarm {
+    directory: PathBuf,
+    info: FarmInfo,
+    subscriptions: FarmSubscriptions,
+}
+
+/// Context of the RPC methods, it doesn't keep the farmer alive
+struct FarmerRpc {
+    version: String,
+    reward_address: PublicKey,
+    sector_size: u64,
+    farms: Vec<RpcFarm>,
+}
+
+impl FarmerRpc {
+    async fn info(&self) -> RpcFarmerInfo {
+        let mut farms = Vec::with_capacity(self.farms.len());
+        for RpcFarm { directory, info, subscriptions } in &self.farms {
+            let progress = subscriptions.initial_plotting_progress().await;
+            farms.push(RpcFarmInfo {
+                directory: directory.clone(),
+                id: info.id,
+                public_key: info.public_key,
+                allocated_space: info.allocated_space.as_u64(),
+                pieces_in_sector: info.pieces_in_sector,
+                piece_cache_size: info.piece_cache_size.as_u64(),
+                plotted_sectors: progress.current_sector,
+                total_sectors: progress.total_sectors,
+            });
+        }
+
+        RpcFarmerInfo {
+            version: self.version.clone(),
+            reward_address: self.reward_address,
+            sector_size: self.sector_size,
+            farms,
+        }
+    }
+}
+
+impl<T: subspace_proof_of_space::Table> Farmer<T> {
+    /// `farmer_*` RPC methods, which can be merged into any RPC server
+    ///
+    /// Subscriptions end once the farmer is closed. Only the farms of the
+    /// farmer at the time of the call are served.
+    pub async fn rpc_module(&self) -> anyhow::Result<RpcModule<()>> {
+        let Info { version, reward_address, mut farms_info, sector_size } = self.get_info().await?;
+        let farms = self
+            .farm_info
+            .iter()
+            .map(|(directory, farm)| {
+                Ok(RpcFarm {
+                    directory: directory.clone(),
+                    info: farms_info
+                        .remove(directory)
+                        .with_context(|| format!("Didn't found farm at `{directory:?}'"))?,
+                    subscriptions: farm.subscriptions(),
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let mut module = RpcModule::new(FarmerRpc { version, reward_address, sector_size, farms });
+
+        module.register_async_method("farmer_info", |_, rpc| async move {
+            Ok::<_, jsonrpsee::core::Error>(rpc.info().await)
+        })?;
+
+        module.register_subscription(
+            "farmer_subscribePlottingProgress",
+            "farmer_plottingProgress",
+            "farmer_unsubscribePlottingProgress",
+            |_, sink, rpc| {
+                sdk_utils::task_spawn("sdk-farmer-rpc-plotting-progress", async move {
+                    let progress = future::join_all(rpc.farms.iter().enumerate().map(
+                        |(farm_index, farm)| async move {
+                            farm.subscriptions.subscribe_initial_plotting_progress().await.map(
+                                move |progress| RpcPlottingProgress {
+                                    farm: farm_index,
+                                    directory: farm.directory.clone(),
+                                    plotted_sectors: progress.current_sector,
+                                    total_sectors: progress.total_sectors,
+                                },
+                            )
+                        },
+                    ))
+                    .await;
+                    sink.pipe_from_stream(stream::select_all(progress)).await;
+                });
+                Ok(())
+            },
+        )?;
+
+        module.register_subscription(
+            "farmer_subscribeSolutions",
+            "farmer_solution",
+            "farmer_unsubscribeSolutions",
+            |_, sink, rpc| {
+                sdk_utils::task_spawn("sdk-farmer-rpc-solutions", async move {
+                    let solutions = stream::select_all(rpc.farms.iter().enumerate().map(
+                        |(farm_index, farm)| {
+                            farm.subscriptions.subscribe_new_solutions().map(move |response| {
+                                RpcSolution {
+                                    farm: farm_index,
+                                    directory: farm.directory.clone(),
+                                    slot_number: response.slot_number,
+                                    sector_index: response.solution.sector_index,
+                                }
+                            })
+                        },
+                    ));
+                    sink.pipe_from_stream(solutions).await;
+                });
+                Ok(())
+            },
+        )?;
+
+        let mut methods = RpcModule::new(());
+        methods.merge(module)?;
+        Ok(methods)
+    }
+}
+
+/// Serves the RPC methods on the given address, until the returned handle is
+/// stopped
+pub(crate) async fn start_server(
+    address: SocketAddr,
+    module: RpcModule<()>,
+) -> anyhow::Result<ServerHandle> {
+    let server = ServerBuilder::default()
+        .build(address)
+        .await
+        .with_context(|| format!("Failed to bind the farmer RPC server to `{address}`"))?;
+    tracing::info!(address = %server.local_addr()?, "Farmer RPC server is listening");
+    server.start(module).context("Failed to start the farmer RPC server")
+}
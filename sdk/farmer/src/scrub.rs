@@ -0,0 +1,265 @@
+//! Offline integrity check of the farm, see [`FarmDescription::scrub`]
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+use anyhow::{anyhow, Context};
+use derive_more::Display;
+use parity_scale_codec::{Decode, Encode, IoReader};
+use subspace_core_primitives::{HistorySize, Record, SectorIndex, SegmentIndex};
+use subspace_farmer::single_disk_farm::SingleDiskFarmInfo;
+use subspace_farmer::{Identity, KNOWN_PEERS_CACHE_SIZE};
+use subspace_farmer_components::sector::{sector_size, SectorMetadata, SectorMetadataChecksummed};
+use subspace_networking::KnownPeersManager;
+use tracing::{info, warn};
+
+use crate::layout::{
+    PlotMetadataHeader, CHECKSUM_SIZE, KNOWN_PEERS_FILE, METADATA_FILE, PIECE_CACHE_ELEMENT_SIZE,
+    PIECE_CACHE_FILE, PLOT_FILE, RESERVED_PLOT_METADATA,
+};
+use crate::FarmDescription;
+
+/// Size of the reads while hashing sectors
+const READ_CHUNK_SIZE: usize = 1024 * 1024;
+
+/// Problem found by [`FarmDescription::scrub`]
+#[derive(Debug, Display, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ScrubIssue {
+    /// Identity of the farm is missing or can't be read, it can't be repaired
+    #[display(fmt = "Identity is missing or corrupted: {_0}")]
+    Identity(String),
+    /// Known peers cache has a wrong size, it is rebuilt on repair
+    #[display(fmt = "Known peers cache is corrupted")]
+    KnownPeers,
+    /// Checksum of the sector metadata doesn't match
+    #[display(fmt = "Metadata of sector {_0} is corrupted")]
+    SectorMetadata(SectorIndex),
+    /// Checksum of the plotted sector doesn't match
+    #[display(fmt = "Sector {_0} is corrupted")]
+    Sector(SectorIndex),
+    /// Sector is counted as plotted, but it isn't on the disk
+    #[display(fmt = "Sector {_0} is missing")]
+    MissingSector(SectorIndex),
+    /// Checksum of the piece cache element doesn't match
+    #[display(fmt = "Piece cache element {_0} is corrupted")]
+    PieceCacheElement(u64),
+}
+
+/// Result of [`FarmDescription::scrub`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ScrubReport {
+    /// Number of plotted sectors which were checked
+    pub plotted_sectors: u64,
+    /// Number of piece cache elements which were checked
+    pub piece_cache_elements: u64,
+    /// Problems found in the farm
+    pub issues: Vec<ScrubIssue>,
+    /// Whether the issues were repaired. Corrupted and missing sectors are
+    /// replotted once the farm is farmed again.
+    pub repaired: bool,
+}
+
+impl ScrubReport {
+    /// Whether no problems were found
+    pub fn is_healthy(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+impl FarmDescription {
+    /// Checks the identity, the sectors and the piece cache of the farm
+    /// against their checksums. The farm should not be farmed meanwhile.
+    ///
+    /// With `repair`, corrupted sectors are marked as expired and missing
+    /// ones are marked as not plotted, so that they are replotted, and
+    /// corrupted piece cache elements are cleared.
+    pub async fn scrub(&self, repair: bool) -> anyhow::Result<ScrubReport> {
+        let directory = self.directory.clone();
+        sdk_utils::task_spawn_blocking("sdk-farmer-scrub", move || scrub(&directory, repair))
+            .await
+            .context("Scrubbing task panicked")?
+    }
+}
+
+fn scrub(directory: &Path, repair: bool) -> anyhow::Result<ScrubReport> {
+    let info = SingleDiskFarmInfo::load_from(directory)
+        .with_context(|| format!("Failed to read farm info at `{directory:?}'"))?
+        .ok_or_else(|| anyhow!("Didn't found farm at `{directory:?}'"))?;
+    let mut report = ScrubReport { repaired: repair, ..Default::default() };
+
+    match Identity::open(directory) {
+        Ok(Some(_)) => {}
+        Ok(None) => report.issues.push(ScrubIssue::Identity("identity file is missing".into())),
+        Err(error) => report.issues.push(ScrubIssue::Identity(error.to_string())),
+    }
+
+    let known_peers = directory.join(KNOWN_PEERS_FILE);
+    if let Ok(metadata) = fs::metadata(&known_peers) {
+        let expected_size = KnownPeersManager::file_size(KNOWN_PEERS_CACHE_SIZE) as u64;
+        if metadata.len() != expected_size {
+            report.issues.push(ScrubIssue::KnownPeers);
+            if repair {
+                fs::remove_file(&known_peers).context("Failed to remove known peers cache")?;
+            }
+        }
+    }
+
+    scrub_sectors(directory, info.pieces_in_sector(), repair, &mut report)?;
+    scrub_piece_cache(directory, repair, &mut report)?;
+
+    if report.is_healthy() {
+        info!(?directory, "Farm is healthy");
+    } else {
+        warn!(?directory, issues = report.issues.len(), repair, "Farm has issues");
+    }
+    Ok(report)
+}
+
+fn scrub_sectors(
+    directory: &Path,
+    pieces_in_sector: u16,
+    repair: bool,
+    report: &mut ScrubReport,
+) -> anyhow::Result<()> {
+    let mut metadata_file = OpenOptions::new()
+        .read(true)
+        .write(repair)
+        .open(directory.join(METADATA_FILE))
+        .context("Failed to open sector metadata")?;
+    let mut header = PlotMetadataHeader::decode(&mut IoReader(&mut metadata_file))
+        .map_err(|error| anyhow!("Failed to decode sector metadata header: {error}"))?;
+    let mut plot_file = File::open(directory.join(PLOT_FILE)).context("Failed to open plot")?;
+
+    let sector_size = sector_size(pieces_in_sector) as u64;
+    let sector_metadata_size = SectorMetadataChecksummed::encoded_size() as u64;
+    let metadata_file_size = metadata_file.metadata()?.len();
+    let plot_file_size = plot_file.metadata()?.len();
+
+    let mut present_sectors = header.plotted_sector_count;
+    for sector_index in 0..header.plotted_sector_count {
+        let metadata_offset =
+            RESERVED_PLOT_METADATA + u64::from(sector_index) * sector_metadata_size;
+        let sector_offset = u64::from(sector_index) * sector_size;
+        if metadata_offset + sector_metadata_size > metadata_file_size
+            || sector_offset + sector_size > plot_file_size
+        {
+            // Sectors are plotted in order, so the rest is missing too
+            report
+                .issues
+                .extend((sector_index..header.plotted_sector_count).map(ScrubIssue::MissingSector));
+            present_sectors = sector_index;
+            break;
+        }
+        report.plotted_sectors += 1;
+
+        metadata_file.seek(SeekFrom::Start(metadata_offset))?;
+        let metadata_is_valid =
+            SectorMetadataChecksummed::decode(&mut IoReader(&mut metadata_file)).is_ok();
+        let sector_is_valid = sector_checksum_matches(&mut plot_file, sector_offset, sector_size)
+            .with_context(|| format!("Failed to read sector {sector_index}"))?;
+        if metadata_is_valid && sector_is_valid {
+            continue;
+        }
+        report.issues.push(if metadata_is_valid {
+            ScrubIssue::Sector(sector_index)
+        } else {
+            ScrubIssue::SectorMetadata(sector_index)
+        });
+
+        if repair {
+            // Expired sectors are replotted by the farm
+            let expired_metadata = SectorMetadataChecksummed::from(SectorMetadata {
+                sector_index,
+                pieces_in_sector,
+                s_bucket_sizes: Box::new([0; Record::NUM_S_BUCKETS]),
+                history_size: HistorySize::from(SegmentIndex::ZERO),
+            });
+            metadata_file.seek(SeekFrom::Start(metadata_offset))?;
+            metadata_file
+                .write_all(&expired_metadata.encode())
+                .context("Failed to mark the sector as expired")?;
+        }
+    }
+
+    if repair && present_sectors != header.plotted_sector_count {
+        header.plotted_sector_count = present_sectors;
+        metadata_file.seek(SeekFrom::Start(0))?;
+        metadata_file
+            .write_all(&header.encode())
+            .context("Failed to update the number of plotted sectors")?;
+    }
+    if repair {
+        metadata_file.sync_all()?;
+    }
+
+    Ok(())
+}
+
+/// Sectors end with the checksum of the rest of the sector
+fn sector_checksum_matches(plot_file: &mut File, offset: u64, size: u64) -> io::Result<bool> {
+    plot_file.seek(SeekFrom::Start(offset))?;
+    let mut hasher = blake3::Hasher::new();
+    let mut remaining = size - CHECKSUM_SIZE as u64;
+    let mut buffer = vec![0; READ_CHUNK_SIZE];
+    while remaining > 0 {
+        let chunk = &mut buffer[..remaining.min(READ_CHUNK_SIZE as u64) as usize];
+        plot_file.read_exact(chunk)?;
+        hasher.update(chunk);
+        remaining -= chunk.len() as u64;
+    }
+    let mut checksum = [0; CHECKSUM_SIZE];
+    plot_file.read_exact(&mut checksum)?;
+
+    Ok(*hasher.finalize().as_bytes() == checksum)
+}
+
+fn scrub_piece_cache(
+    directory: &Path,
+    repair: bool,
+    report: &mut ScrubReport,
+) -> anyhow::Result<()> {
+    let mut piece_cache_file =
+        match OpenOptions::new().read(true).write(repair).open(directory.join(PIECE_CACHE_FILE)) {
+            Ok(file) => file,
+            // Farm without piece cache
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(error) => return Err(error).context("Failed to open piece cache"),
+        };
+
+    let element_size = PIECE_CACHE_ELEMENT_SIZE;
+    let elements = piece_cache_file.metadata()?.len() / element_size as u64;
+    let mut element = vec![0; element_size];
+    for offset in 0..elements {
+        piece_cache_file.seek(SeekFrom::Start(offset * element_size as u64))?;
+        piece_cache_file
+            .read_exact(&mut element)
+            .with_context(|| format!("Failed to read piece cache element {offset}"))?;
+        report.piece_cache_elements += 1;
+
+        // Empty elements are zeroed
+        if element.iter().all(|&byte| byte == 0) {
+            continue;
+        }
+        let (data, checksum) = element.split_at(element_size - CHECKSUM_SIZE);
+        if blake3::hash(data).as_bytes() == checksum {
+            continue;
+        }
+
+        report.issues.push(ScrubIssue::PieceCacheElement(offset));
+        if repair {
+            // Cleared elements are filled again by the piece cache
+            piece_cache_file.seek(SeekFrom::Start(offset * element_size as u64))?;
+            piece_cache_file
+                .write_all(&vec![0; element_size])
+                .context("Failed to clear the piece cache element")?;
+        }
+    }
+    if repair {
+        piece_cache_file.sync_all()?;
+    }
+
+    Ok(())
+}
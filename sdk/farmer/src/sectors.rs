@@ -0,0 +1,201 @@
+//! Updates and expiration of the sectors of the farm, see
+//! [`crate::Farm::subscribe_sector_updates`] and
+//! [`crate::Farm::sector_expiry_report`]
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use subspace_core_primitives::{SectorIndex, SegmentIndex};
+use subspace_farmer::single_disk_farm::{
+    SectorExpirationDetails, SectorPlottingDetails, SectorUpdate,
+};
+
+/// Update of a sector of the farm
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SectorEvent {
+    /// Index of the sector in the farm
+    pub sector_index: SectorIndex,
+    /// What happened to the sector
+    pub kind: SectorEventKind,
+}
+
+/// What happened to a sector, see [`SectorEvent`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SectorEventKind {
+    /// Sector is plotted for the first time
+    PlottingStarted,
+    /// Sector is replotted, since it expired or it is about to
+    ReplottingStarted,
+    /// Pieces of the sector are downloaded
+    Downloaded {
+        /// Time it took
+        time: Duration,
+    },
+    /// Sector is encoded
+    Encoded {
+        /// Time it took
+        time: Duration,
+    },
+    /// Sector is written to the disk
+    Written {
+        /// Time it took
+        time: Duration,
+    },
+    /// Sector is plotted
+    Finished {
+        /// Whether the sector replaced an expired one
+        replotted: bool,
+        /// Time it took to plot the sector
+        time: Duration,
+    },
+    /// Segment at which the sector expires became known
+    Expiring {
+        /// Segment at which the sector expires
+        expires_at: SegmentIndex,
+    },
+    /// Sector expires with the next segment, and it is replotted soon
+    AboutToExpire,
+    /// Sector is expired, and it isn't farmed until it is replotted
+    Expired,
+}
+
+impl SectorEvent {
+    /// Converts the update of the farm, the updates of the plotting steps
+    /// which are in progress are skipped
+    pub(crate) fn new(sector_index: SectorIndex, update: &SectorUpdate) -> Option<Self> {
+        let kind = match update {
+            SectorUpdate::Plotting(details) => match details {
+                SectorPlottingDetails::Starting { replotting: true, .. } =>
+                    SectorEventKind::ReplottingStarted,
+                SectorPlottingDetails::Starting { replotting: false, .. } =>
+                    SectorEventKind::PlottingStarted,
+                SectorPlottingDetails::Downloading
+                | SectorPlottingDetails::Encoding
+                | SectorPlottingDetails::Writing => return None,
+                SectorPlottingDetails::Downloaded(time) =>
+                    SectorEventKind::Downloaded { time: *time },
+                SectorPlottingDetails::Encoded(time) => SectorEventKind::Encoded { time: *time },
+                SectorPlottingDetails::Written(time) => SectorEventKind::Written { time: *time },
+                SectorPlottingDetails::Finished { old_plotted_sector, time, .. } =>
+                    SectorEventKind::Finished {
+                        replotted: old_plotted_sector.is_some(),
+                        time: *time,
+                    },
+            },
+            SectorUpdate::Expiration(details) => match details {
+                SectorExpirationDetails::Determined { expires_at } =>
+                    SectorEventKind::Expiring { expires_at: *expires_at },
+                SectorExpirationDetails::AboutToExpire => SectorEventKind::AboutToExpire,
+                SectorExpirationDetails::Expired => SectorEventKind::Expired,
+            },
+        };
+
+        Some(Self { sector_index, kind })
+    }
+}
+
+/// Item of [`crate::Farm::subscribe_sector_updates`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SectorUpdates {
+    /// Update of a sector of the farm
+    Event(SectorEvent),
+    /// Subscriber lagged too far behind, so the updates since the previous
+    /// item are skipped. Current expiration of the sectors whose expiration
+    /// is known replaces them, as [`SectorEventKind::Expiring`],
+    /// [`SectorEventKind::AboutToExpire`] and [`SectorEventKind::Expired`]
+    /// events.
+    Resynced(Vec<SectorEvent>),
+}
+
+/// Expiration of the sectors of the farm, returned by
+/// [`crate::Farm::sector_expiry_report`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SectorExpiryReport {
+    /// Last archived segment, which the expirations are relative to
+    pub current_segment_index: SegmentIndex,
+    /// Number of sectors which are expired, and wait for replotting
+    pub expired: u64,
+    /// Number of sectors which expire within the asked number of segments
+    pub expiring: u64,
+    /// Number of sectors which expire later
+    pub expiring_later: u64,
+}
+
+/// Expiration of a single sector
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SectorExpiration {
+    ExpiresAt(SegmentIndex),
+    AboutToExpire,
+    Expired,
+}
+
+/// Expirations of the sectors of the farm, as they are reported by the farm.
+/// Sectors whose expiration isn't known yet are not tracked.
+#[derive(Debug, Default)]
+pub(crate) struct SectorExpirations {
+    sectors: HashMap<SectorIndex, SectorExpiration>,
+}
+
+impl SectorExpirations {
+    pub(crate) fn update(&mut self, SectorEvent { sector_index, kind }: SectorEvent) {
+        let expiration = match kind {
+            SectorEventKind::Expiring { expires_at } => SectorExpiration::ExpiresAt(expires_at),
+            SectorEventKind::AboutToExpire => SectorExpiration::AboutToExpire,
+            SectorEventKind::Expired => SectorExpiration::Expired,
+            // Expiration of the replotted sector is determined again
+            SectorEventKind::Finished { .. } => {
+                self.sectors.remove(&sector_index);
+                return;
+            }
+            _ => return,
+        };
+        self.sectors.insert(sector_index, expiration);
+    }
+
+    /// Current expiration of the sectors, as the events which lead to it
+    pub(crate) fn events(&self) -> Vec<SectorEvent> {
+        self.sectors
+            .iter()
+            .map(|(&sector_index, expiration)| SectorEvent {
+                sector_index,
+                kind: match *expiration {
+                    SectorExpiration::ExpiresAt(expires_at) =>
+                        SectorEventKind::Expiring { expires_at },
+                    SectorExpiration::AboutToExpire => SectorEventKind::AboutToExpire,
+                    SectorExpiration::Expired => SectorEventKind::Expired,
+                },
+            })
+            .collect()
+    }
+
+    pub(crate) fn report(
+        &self,
+        current_segment_index: SegmentIndex,
+        within_segments: u64,
+    ) -> SectorExpiryReport {
+        let mut report = SectorExpiryReport {
+            current_segment_index,
+            expired: 0,
+            expiring: 0,
+            expiring_later: 0,
+        };
+        let expiring_until =
+            SegmentIndex::from(u64::from(current_segment_index).saturating_add(within_segments));
+        for expiration in self.sectors.values() {
+            match expiration {
+                SectorExpiration::Expired => report.expired += 1,
+                SectorExpiration::ExpiresAt(expires_at) if *expires_at <= current_segment_index =>
+                    report.expired += 1,
+                SectorExpiration::AboutToExpire => report.expiring += 1,
+                SectorExpiration::ExpiresAt(expires_at) if *expires_at <= expiring_until =>
+                    report.expiring += 1,
+                SectorExpiration::ExpiresAt(_) => report.expiring_later += 1,
+            }
+        }
+        report
+    }
+}
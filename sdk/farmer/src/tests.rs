@@ -0,0 +1,502 @@
+#![allow(clippy::unwrap_used)]
+
+use std::fs;
+use std::time::Duration;
+
+use parity_scale_codec::Encode;
+use subspace_core_primitives::SegmentIndex;
+use subspace_farmer_components::sector::SectorMetadata;
+
+use super::*;
+use crate::layout::{
+    self, PlotMetadataHeader, CHECKSUM_SIZE, METADATA_FILE, PIECE_CACHE_ELEMENT_SIZE,
+    PIECE_CACHE_FILE, PLOT_FILE, RESERVED_PLOT_METADATA,
+};
+
+const PIECES_IN_SECTOR: u16 = 1;
+const GENESIS_HASH: [u8; 32] = [1; 32];
+
+/// Farm files in a temporary directory, which is removed once it is dropped
+struct TestFarm {
+    directory: PathBuf,
+}
+
+impl TestFarm {
+    fn new(name: &str, allocated_space: ByteSize) -> Self {
+        let directory =
+            std::env::temp_dir().join(format!("sdk-farmer-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&directory);
+        fs::create_dir_all(&directory).unwrap();
+
+        let identity = Identity::open_or_create(&directory).unwrap();
+        SingleDiskFarmInfo::new(
+            SingleDiskFarmId::new(),
+            GENESIS_HASH,
+            subspace_core_primitives::PublicKey::from(identity.public_key().to_bytes()),
+            PIECES_IN_SECTOR,
+            allocated_space.as_u64(),
+        )
+        .store_to(&directory)
+        .unwrap();
+
+        Self { directory }
+    }
+
+    /// Writes the metadata and the plot of `sector_count` sectors, with valid
+    /// checksums
+    fn plot(&self, sector_count: SectorIndex) {
+        let mut metadata =
+            PlotMetadataHeader { version: 0, plotted_sector_count: sector_count }.encode();
+        metadata.resize(RESERVED_PLOT_METADATA as usize, 0);
+        let mut plot = Vec::new();
+        for sector_index in 0..sector_count {
+            let sector_metadata = SectorMetadataChecksummed::from(SectorMetadata {
+                sector_index,
+                pieces_in_sector: PIECES_IN_SECTOR,
+                s_bucket_sizes: Box::new([1; Record::NUM_S_BUCKETS]),
+                history_size: HistorySize::from(SegmentIndex::ONE),
+            });
+            metadata.extend(sector_metadata.encode());
+
+            let mut sector =
+                vec![sector_index as u8; sector_size(PIECES_IN_SECTOR) - CHECKSUM_SIZE];
+            let checksum = blake3::hash(&sector);
+            sector.extend_from_slice(checksum.as_bytes());
+            plot.extend(sector);
+        }
+        fs::write(self.path(METADATA_FILE), metadata).unwrap();
+        fs::write(self.path(PLOT_FILE), plot).unwrap();
+    }
+
+    /// Writes a piece cache of `elements` elements with valid checksums, and an
+    /// empty one after them
+    fn cache_pieces(&self, elements: u64) {
+        let mut piece_cache = Vec::new();
+        for offset in 0..elements {
+            let mut element = vec![offset as u8 + 1; PIECE_CACHE_ELEMENT_SIZE - CHECKSUM_SIZE];
+            let checksum = blake3::hash(&element);
+            element.extend_from_slice(checksum.as_bytes());
+            piece_cache.extend(element);
+        }
+        piece_cache.resize(piece_cache.len() + PIECE_CACHE_ELEMENT_SIZE, 0);
+        fs::write(self.path(PIECE_CACHE_FILE), piece_cache).unwrap();
+    }
+
+    /// Flips a byte of the file at `offset`
+    fn corrupt(&self, file: &str, offset: u64) {
+        let mut contents = fs::read(self.path(file)).unwrap();
+        contents[offset as usize] ^= 0xff;
+        fs::write(self.path(file), contents).unwrap();
+    }
+
+    fn path(&self, file: &str) -> PathBuf {
+        self.directory.join(file)
+    }
+
+    fn file_size(&self, file: &str) -> u64 {
+        fs::metadata(self.path(file)).unwrap().len()
+    }
+
+    fn description(&self, space_pledged: ByteSize) -> FarmDescription {
+        FarmDescription::new(&self.directory, space_pledged)
+    }
+
+    fn allocated_space(&self) -> u64 {
+        SingleDiskFarmInfo::load_from(&self.directory).unwrap().unwrap().allocated_space()
+    }
+}
+
+impl Drop for TestFarm {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.directory);
+    }
+}
+
+#[test]
+fn plan_resize() {
+    let farm = TestFarm::new("plan-resize", ByteSize::mib(64));
+    let description = farm.description(ByteSize::mib(64));
+    let cache_percentage = NonZeroU8::MIN;
+    let sectors =
+        |space: ByteSize| target_sector_count(space.as_u64(), cache_percentage, PIECES_IN_SECTOR);
+
+    let growing = description.plan_resize(ByteSize::mib(128), PieceCacheSize::default()).unwrap();
+    assert_eq!(growing.current_space, ByteSize::mib(64));
+    assert_eq!(growing.current_sectors, sectors(ByteSize::mib(64)));
+    assert_eq!(growing.new_sectors, sectors(ByteSize::mib(128)));
+    assert!(!growing.is_shrinking());
+    assert_eq!(growing.dropped_sectors(), 0);
+
+    let shrinking = description.plan_resize(ByteSize::mib(16), PieceCacheSize::default()).unwrap();
+    assert!(shrinking.is_shrinking());
+    assert_eq!(
+        shrinking.dropped_sectors(),
+        sectors(ByteSize::mib(64)) - sectors(ByteSize::mib(16))
+    );
+
+    // Planning doesn't change the farm
+    assert_eq!(farm.allocated_space(), ByteSize::mib(64).as_u64());
+
+    assert!(description.plan_resize(ByteSize::kib(1), PieceCacheSize::default()).is_err());
+    let piece_cache_size = PieceCacheSize::Size(ByteSize::mib(32));
+    assert!(description.plan_resize(ByteSize::mib(16), piece_cache_size).is_err());
+}
+
+#[test]
+fn resize() {
+    const PLOTTED_SECTORS: SectorIndex = 20;
+
+    let farm = TestFarm::new("resize", ByteSize::mib(64));
+    farm.plot(PLOTTED_SECTORS);
+    let sector_size = sector_size(PIECES_IN_SECTOR) as u64;
+    let sector_metadata_size = SectorMetadataChecksummed::encoded_size() as u64;
+
+    // Growing keeps the plotted sectors
+    let (description, resize) = farm
+        .description(ByteSize::mib(64))
+        .resize(ByteSize::mib(128), PieceCacheSize::default())
+        .unwrap();
+    assert_eq!(description.space_pledged, ByteSize::mib(128));
+    assert!(!resize.is_shrinking());
+    assert_eq!(farm.allocated_space(), ByteSize::mib(128).as_u64());
+    assert_eq!(
+        layout::read_sectors_metadata(&farm.directory).unwrap().len(),
+        usize::from(PLOTTED_SECTORS)
+    );
+    assert_eq!(farm.file_size(PLOT_FILE), u64::from(PLOTTED_SECTORS) * sector_size);
+
+    // Shrinking drops the sectors which don't fit
+    let (description, resize) =
+        description.resize(ByteSize::mib(16), PieceCacheSize::default()).unwrap();
+    assert_eq!(description.space_pledged, ByteSize::mib(16));
+    assert!(resize.is_shrinking());
+    let new_sectors = resize.new_sectors;
+    assert!(new_sectors < u64::from(PLOTTED_SECTORS));
+    assert_eq!(farm.allocated_space(), ByteSize::mib(16).as_u64());
+    assert_eq!(u64::from(layout::plotted_sector_count(&farm.directory).unwrap()), new_sectors);
+    let sectors_metadata = layout::read_sectors_metadata(&farm.directory).unwrap();
+    assert_eq!(sectors_metadata.len() as u64, new_sectors);
+    assert!(sectors_metadata
+        .iter()
+        .zip(0..)
+        .all(|(metadata, sector_index)| metadata.sector_index == sector_index));
+    assert_eq!(
+        farm.file_size(METADATA_FILE),
+        RESERVED_PLOT_METADATA + new_sectors * sector_metadata_size
+    );
+    assert_eq!(farm.file_size(PLOT_FILE), new_sectors * sector_size);
+
+    assert!(description.resize(ByteSize::kib(1), PieceCacheSize::default()).is_err());
+    assert_eq!(farm.allocated_space(), ByteSize::mib(16).as_u64());
+}
+
+#[tokio::test]
+async fn scrub() {
+    let farm = TestFarm::new("scrub", ByteSize::mib(64));
+    farm.plot(3);
+    farm.cache_pieces(2);
+    let description = farm.description(ByteSize::mib(64));
+    let sector_size = sector_size(PIECES_IN_SECTOR) as u64;
+    let element_size = PIECE_CACHE_ELEMENT_SIZE as u64;
+
+    let report = description.scrub(false).await.unwrap();
+    assert!(report.is_healthy(), "{report:?}");
+    assert_eq!(report.plotted_sectors, 3);
+    assert_eq!(report.piece_cache_elements, 3);
+
+    let piece_cache = fs::read(farm.path(PIECE_CACHE_FILE)).unwrap();
+    farm.corrupt(PLOT_FILE, sector_size + 10);
+    farm.corrupt(PIECE_CACHE_FILE, element_size + 10);
+    let issues = vec![ScrubIssue::Sector(1), ScrubIssue::PieceCacheElement(1)];
+
+    // Issues are only reported without repair
+    let plot = fs::read(farm.path(PLOT_FILE)).unwrap();
+    let metadata = fs::read(farm.path(METADATA_FILE)).unwrap();
+    let report = description.scrub(false).await.unwrap();
+    assert_eq!(report.issues, issues);
+    assert!(!report.repaired);
+    assert_eq!(fs::read(farm.path(PLOT_FILE)).unwrap(), plot);
+    assert_eq!(fs::read(farm.path(METADATA_FILE)).unwrap(), metadata);
+
+    let report = description.scrub(true).await.unwrap();
+    assert_eq!(report.issues, issues);
+    assert!(report.repaired);
+
+    // Corrupted sector is expired, so that it is replotted
+    let sectors_metadata = layout::read_sectors_metadata(&farm.directory).unwrap();
+    assert_eq!(sectors_metadata.len(), 3);
+    assert_eq!(sectors_metadata[1].history_size, HistorySize::from(SegmentIndex::ZERO));
+    assert!(sectors_metadata[1].s_bucket_sizes.iter().all(|&size| size == 0));
+    for sector_index in [0, 2] {
+        assert_eq!(
+            sectors_metadata[sector_index].history_size,
+            HistorySize::from(SegmentIndex::ONE)
+        );
+    }
+
+    // Corrupted piece cache element is cleared, the others are kept
+    let repaired_piece_cache = fs::read(farm.path(PIECE_CACHE_FILE)).unwrap();
+    let elements = repaired_piece_cache.chunks(PIECE_CACHE_ELEMENT_SIZE).collect::<Vec<_>>();
+    assert_eq!(elements[0], &piece_cache[..PIECE_CACHE_ELEMENT_SIZE]);
+    assert!(elements[1].iter().all(|&byte| byte == 0));
+    assert!(elements[2].iter().all(|&byte| byte == 0));
+
+    // Sector stays corrupted in the plot until it is replotted
+    let report = description.scrub(false).await.unwrap();
+    assert_eq!(report.issues, vec![ScrubIssue::Sector(1)]);
+
+    // Sectors missing from the plot are marked as not plotted
+    let plot_file = fs::OpenOptions::new().write(true).open(farm.path(PLOT_FILE)).unwrap();
+    plot_file.set_len(2 * sector_size).unwrap();
+    drop(plot_file);
+    let report = description.scrub(true).await.unwrap();
+    assert_eq!(report.issues, vec![ScrubIssue::Sector(1), ScrubIssue::MissingSector(2)]);
+    assert_eq!(report.plotted_sectors, 2);
+    assert_eq!(layout::plotted_sector_count(&farm.directory).unwrap(), 2);
+}
+
+#[test]
+fn inspect_farm() {
+    let farm = TestFarm::new("inspect", ByteSize::mib(64));
+
+    // Farm which was never farmed has no metadata yet
+    let inspection = inspect(&farm.directory).unwrap();
+    assert_eq!(inspection.directory, farm.directory);
+    assert_eq!(inspection.plotted_sectors, 0);
+    assert_eq!(inspection.piece_cache_capacity, 0);
+    assert_eq!(inspection.info.allocated_space, ByteSize::mib(64));
+    assert_eq!(inspection.info.pieces_in_sector, PIECES_IN_SECTOR);
+    assert_eq!(inspection.info.genesis_hash, GENESIS_HASH);
+
+    farm.plot(3);
+    farm.cache_pieces(4);
+    let inspection = inspect(&farm.directory).unwrap();
+    assert_eq!(inspection.plotted_sectors, 3);
+    // Empty element after the cached pieces is a part of the cache too
+    assert_eq!(inspection.piece_cache_capacity, 5);
+    assert_eq!(inspection.info.piece_cache_size, ByteSize::b(5 * PIECE_CACHE_ELEMENT_SIZE as u64));
+
+    fs::remove_dir_all(&farm.directory).unwrap();
+    assert!(inspect(&farm.directory).is_err());
+}
+
+#[test]
+fn check_existing_farm() {
+    let farm = TestFarm::new("check-existing-farm", ByteSize::mib(64));
+    let check = |genesis_hash| super::check_existing_farm(&farm.directory, genesis_hash);
+    check(GENESIS_HASH).unwrap();
+
+    let node_genesis_hash = [2; 32];
+    assert!(matches!(
+        check(node_genesis_hash),
+        Err(BuildError::ChainMismatch { farm_genesis_hash, node_genesis_hash: node, .. })
+            if farm_genesis_hash == GENESIS_HASH && node == node_genesis_hash
+    ));
+
+    // Farm info of another farm, next to the identity of this one
+    let identity = Identity::open(&farm.directory).unwrap().unwrap();
+    let identity_public_key =
+        subspace_core_primitives::PublicKey::from(identity.public_key().to_bytes());
+    let farm_public_key = subspace_core_primitives::PublicKey::from([3; 32]);
+    SingleDiskFarmInfo::new(
+        SingleDiskFarmId::new(),
+        GENESIS_HASH,
+        farm_public_key,
+        PIECES_IN_SECTOR,
+        ByteSize::mib(64).as_u64(),
+    )
+    .store_to(&farm.directory)
+    .unwrap();
+    assert!(matches!(
+        check(GENESIS_HASH),
+        Err(BuildError::IdentityMismatch {
+            farm_public_key: farm_key,
+            identity_public_key: identity_key,
+            ..
+        }) if farm_key == PublicKey(farm_public_key)
+            && identity_key == PublicKey(identity_public_key)
+    ));
+
+    // Farms which don't exist yet are created
+    super::check_existing_farm(&farm.directory.join("new"), [2; 32]).unwrap();
+}
+
+/// Lets the spawned tasks, like the throttle of the plotting controller, run
+async fn settle() {
+    for _ in 0..10 {
+        tokio::task::yield_now().await;
+    }
+}
+
+#[tokio::test]
+async fn plotting_controller() {
+    let concurrency = |concurrency| NonZeroUsize::new(concurrency).unwrap();
+    let downloading_semaphore = Arc::new(Semaphore::new(4));
+    let core_indices =
+        || thread_pool_core_indices(Some(NonZeroUsize::MIN), Some(NonZeroUsize::MIN));
+    let plotting_thread_pool_manager =
+        create_plotting_thread_pool_manager(core_indices().into_iter().zip(core_indices()))
+            .unwrap();
+    let thread_pool_is_free =
+        || plotting_thread_pool_manager.get_thread_pools().now_or_never().is_some();
+    let controller = PlottingController::new(
+        Arc::clone(&downloading_semaphore),
+        plotting_thread_pool_manager.clone(),
+        concurrency(4),
+        NonZeroUsize::MIN,
+    );
+
+    // Lower concurrency holds the rest of the permits
+    controller.set_sector_downloading_concurrency(concurrency(2));
+    settle().await;
+    assert_eq!(downloading_semaphore.available_permits(), 2);
+    assert!(thread_pool_is_free());
+
+    // Pause holds all the permits and the thread pools
+    controller.pause();
+    settle().await;
+    assert!(controller.status().paused);
+    assert_eq!(downloading_semaphore.available_permits(), 0);
+    assert!(!thread_pool_is_free());
+
+    // Resume gives them back, and higher concurrency adds permits
+    controller.resume();
+    controller.set_sector_downloading_concurrency(concurrency(6));
+    settle().await;
+    assert_eq!(downloading_semaphore.available_permits(), 6);
+    assert!(thread_pool_is_free());
+
+    // Encoding concurrency is capped at the number of thread pools
+    controller.set_sector_encoding_concurrency(concurrency(3));
+    assert_eq!(
+        controller.status(),
+        PlottingStatus {
+            paused: false,
+            sector_downloading_concurrency: concurrency(6),
+            sector_encoding_concurrency: NonZeroUsize::MIN,
+            max_sector_encoding_concurrency: NonZeroUsize::MIN,
+        }
+    );
+
+    // Closed controller gives everything back, and ignores later changes
+    controller.pause();
+    settle().await;
+    assert_eq!(downloading_semaphore.available_permits(), 0);
+    controller.close();
+    settle().await;
+    assert_eq!(downloading_semaphore.available_permits(), 6);
+    controller.pause();
+    settle().await;
+    assert_eq!(downloading_semaphore.available_permits(), 6);
+    assert!(thread_pool_is_free());
+}
+
+#[test]
+fn sector_expiry_report() {
+    let mut expirations = sectors::SectorExpirations::default();
+    let event = |sector_index, kind| SectorEvent { sector_index, kind };
+    let expires_at =
+        |segment_index| SectorEventKind::Expiring { expires_at: SegmentIndex::from(segment_index) };
+    let finished = SectorEventKind::Finished { replotted: true, time: Default::default() };
+
+    expirations.update(event(0, SectorEventKind::Expired));
+    expirations.update(event(1, expires_at(90)));
+    expirations.update(event(2, SectorEventKind::AboutToExpire));
+    expirations.update(event(3, expires_at(100)));
+    expirations.update(event(4, expires_at(110)));
+    expirations.update(event(5, expires_at(111)));
+    expirations.update(event(6, expires_at(200)));
+    // Replotted sector isn't tracked until its expiration is determined again
+    expirations.update(event(7, SectorEventKind::Expired));
+    expirations.update(event(7, finished));
+    // Events which don't change the expiration are ignored
+    expirations.update(event(8, SectorEventKind::PlottingStarted));
+
+    let current_segment_index = SegmentIndex::from(100);
+    assert_eq!(
+        expirations.report(current_segment_index, 10),
+        SectorExpiryReport { current_segment_index, expired: 3, expiring: 2, expiring_later: 2 }
+    );
+
+    // Sectors which expire later are counted as expiring, without overflowing
+    let report = expirations.report(current_segment_index, u64::MAX);
+    assert_eq!((report.expired, report.expiring, report.expiring_later), (3, 4, 0));
+
+    // Lagging subscribers resync with the same expirations
+    let mut resynced = sectors::SectorExpirations::default();
+    expirations.events().into_iter().for_each(|event| resynced.update(event));
+    assert_eq!(resynced.events().len(), 7);
+    assert_eq!(
+        resynced.report(current_segment_index, 10),
+        expirations.report(current_segment_index, 10)
+    );
+}
+
+#[test]
+fn initial_plotting_progress() {
+    let finished = |replotted| SectorEventKind::Finished { replotted, time: Default::default() };
+    let mut progress = InitialPlottingProgress::new(7, 10);
+    assert!(!progress.is_finished());
+
+    assert!(progress.update(SectorEventKind::PlottingStarted));
+    assert!(progress.update(SectorEventKind::PlottingStarted));
+    assert!(progress.update(finished(false)));
+    assert_eq!((progress.current_sector, progress.sectors_in_flight), (8, 1));
+
+    // Replotted sectors are not part of the initial plotting
+    assert!(!progress.update(SectorEventKind::ReplottingStarted));
+    assert!(!progress.update(finished(true)));
+    assert!(!progress.update(SectorEventKind::Expired));
+
+    // Sectors in flight are capped at the sectors left to plot
+    assert!(progress.update(SectorEventKind::PlottingStarted));
+    assert!(!progress.update(SectorEventKind::PlottingStarted));
+    assert_eq!(progress.sectors_in_flight, 2);
+    assert!(progress.update(finished(false)));
+    assert!(progress.update(finished(false)));
+    assert!(progress.is_finished());
+
+    // Current sector is clamped at the total sectors
+    assert!(!progress.update(finished(false)));
+    assert_eq!(
+        progress,
+        InitialPlottingProgress {
+            starting_sector: 7,
+            current_sector: 10,
+            sectors_in_flight: 0,
+            total_sectors: 10,
+        }
+    );
+    assert!(InitialPlottingProgress::new(12, 10).is_finished());
+}
+
+#[test]
+fn benchmark_report() {
+    let times = [Duration::from_millis(300), Duration::from_millis(500)];
+
+    let audit = BenchmarkReport::new(BenchmarkKind::Audit, 4, ByteSize::mib(1), 2, &times);
+    assert_eq!(audit.samples, 2);
+    assert_eq!(audit.slot_time, Duration::from_millis(400));
+    // All the sectors are audited every slot
+    assert_eq!(audit.sector_latency, Duration::from_millis(100));
+    assert!((audit.sectors_per_second - 10.0).abs() < 1e-9);
+    assert_eq!(audit.throughput(), ByteSize::mib(10));
+    assert_eq!(audit.deadline_headroom(), Some(Duration::from_millis(600)));
+
+    // Single sector is proven every slot
+    let prove = BenchmarkReport::new(BenchmarkKind::Prove, 4, ByteSize::mib(1), 2, &times);
+    assert_eq!(prove.sector_latency, Duration::from_millis(400));
+    assert!((prove.sectors_per_second - 2.5).abs() < 1e-9);
+
+    let slow = BenchmarkReport::new(
+        BenchmarkKind::Audit,
+        4,
+        ByteSize::mib(1),
+        2,
+        &[SLOT_DURATION + Duration::from_millis(1)],
+    );
+    assert_eq!(slow.deadline_headroom(), None);
+    let on_time =
+        BenchmarkReport::new(BenchmarkKind::Audit, 4, ByteSize::mib(1), 2, &[SLOT_DURATION]);
+    assert_eq!(on_time.deadline_headroom(), Some(Duration::ZERO));
+}
@@ -3,6 +3,7 @@
 use std::collections::BTreeSet;
 use std::marker::PhantomData;
 use std::num::NonZeroU32;
+use std::path::Path;
 
 use hex_literal::hex;
 use parity_scale_codec::Encode;
@@ -72,6 +73,13 @@ struct GenesisDomainParams {
 /// Chain spec type for the subspace
 pub type ChainSpec = GenericChainSpec<RuntimeGenesisConfig>;
 
+/// Loads a raw or plain chain spec from the JSON file at the given path
+pub fn from_json_file(path: impl AsRef<Path>) -> anyhow::Result<ChainSpec> {
+    let path = path.as_ref();
+    ChainSpec::from_json_file(path.to_path_buf())
+        .map_err(|error| anyhow::anyhow!("Failed to load chain spec from {path:?}: {error}"))
+}
+
 /// Gemini 3g chain spec
 pub fn gemini_3h() -> ChainSpec {
     ChainSpec::from_json_bytes(GEMINI_3H_CHAIN_SPEC.as_bytes()).expect("Always valid")
@@ -444,4 +452,17 @@ mod tests {
         devnet_config();
         dev_config();
     }
+
+    #[test]
+    fn test_chain_spec_from_json_file() {
+        let path = std::env::temp_dir().join(format!("chain-spec-{}.json", std::process::id()));
+
+        for raw in [false, true] {
+            std::fs::write(&path, dev_config().as_json(raw).unwrap()).unwrap();
+            assert_eq!(from_json_file(&path).unwrap().id(), dev_config().id());
+        }
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(from_json_file(&path).is_err());
+    }
 }
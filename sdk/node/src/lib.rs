@@ -450,7 +450,6 @@ pub struct Node<F: Farmer> {
 }
 
 impl<F: Farmer> sdk_traits::Node for Node<F> {
-    type Client = FullClient;
     type Rpc = sdk_utils::Rpc;
     type Table = F::Table;
 
@@ -808,6 +807,13 @@ impl<F: Farmer + 'static> Node<F> {
             .map(|opt| opt.map(Into::into))
     }
 
+    /// Time of the block, as it is set by its timestamp inherent, in
+    /// milliseconds since the unix epoch. Slots of the block can't be used
+    /// instead, since they aren't tied to the wall-clock time.
+    pub async fn block_timestamp(&self, hash: Hash) -> anyhow::Result<Option<u64>> {
+        self.rpc_handle.get_timestamp(hash).await
+    }
+
     /// Subscribe to new heads imported
     pub async fn subscribe_new_heads(
         &self,
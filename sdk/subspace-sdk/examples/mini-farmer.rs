@@ -1,4 +1,3 @@
-use std::num::NonZeroU8;
 use std::path::PathBuf;
 
 use anyhow::Context;
@@ -41,6 +40,9 @@ pub struct Args {
     /// Cache size
     #[arg(short, long, default_value_t = ByteSize::gib(1))]
     cache_size: ByteSize,
+    /// Serve node, DSN and farmer metrics on the given port
+    #[arg(long)]
+    prometheus_port: Option<u16>,
 }
 
 #[tokio::main]
@@ -67,7 +69,8 @@ async fn main() -> anyhow::Result<()> {
         reward_address,
         base_path,
         plot_size,
-        cache_size: _,
+        cache_size,
+        prometheus_port,
     } = Args::parse();
     let (base_path, _tmp_dir) = base_path.map(|x| (x, None)).unwrap_or_else(|| {
         let tmp = tempfile::tempdir().expect("Failed to create temporary directory");
@@ -81,6 +84,10 @@ async fn main() -> anyhow::Result<()> {
         Chain::Dev => Node::dev().dsn(subspace_sdk::node::DsnBuilder::dev()),
     }
     .role(node::Role::Authority);
+    let node = match prometheus_port {
+        Some(port) => node.prometheus(node::PrometheusBuilder::new().port(port)),
+        None => node,
+    };
 
     #[cfg(feature = "executor")]
     let node = if executor {
@@ -124,7 +131,7 @@ async fn main() -> anyhow::Result<()> {
             reward_address,
             &node,
             &[FarmDescription::new(base_path.join("plot"), plot_size)],
-            NonZeroU8::new(1).expect("static value should not fail; qed"),
+            cache_size,
         )
         .await?;
 
@@ -18,6 +18,7 @@ use subspace_proof_of_space::chia::ChiaTable;
 static_assertions::assert_impl_all!(Node: Send, Sync);
 static_assertions::assert_impl_all!(Farmer: Send, Sync);
 static_assertions::assert_impl_all!(Farm: Send, Sync);
+static_assertions::assert_impl_all!(RemoteNode: Send, Sync);
 
 /// Subspace farmer type
 pub type Farmer = sdk_farmer::Farmer<ChiaTable>;
@@ -25,12 +26,21 @@ pub type Farmer = sdk_farmer::Farmer<ChiaTable>;
 pub type Farm = sdk_farmer::Farm<ChiaTable>;
 /// Subspace primary node
 pub type Node = sdk_node::Node<Farmer>;
+/// Subspace node reached over RPC, used by farmers without a node
+pub type RemoteNode = sdk_farmer::RemoteNode<ChiaTable>;
 
 /// Farmer related things located here
 pub mod farmer {
-    pub use sdk_farmer::FarmDescription;
+    pub use sdk_farmer::{
+        inspect, rpc, BenchmarkKind, BenchmarkReport, BuildError, FarmDescription, FarmInfo,
+        FarmInspection, FarmResize, InitialPlottingProgress, PieceCacheSize, PlottingController,
+        PlottingStatus, ScrubIssue, ScrubReport, SectorEvent, SectorEventKind, SectorExpiryReport,
+        SectorUpdates, SLOT_DURATION,
+    };
+    /// Node which a farmer can be built with, either in-process or over RPC
+    pub use sdk_traits::Node as FarmerNode;
 
-    pub use super::{Farm, Farmer};
+    pub use super::{Farm, Farmer, RemoteNode};
 }
 
 /// Node related things located here
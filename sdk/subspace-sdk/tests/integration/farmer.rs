@@ -1,4 +1,7 @@
 use futures::prelude::*;
+use jsonrpsee_core::params::ArrayParams;
+use subspace_sdk::farmer::rpc::{RpcFarmerInfo, RpcPlottingProgress, RpcSolution};
+use subspace_sdk::farmer::FarmDescription;
 use subspace_sdk::utils::ByteSize;
 
 use crate::common::{Farmer, Node};
@@ -19,16 +22,20 @@ async fn track_progress() {
         .build(&node, ByteSize::b(space_pledged as u64))
         .await;
 
-    let progress = farmer
-        .iter_farms()
-        .await
-        .next()
-        .unwrap()
-        .subscribe_initial_plotting_progress()
-        .await
-        .collect::<Vec<_>>()
-        .await;
-    assert_eq!(progress.len(), number_of_sectors);
+    let plot = farmer.iter_farms().await.next().unwrap();
+    let (progress, other_progress) = futures::join!(
+        plot.subscribe_initial_plotting_progress().await.collect::<Vec<_>>(),
+        plot.subscribe_initial_plotting_progress().await.collect::<Vec<_>>(),
+    );
+    for progress in [progress, other_progress] {
+        assert!(!progress.is_empty());
+        assert!(progress
+            .windows(2)
+            .all(|window| window[0].current_sector <= window[1].current_sector));
+        assert!(progress.iter().all(|progress| progress.current_sector < number_of_sectors as u64
+            && progress.total_sectors == number_of_sectors as u64));
+    }
+    assert!(plot.subscribe_initial_plotting_progress().await.next().await.is_none());
 
     farmer.close().await;
     node.close().await;
@@ -136,3 +143,92 @@ async fn farmer_close() {
     farmer.close().await;
     node.close().await;
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn rpc_module() {
+    crate::common::setup();
+
+    let number_of_sectors = 10;
+    let pieces_in_sector = 50u16;
+    let sector_size = subspace_farmer_components::sector::sector_size(pieces_in_sector as _);
+    let space_pledged = sector_size * number_of_sectors;
+
+    let node = Node::dev().build(true).await;
+    let mut farmer = Farmer::dev()
+        .pieces_in_sector(pieces_in_sector)
+        .build(&node, ByteSize::b(space_pledged as u64))
+        .await;
+    let other_plot_dir = farmer.path.path().join("other-plot");
+    farmer
+        .add_farm(FarmDescription::new(&other_plot_dir, ByteSize::b(space_pledged as u64)))
+        .await
+        .unwrap();
+
+    let module = farmer.rpc_module().await.unwrap();
+    let info: RpcFarmerInfo = module.call("farmer_info", ArrayParams::new()).await.unwrap();
+    let mut directories = info.farms.iter().map(|farm| farm.directory.clone()).collect::<Vec<_>>();
+    directories.sort();
+    assert_eq!(directories, [other_plot_dir.clone(), farmer.plot_dir()]);
+    assert!(info.farms.iter().all(|farm| farm.total_sectors == number_of_sectors as u64));
+
+    // notifications reference the farms by their index in `farmer_info`
+    let mut progress =
+        module.subscribe("farmer_subscribePlottingProgress", ArrayParams::new()).await.unwrap();
+    let (progress, _) = progress.next::<RpcPlottingProgress>().await.unwrap().unwrap();
+    assert_eq!(info.farms[progress.farm].directory, progress.directory);
+    assert_eq!(progress.total_sectors, number_of_sectors as u64);
+
+    let mut solutions =
+        module.subscribe("farmer_subscribeSolutions", ArrayParams::new()).await.unwrap();
+    let (solution, _) = solutions.next::<RpcSolution>().await.unwrap().unwrap();
+    assert_eq!(info.farms[solution.farm].directory, solution.directory);
+
+    farmer.close().await;
+    node.close().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn add_and_remove_farms() {
+    crate::common::setup();
+
+    let number_of_sectors = 10;
+    let pieces_in_sector = 50u16;
+    let sector_size = subspace_farmer_components::sector::sector_size(pieces_in_sector as _);
+    let space_pledged = sector_size * number_of_sectors;
+
+    let node = Node::dev().build(true).await;
+    let mut farmer = Farmer::dev()
+        .pieces_in_sector(pieces_in_sector)
+        .build(&node, ByteSize::b(space_pledged as u64))
+        .await;
+    let plot_dir = farmer.plot_dir();
+    let other_plot_dir = farmer.path.path().join("other-plot");
+    let other_farm = FarmDescription::new(&other_plot_dir, ByteSize::b(space_pledged as u64));
+
+    // the added farm is plotted, and its pieces are served
+    farmer.add_farm(other_farm.clone()).await.unwrap();
+    let other_plot = farmer.iter_farms().await.find(|farm| farm.directory() == &other_plot_dir);
+    let progress = other_plot.unwrap().subscribe_initial_plotting_progress().await;
+    let progress = progress.collect::<Vec<_>>().await;
+    assert!(progress.iter().any(|progress| progress.current_sector > 0));
+    assert!(farmer.served_piece_count(&other_plot_dir).unwrap() > 0);
+
+    // pieces of the removed farm aren't served, and the other farm is farmed on
+    farmer.remove_farm(&other_plot_dir).await.unwrap();
+    assert_eq!(farmer.served_piece_count(&other_plot_dir), None);
+    assert_eq!(farmer.iter_farms().await.count(), 1);
+    let plot = farmer.iter_farms().await.next().unwrap();
+    plot.subscribe_new_solutions().await.next().await.expect("Farmer should send new solutions");
+
+    // the farm is added again in the freed slot, with its plotted sectors
+    farmer.add_farm(other_farm).await.unwrap();
+    assert!(farmer.served_piece_count(&other_plot_dir).unwrap() > 0);
+    assert_eq!(farmer.iter_farms().await.count(), 2);
+
+    farmer.remove_farm(&plot_dir).await.unwrap();
+    assert!(farmer.remove_farm(&other_plot_dir).await.is_err());
+    assert_eq!(farmer.iter_farms().await.count(), 1);
+
+    farmer.close().await;
+    node.close().await;
+}
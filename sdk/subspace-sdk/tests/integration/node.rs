@@ -199,3 +199,38 @@ async fn fetch_block_author() {
     .await
     .unwrap();
 }
+
+#[tokio::test(flavor = "multi_thread")]
+#[cfg_attr(any(tarpaulin, not(target_os = "linux")), ignore = "Slow tests are run only on linux")]
+async fn block_timestamp() {
+    crate::common::setup();
+
+    tokio::time::timeout(std::time::Duration::from_secs(30 * 60), async {
+        let number_of_sectors = 10;
+        let pieces_in_sector = 50u16;
+        let sector_size = subspace_farmer_components::sector::sector_size(pieces_in_sector as _);
+        let space_pledged = sector_size * number_of_sectors;
+
+        let node = Node::dev().build(false).await;
+        let farmer = Farmer::dev()
+            .pieces_in_sector(pieces_in_sector)
+            .build(&node, ByteSize::b(space_pledged as u64))
+            .await;
+
+        let block = node.subscribe_new_heads().await.unwrap().skip(1).take(1).next().await.unwrap();
+        let now =
+            std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_millis()
+                as u64;
+        let timestamp = node.block_timestamp(block.hash).await.unwrap().unwrap();
+        // the block is imported just now, unlike its slot, which is counted from
+        // the genesis
+        assert!(now.abs_diff(timestamp) < 60 * 1000, "timestamp {timestamp} is not close to {now}");
+        let slot: u64 = block.pre_digest.unwrap().slot().into();
+        assert_ne!(timestamp / 1000, slot);
+
+        farmer.close().await;
+        node.close().await;
+    })
+    .await
+    .unwrap();
+}
@@ -72,6 +72,10 @@ pub struct Base {
     #[builder(default)]
     #[serde(default, skip_serializing_if = "sdk_utils::is_default")]
     pub telemetry: Vec<(Multiaddr, u8)>,
+    /// Prometheus exporter settings, disabled if `None`
+    #[builder(setter(into, strip_option), default)]
+    #[serde(default, skip_serializing_if = "sdk_utils::is_default")]
+    pub prometheus: Option<Prometheus>,
 }
 
 #[doc(hidden)]
@@ -120,6 +124,8 @@ macro_rules! derive_base {
             informant_enable_color: bool,
             /// Additional telemetry endpoints
             telemetry: Vec<(sdk_utils::Multiaddr, u8)>,
+            /// Prometheus exporter settings
+            prometheus: $crate::Prometheus,
         });
     }
 }
@@ -157,6 +163,7 @@ impl Base {
             network,
             informant_enable_color,
             telemetry,
+            prometheus,
         } = self;
 
         let base_path = BasePath::new(directory.as_ref());
@@ -224,7 +231,8 @@ impl Base {
                 methods: rpc_methods.into(),
                 max_subscriptions_per_connection: rpc_max_subs_per_conn.unwrap_or(100),
             },
-            prometheus_listen_on: None,
+            // DSN and farmer metrics are served by the same exporter
+            prometheus_listen_on: prometheus.map(|prometheus| prometheus.listen_on()),
             telemetry_endpoints: Some(telemetry_endpoints),
             force_authoring,
             chain_spec: Box::new(chain_spec),
@@ -310,6 +318,40 @@ impl RpcBuilder {
     }
 }
 
+/// Node prometheus exporter builder
+#[derive(Debug, Clone, Derivative, Builder, Deserialize, Serialize, PartialEq, Eq)]
+#[derivative(Default)]
+#[builder(pattern = "owned", build_fn(private, name = "_build"), name = "PrometheusBuilder")]
+#[non_exhaustive]
+pub struct Prometheus {
+    /// Prometheus address, overrides `external`
+    #[builder(setter(strip_option), default)]
+    #[serde(default, skip_serializing_if = "sdk_utils::is_default")]
+    pub addr: Option<IpAddr>,
+    /// Prometheus port, `9615` if `None`
+    #[builder(setter(strip_option), default)]
+    #[serde(default, skip_serializing_if = "sdk_utils::is_default")]
+    pub port: Option<u16>,
+    /// Listen on all interfaces, instead of only on localhost
+    #[builder(default)]
+    #[serde(default, skip_serializing_if = "sdk_utils::is_default")]
+    pub external: bool,
+}
+
+impl Prometheus {
+    const DEFAULT_PORT: u16 = 9615;
+
+    /// Address the exporter listens on
+    pub fn listen_on(&self) -> SocketAddr {
+        let addr = self.addr.unwrap_or(if self.external {
+            IpAddr::V4(Ipv4Addr::UNSPECIFIED)
+        } else {
+            IpAddr::V4(Ipv4Addr::LOCALHOST)
+        });
+        SocketAddr::new(addr, self.port.unwrap_or(Self::DEFAULT_PORT))
+    }
+}
+
 /// Node network builder
 #[derive(Debug, Default, Clone, Builder, Deserialize, Serialize, PartialEq)]
 #[builder(pattern = "owned", build_fn(private, name = "_build"), name = "NetworkBuilder")]
@@ -360,4 +402,4 @@ impl NetworkBuilder {
     }
 }
 
-sdk_utils::generate_builder!(Base, Rpc, Network);
+sdk_utils::generate_builder!(Base, Rpc, Network, Prometheus);
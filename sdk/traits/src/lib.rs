@@ -30,9 +30,9 @@ pub trait Farmer {
 }
 
 /// Trait which abstracts node for farmer
+///
+/// Implemented both by the in-process node and by nodes reached over RPC
 pub trait Node {
-    /// Client for aux store for DSN
-    type Client: sc_client_api::AuxStore + Send + Sync + 'static;
     /// Proof of space table type
     type Table: subspace_proof_of_space::Table;
     /// Rpc implementation
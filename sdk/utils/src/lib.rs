@@ -124,6 +124,23 @@ impl Rpc {
             None => Ok(vec![]),
         }
     }
+
+    /// Get the time set by the timestamp inherent of the block, in
+    /// milliseconds since the unix epoch
+    pub async fn get_timestamp<H>(&self, block: H) -> anyhow::Result<Option<u64>>
+    where
+        H: Send + Sync + 'static + serde::ser::Serialize + serde::de::DeserializeOwned,
+    {
+        match self
+            .get_storage(StorageKey::timestamp(), Some(block))
+            .await
+            .context("Failed to get timestamp from storage")?
+        {
+            Some(sp_storage::StorageData(timestamp)) =>
+                u64::decode(&mut timestamp.as_ref()).map(Some).context("Failed to decode timestamp"),
+            None => Ok(None),
+        }
+    }
 }
 
 #[async_trait::async_trait]
@@ -734,6 +751,11 @@ impl StorageKey {
     pub fn events() -> Self {
         Self::new(["System", "Events"])
     }
+
+    /// Storage key for the time of the current block
+    pub fn timestamp() -> Self {
+        Self::new(["Timestamp", "Now"])
+    }
 }
 
 impl Rpc {